@@ -0,0 +1,186 @@
+//! Upstream endpoints, overridable to use a mirror.
+//!
+//! Each endpoint comes from the first of its environment variable, the
+//! `[mirror]` section of the config file, and the upstream default.
+
+use snafu::ResultExt;
+use url::Url;
+
+use crate::config::MirrorConfig;
+use crate::constants::{
+    WASMEDGEUP_GH_RELEASES_API, WASMEDGE_GH_RELEASE_TAG_API, WASMEDGE_GIT_URL,
+    WASMEDGE_RELEASE_BASE_URL,
+};
+use crate::prelude::*;
+
+/// Environment variable overriding the release download base URL.
+pub const DIST_SERVER_ENV: &str = "WASMEDGEUP_DIST_SERVER";
+/// Environment variable overriding the git remote releases are listed from.
+pub const GIT_URL_ENV: &str = "WASMEDGEUP_GIT_URL";
+/// Environment variable overriding the release metadata API.
+pub const RELEASE_API_ENV: &str = "WASMEDGEUP_RELEASE_API";
+/// Environment variable overriding where `self update` looks for releases.
+pub const SELF_UPDATE_API_ENV: &str = "WASMEDGEUP_SELF_UPDATE_API";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    /// Git remote whose tags list the available releases.
+    pub git_url: String,
+    /// Base URL holding `<tag>/<archive>` and `<tag>/SHA256SUM`.
+    pub dist_server: Url,
+    /// Release metadata endpoint, queried as `<release_api>/<tag>`.
+    pub release_api: Url,
+    /// wasmedgeup's own releases, queried as `<self_update_api>/latest`.
+    pub self_update_api: Url,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            git_url: WASMEDGE_GIT_URL.to_string(),
+            dist_server: Url::parse(WASMEDGE_RELEASE_BASE_URL)
+                .expect("WASMEDGE_RELEASE_BASE_URL must be a valid URL"),
+            release_api: Url::parse(WASMEDGE_GH_RELEASE_TAG_API)
+                .expect("WASMEDGE_GH_RELEASE_TAG_API must be a valid URL"),
+            self_update_api: Url::parse(WASMEDGEUP_GH_RELEASES_API)
+                .expect("WASMEDGEUP_GH_RELEASES_API must be a valid URL"),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Resolve the endpoints from the environment and the `[mirror]` config.
+    pub fn resolve(mirror: &MirrorConfig) -> Result<Self> {
+        Self::resolve_with(mirror, |name| std::env::var(name).ok())
+    }
+
+    /// The endpoints set by the `[mirror]` config alone, failing on an
+    /// invalid URL. Used to check a setting before it is saved.
+    pub fn from_config(mirror: &MirrorConfig) -> Result<Self> {
+        Self::resolve_with(mirror, |_| None)
+    }
+
+    fn resolve_with(mirror: &MirrorConfig, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        // The setting is reported alongside an invalid value.
+        let pick = |var: &str, key: &str, configured: &Option<String>| {
+            env(var)
+                .filter(|v| !v.is_empty())
+                .map(|v| (var.to_string(), v))
+                .or_else(|| configured.clone().map(|v| (format!("mirror.{key}"), v)))
+        };
+        let parse = |(setting, url): (String, String)| {
+            let parsed = Url::parse(&url);
+            match parsed {
+                Ok(u) if u.cannot_be_a_base() => {
+                    Err(url::ParseError::RelativeUrlWithCannotBeABaseBase)
+                }
+                other => other,
+            }
+            .context(InvalidEndpointSnafu { setting, url })
+        };
+
+        let mut endpoints = Self::default();
+        if let Some((_, url)) = pick(GIT_URL_ENV, "git_url", &mirror.git_url) {
+            endpoints.git_url = url;
+        }
+        if let Some(found) = pick(DIST_SERVER_ENV, "dist_server", &mirror.dist_server) {
+            endpoints.dist_server = parse(found)?;
+        }
+        if let Some(found) = pick(RELEASE_API_ENV, "release_api", &mirror.release_api) {
+            endpoints.release_api = parse(found)?;
+        }
+        if let Some(found) = pick(
+            SELF_UPDATE_API_ENV,
+            "self_update_api",
+            &mirror.self_update_api,
+        ) {
+            endpoints.self_update_api = parse(found)?;
+        }
+        if endpoints != Self::default() {
+            tracing::debug!(?endpoints, "Using mirror endpoints");
+        }
+        Ok(endpoints)
+    }
+
+    /// URL of `file_name` published with release `tag`.
+    pub fn release_file_url(&self, tag: &str, file_name: &str) -> Url {
+        join(&self.dist_server, &[tag, file_name])
+    }
+
+    /// URL of the metadata for release `tag`.
+    pub fn release_api_url(&self, tag: &str) -> Url {
+        join(&self.release_api, &[tag])
+    }
+
+    /// URL of the metadata for the latest wasmedgeup release.
+    pub fn self_update_url(&self) -> Url {
+        join(&self.self_update_api, &["latest"])
+    }
+}
+
+fn join(base: &Url, segments: &[&str]) -> Url {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .expect("endpoints are validated to be base URLs")
+        .pop_if_empty()
+        .extend(segments);
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_point_at_github() {
+        let endpoints = Endpoints::resolve_with(&MirrorConfig::default(), |_| None).unwrap();
+        assert_eq!(endpoints, Endpoints::default());
+        assert_eq!(
+            endpoints.release_file_url("0.14.1", "SHA256SUM").as_str(),
+            "https://github.com/WasmEdge/WasmEdge/releases/download/0.14.1/SHA256SUM"
+        );
+        assert_eq!(
+            endpoints.release_api_url("0.14.1").as_str(),
+            "https://api.github.com/repos/WasmEdge/WasmEdge/releases/tags/0.14.1"
+        );
+    }
+
+    #[test]
+    fn env_overrides_config() {
+        let mirror = MirrorConfig {
+            dist_server: Some("http://config.local/dl/".to_string()),
+            git_url: Some("http://config.local/WasmEdge.git".to_string()),
+            ..Default::default()
+        };
+        let endpoints = Endpoints::resolve_with(&mirror, |name| {
+            (name == DIST_SERVER_ENV).then(|| "http://env.local/dl".to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            endpoints.release_file_url("0.14.1", "a.tar.gz").as_str(),
+            "http://env.local/dl/0.14.1/a.tar.gz"
+        );
+        assert_eq!(endpoints.git_url, "http://config.local/WasmEdge.git");
+        assert_eq!(endpoints.release_api, Endpoints::default().release_api);
+
+        let endpoints = Endpoints::resolve_with(&mirror, |_| None).unwrap();
+        // A trailing slash on the base does not produce an empty segment.
+        assert_eq!(
+            endpoints.release_file_url("0.14.1", "a.tar.gz").as_str(),
+            "http://config.local/dl/0.14.1/a.tar.gz"
+        );
+    }
+
+    #[test]
+    fn invalid_url_names_the_setting() {
+        let mirror = MirrorConfig {
+            release_api: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        let err = Endpoints::resolve_with(&mirror, |_| None).unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidEndpoint { setting, .. } if setting == "mirror.release_api"),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! A `gix` HTTP backend on top of wasmedgeup's own reqwest client.
+//!
+//! gix's bundled reqwest backend builds its own client and ignores the
+//! proxy and CA settings it is given, so listing releases from the git
+//! remote would bypass `--proxy` and `--cacert`. This backend sends the
+//! requests through a client built from the same [`HttpClientConfig`] as
+//! every other request.
+//!
+//! Only the ref advertisement is ever needed, which protocol V1 returns
+//! from a single `GET`; `POST` is therefore not supported.
+
+use std::io::{BufReader, Cursor, Sink};
+
+use gix::protocol::transport::client::blocking_io::http::{
+    Error, GetResponse, Http, PostBodyDataKind, PostResponse,
+};
+use reqwest::blocking::{Client, Response};
+
+pub(crate) struct BlockingHttp {
+    client: Client,
+}
+
+impl BlockingHttp {
+    pub(crate) fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Http for BlockingHttp {
+    type Headers = Cursor<Vec<u8>>;
+    type ResponseBody = BufReader<Response>;
+    type PostBody = Sink;
+
+    fn get(
+        &mut self,
+        url: &str,
+        _base_url: &str,
+        headers: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<GetResponse<Self::Headers, Self::ResponseBody>, Error> {
+        let mut request = self.client.get(url);
+        for line in headers {
+            if let Some((name, value)) = line.as_ref().split_once(':') {
+                request = request.header(name.trim(), value.trim());
+            }
+        }
+        let response = request.send().map_err(|e| Error::InitHttpClient {
+            source: Box::new(e),
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Detail {
+                description: format!("Received HTTP status {}", status.as_str()),
+            });
+        }
+        let mut head = Vec::new();
+        for (name, value) in response.headers() {
+            head.extend_from_slice(name.as_str().as_bytes());
+            head.push(b':');
+            head.extend_from_slice(value.as_bytes());
+            head.push(b'\n');
+        }
+        Ok(GetResponse {
+            headers: Cursor::new(head),
+            body: BufReader::new(response),
+        })
+    }
+
+    fn post(
+        &mut self,
+        url: &str,
+        _base_url: &str,
+        _headers: impl IntoIterator<Item = impl AsRef<str>>,
+        _body: PostBodyDataKind,
+    ) -> Result<PostResponse<Self::Headers, Self::ResponseBody, Self::PostBody>, Error> {
+        Err(Error::Detail {
+            description: format!("POST {url} is not supported when listing releases"),
+        })
+    }
+
+    fn configure(
+        &mut self,
+        _config: &dyn std::any::Any,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Authentication and rate-limit handling for the GitHub REST API.
+//!
+//! Unauthenticated API calls share a small per-IP rate limit, which CI
+//! runners behind one NAT exhaust quickly. A token from `GITHUB_TOKEN`,
+//! `GH_TOKEN` or the `github_token` config key raises it. The token is only
+//! sent to `api.github.com`, never to mirrors or download hosts.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{RequestBuilder, Response};
+use url::Url;
+
+use crate::http::{is_rate_limited, server_wait};
+use crate::prelude::*;
+
+/// Environment variables holding a GitHub token, in order of precedence.
+pub const TOKEN_ENVS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Host the token is sent to.
+const API_HOST: &str = "api.github.com";
+
+/// A GitHub token; kept out of `Debug` output so it never reaches the logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct GitHubToken(String);
+
+impl GitHubToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// The first non-empty token from [`TOKEN_ENVS`], else `configured`.
+    pub fn resolve(configured: Option<&str>) -> Option<Self> {
+        TOKEN_ENVS
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .chain(configured.map(str::to_string))
+            .map(|t| t.trim().to_string())
+            .find(|t| !t.is_empty())
+            .map(Self)
+    }
+
+    /// Attach the token to `request` if it goes to the GitHub API.
+    pub fn authorize(&self, url: &Url, request: RequestBuilder) -> RequestBuilder {
+        if url.host_str() == Some(API_HOST) {
+            request.bearer_auth(&self.0)
+        } else {
+            request
+        }
+    }
+}
+
+impl fmt::Debug for GitHubToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GitHubToken(***)")
+    }
+}
+
+/// [`Error::GitHubRateLimited`] if `response` reports an exhausted rate
+/// limit whose reset time is known.
+pub fn rate_limit_error(response: &Response) -> Option<Error> {
+    if !is_rate_limited(response.status(), response.headers()) {
+        return None;
+    }
+    let wait = server_wait(response.headers())?;
+    Some(Error::GitHubRateLimited {
+        resets: describe_reset(SystemTime::now() + wait, wait),
+    })
+}
+
+/// "at 14:05 UTC (in 23 minutes)".
+fn describe_reset(at: SystemTime, wait: Duration) -> String {
+    let secs = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() % 86_400;
+    let minutes = wait.as_secs().div_ceil(60);
+    format!(
+        "at {:02}:{:02} UTC (in {minutes} minute{})",
+        secs / 3600,
+        secs % 3600 / 60,
+        if minutes == 1 { "" } else { "s" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_only_sent_to_the_api_host() {
+        let token = GitHubToken::new("secret");
+        let client = reqwest::Client::new();
+        for (url, sent) in [
+            (
+                "https://api.github.com/repos/WasmEdge/WasmEdge/releases/tags/0.14.1",
+                true,
+            ),
+            (
+                "https://github.com/WasmEdge/WasmEdge/releases/download/0.14.1/x.tar.gz",
+                false,
+            ),
+            ("https://mirror.local/api/releases/tags/0.14.1", false),
+        ] {
+            let url = Url::parse(url).unwrap();
+            let request = token
+                .authorize(&url, client.get(url.clone()))
+                .build()
+                .unwrap();
+            assert_eq!(
+                request.headers().get("authorization").is_some(),
+                sent,
+                "{url}"
+            );
+        }
+        assert_eq!(format!("{token:?}"), "GitHubToken(***)");
+    }
+
+    #[test]
+    fn describes_reset_time() {
+        let at = UNIX_EPOCH + Duration::from_secs(86_400 * 3 + 14 * 3600 + 5 * 60);
+        assert_eq!(
+            describe_reset(at, Duration::from_secs(23 * 60 - 10)),
+            "at 14:05 UTC (in 23 minutes)"
+        );
+        assert_eq!(
+            describe_reset(at, Duration::from_secs(30)),
+            "at 14:05 UTC (in 1 minute)"
+        );
+    }
+}
@@ -1,26 +1,37 @@
 use std::{
     fmt::Write,
     io::{Read, Seek},
-    path::Path,
+    path::{Path, PathBuf},
     sync::OnceLock,
 };
 
 use crate::{
+    cache::DownloadCache,
     constants::{
         CHECKSUM_FILE_NAME, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS,
-        DOWNLOAD_BUFFER_SIZE, WASMEDGE_GH_RELEASE_TAG_API, WASMEDGE_GIT_URL,
-        WASMEDGE_RELEASE_BASE_URL,
+        DEFAULT_RETRIES, DOWNLOAD_BUFFER_SIZE,
     },
-    http::HttpClientConfig,
+    http::{HttpClientConfig, RetryPolicy},
+    metadata_cache::MetadataCache,
     prelude::*,
+    signature::{SignatureVerifier, SIGNATURE_FILE_NAME},
     target::{TargetArch, TargetOS},
 };
+pub mod endpoints;
+mod git_http;
+pub mod github;
 pub mod releases;
-use indicatif::{ProgressBar, ProgressState, ProgressStyle};
-pub use releases::ReleasesFilter;
-
-use reqwest::{Client, Response};
+pub use endpoints::Endpoints;
+pub use github::GitHubToken;
+use indicatif::{MultiProgress, ProgressBar, ProgressState, ProgressStyle};
+pub use releases::{ReleasesFilter, VersionSpec};
+
+use reqwest::{
+    header::{ACCEPT_RANGES, CONTENT_RANGE, ETAG, IF_RANGE, RANGE},
+    Client, RequestBuilder, Response, StatusCode,
+};
 use semver::{Comparator, Prerelease, Version, VersionReq};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use snafu::ResultExt;
 use tempfile::NamedTempFile;
@@ -36,14 +47,111 @@ pub struct WasmEdgeApiClient {
     pub connect_timeout: u64,
     /// Request timeout in seconds
     pub request_timeout: u64,
+    /// How many times a failed network operation is retried
+    pub retries: u32,
+    /// Persistent archive cache; `None` downloads every archive afresh
+    pub cache: Option<DownloadCache>,
+    /// Where releases are listed and downloaded from
+    pub endpoints: Endpoints,
+    /// Shared display for concurrent downloads; `None` draws each progress
+    /// bar on its own
+    pub progress: Option<MultiProgress>,
+    /// Token sent with GitHub API requests to raise the rate limit
+    pub github_token: Option<GitHubToken>,
+    /// Cached release listings; `None` always asks the remote
+    pub metadata: Option<MetadataCache>,
+    /// Proxy for every request; see [`HttpClientConfig::proxy`]
+    pub proxy: Option<String>,
+    /// Extra trusted root certificates; see [`HttpClientConfig::cacert`]
+    pub cacert: Option<PathBuf>,
+    /// Checks release `SHA256SUM` files against their signatures
+    pub signatures: SignatureVerifier,
 }
 
 impl WasmEdgeApiClient {
-    fn http_client(&self) -> Result<Client> {
+    fn http_config(&self) -> HttpClientConfig {
         HttpClientConfig::new()
             .with_connect_timeout(self.connect_timeout)
             .with_request_timeout(self.request_timeout)
-            .build()
+            .with_retries(self.retries)
+            .with_proxy(self.proxy.clone())
+            .with_cacert(self.cacert.clone())
+    }
+
+    pub(crate) fn http_client(&self) -> Result<Client> {
+        self.http_config().build()
+    }
+
+    pub(crate) fn retry_policy(&self) -> RetryPolicy {
+        self.http_config().retry_policy()
+    }
+
+    /// Send `request` under the client's [`RetryPolicy`].
+    pub(crate) async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        self.retry_policy().send(request).await
+    }
+
+    /// GET `url` from the GitHub API, authenticated when a token is set. An
+    /// exhausted rate limit is reported as [`Error::GitHubRateLimited`].
+    pub(crate) async fn github_api_get(
+        &self,
+        url: Url,
+        resource: &'static str,
+    ) -> Result<Response> {
+        let mut request = self.http_client()?.get(url.clone());
+        if let Some(token) = &self.github_token {
+            request = token.authorize(&url, request);
+        }
+        let response = self
+            .send(request)
+            .await
+            .context(RequestSnafu { resource })?;
+        match github::rate_limit_error(&response) {
+            Some(e) => Err(e),
+            None => Ok(response),
+        }
+    }
+
+    /// List releases, from the metadata cache when it has a usable entry,
+    /// else from the git remote, retrying failed connections.
+    async fn fetch_releases(&self, filter: ReleasesFilter) -> Result<Vec<Version>> {
+        let git_url = &self.endpoints.git_url;
+        let cached = match &self.metadata {
+            Some(metadata) => metadata.releases(git_url)?,
+            None => None,
+        };
+        let all = match cached {
+            Some(tags) => tags.iter().filter_map(|t| Version::parse(t).ok()).collect(),
+            None => {
+                let all = self
+                    .retry_policy()
+                    .retry(
+                        |e| {
+                            matches!(
+                                e,
+                                Error::Git {
+                                    resource: "remote/connect" | "remote/ref_map",
+                                    ..
+                                }
+                            )
+                        },
+                        || {
+                            fetch_releases_blocking(
+                                git_url,
+                                ReleasesFilter::All,
+                                self.http_config(),
+                            )
+                        },
+                    )
+                    .await?;
+                if let Some(metadata) = &self.metadata {
+                    let tags: Vec<String> = all.iter().map(Version::to_string).collect();
+                    metadata.store_releases(git_url, &tags);
+                }
+                all
+            }
+        };
+        Ok(all.into_iter().filter(|v| filter.matches(v)).collect())
     }
 
     /// Fetch the first `num_releases` WasmEdge versions from the upstream git
@@ -54,113 +162,150 @@ impl WasmEdgeApiClient {
         filter: ReleasesFilter,
         num_releases: usize,
     ) -> Result<Vec<Version>> {
-        let releases = fetch_releases_blocking(filter).await?;
+        let releases = self.fetch_releases(filter).await?;
         Ok(releases.into_iter().take(num_releases).collect())
     }
 
     /// Fetch the newest stable WasmEdge release via a `spawn_blocking` wrapper
     /// around the blocking git2 remote call.
     pub async fn latest_release(&self) -> Result<Version> {
-        let releases = fetch_releases_blocking(ReleasesFilter::Stable).await?;
+        let releases = self.fetch_releases(ReleasesFilter::Stable).await?;
         releases.into_iter().next().ok_or(Error::NoReleasesFound)
     }
 
-    /// Parse `version` as semver, or resolve `"latest"` via `latest_release`.
+    /// Resolve `version` as a [`VersionSpec`]. Exact versions are returned
+    /// as is; channels and requirements pick the newest matching release.
     pub async fn resolve_version(&self, version: &str) -> Result<Version> {
-        if version == "latest" {
-            self.latest_release().await
-        } else {
-            Version::parse(version).context(SemVerSnafu {})
+        let spec = VersionSpec::parse(version)?;
+        match spec {
+            VersionSpec::Exact(version) => Ok(version),
+            VersionSpec::Stable => self.latest_release().await,
+            _ => {
+                let releases = self.fetch_releases(ReleasesFilter::All).await?;
+                spec.select(&releases).ok_or(Error::NoMatchingRelease {
+                    requirement: version.to_string(),
+                })
+            }
         }
     }
 
+    /// Download the runtime `asset` into a temporary file in `tmpdir`.
+    ///
+    /// With an `expected` checksum the archive is verified, and served from
+    /// or stored in the download cache; see [`Self::download_to_path`].
     pub async fn download_asset(
         &self,
         asset: &Asset,
         tmpdir: impl AsRef<Path>,
         no_progress: bool,
+        expected: Option<&str>,
     ) -> Result<NamedTempFile> {
-        let url = asset.url()?;
+        let url = asset.url(&self.endpoints)?;
         tracing::debug!(%url, "Starting download for asset");
 
-        let client = self.http_client()?;
-        let response = client.get(url).send().await.context(RequestSnafu {
-            resource: "asset download",
-        })?;
-
         let named = NamedTempFile::new_in(tmpdir)?;
-        let mut async_file = OpenOptions::new().write(true).open(named.path()).await?;
-
-        stream_response_to_file(no_progress, response, &mut async_file).await?;
-        drop(async_file);
+        self.download_verified(
+            url,
+            named.path(),
+            &asset.archive_name,
+            no_progress,
+            "asset download",
+            expected,
+        )
+        .await?;
 
         Ok(named)
     }
 
-    /// Fetch the SHA256 checksum for `archive_name` from the SHA256SUM file
-    /// published alongside release `tag`.
+    /// Fetch the SHA256SUM file published alongside release `tag`, or
+    /// `None` if the release has none.
     ///
     /// The WasmEdge release process publishes a single SHA256SUM file per
     /// release tag that lists hashes for both runtime archives and plugin
-    /// archives, so the same lookup serves both installer paths.
-    pub async fn get_archive_checksum(&self, tag: &str, archive_name: &str) -> Result<String> {
-        let mut url = Url::parse(WASMEDGE_RELEASE_BASE_URL)
-            .expect("WASMEDGE_RELEASE_BASE_URL must be a valid URL");
+    /// archives, so the same file serves both installer paths.
+    ///
+    /// The content is checked against the release's signature first; see
+    /// [`SignatureVerifier`].
+    pub async fn release_checksums(&self, tag: &str) -> Result<Option<String>> {
+        let signed = self.signed_release_checksums(tag).await?;
+        Ok(signed.map(|(content, _)| content))
+    }
+
+    /// Like [`Self::release_checksums`], also returning the signature the
+    /// content was verified against, if one was fetched.
+    pub async fn signed_release_checksums(
+        &self,
+        tag: &str,
+    ) -> Result<Option<(String, Option<String>)>> {
+        let Some(content) = self
+            .fetch_release_file(tag, CHECKSUM_FILE_NAME, "checksums")
+            .await?
+        else {
+            return Ok(None);
+        };
+        tracing::debug!(
+            lines = content.lines().count(),
+            file = CHECKSUM_FILE_NAME,
+            "Got checksum file content"
+        );
 
-        url.path_segments_mut()
-            .expect("base is valid URL")
-            .extend(&[tag, CHECKSUM_FILE_NAME]);
+        let signature = if self.signatures.wants_signature() {
+            self.fetch_release_file(tag, SIGNATURE_FILE_NAME, "signature")
+                .await?
+        } else {
+            None
+        };
+        let url = self.endpoints.release_file_url(tag, CHECKSUM_FILE_NAME);
+        self.signatures
+            .verify(url.as_str(), content.as_bytes(), signature.as_deref())?;
+        Ok(Some((content, signature)))
+    }
 
-        tracing::debug!(%url, CHECKSUM_FILE_NAME, "Trying checksum file");
+    /// Fetch `file` from release `tag` as text, or `None` if the release
+    /// has no such file.
+    async fn fetch_release_file(
+        &self,
+        tag: &str,
+        file: &str,
+        resource: &'static str,
+    ) -> Result<Option<String>> {
+        let url = self.endpoints.release_file_url(tag, file);
+        tracing::debug!(%url, file, "Trying release file");
 
         let client = self.http_client()?;
-        let response = client.get(url).send().await.context(RequestSnafu {
-            resource: "checksums",
-        })?;
+        let response = self
+            .send(client.get(url))
+            .await
+            .context(RequestSnafu { resource })?;
 
-        // 404/410 means the SHA256SUM file genuinely doesn't exist for this
-        // tag — surface ChecksumNotFound so callers can react. Other non-2xx
-        // statuses (403 rate-limit, 5xx outage) are operational errors and
-        // propagate via Error::Request so the user sees the actual status
-        // instead of a misleading "checksum not found".
+        // 404/410 means the file genuinely doesn't exist for this tag —
+        // report it as missing so callers can react. Other non-2xx statuses
+        // (403 rate-limit, 5xx outage) are operational errors and propagate
+        // via Error::Request so the user sees the actual status instead of a
+        // misleading "checksum not found".
         if matches!(
             response.status(),
             reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
         ) {
-            tracing::debug!(
-                status = %response.status(),
-                file = CHECKSUM_FILE_NAME,
-                "Checksum file not found"
-            );
-            return Err(Error::ChecksumNotFound {
-                version: tag.to_string(),
-                asset: archive_name.to_string(),
-            });
+            tracing::debug!(status = %response.status(), file, "Release file not found");
+            return Ok(None);
         }
-        let response = response.error_for_status().context(RequestSnafu {
-            resource: "checksums",
-        })?;
+        let response = response
+            .error_for_status()
+            .context(RequestSnafu { resource })?;
 
-        let content = response.text().await.context(RequestSnafu {
-            resource: "checksums",
-        })?;
+        let content = response.text().await.context(RequestSnafu { resource })?;
+        Ok(Some(content))
+    }
 
-        tracing::debug!(
-            lines = content.lines().count(),
-            file = CHECKSUM_FILE_NAME,
-            "Got checksum file content"
-        );
+    /// Fetch the SHA256 checksum for `archive_name` from the SHA256SUM file
+    /// published alongside release `tag`.
+    pub async fn get_archive_checksum(&self, tag: &str, archive_name: &str) -> Result<String> {
+        let content = self.release_checksums(tag).await?.unwrap_or_default();
 
-        for line in content.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() == 2 && parts[1] == archive_name {
-                tracing::debug!(
-                    checksum = parts[0],
-                    archive = archive_name,
-                    "checksum match"
-                );
-                return Ok(parts[0].to_string());
-            }
+        if let Some(checksum) = find_checksum(&content, archive_name) {
+            tracing::debug!(checksum, archive = archive_name, "checksum match");
+            return Ok(checksum.to_string());
         }
 
         tracing::error!(tag, archive = archive_name, "No checksum entry for archive");
@@ -181,18 +326,7 @@ impl WasmEdgeApiClient {
     }
 
     pub async fn verify_file_checksum(file: &mut std::fs::File, expected: &str) -> Result<()> {
-        let mut hasher = Sha256::new();
-        let mut buffer = vec![0; DOWNLOAD_BUFFER_SIZE];
-
-        loop {
-            let count = file.read(&mut buffer)?;
-            if count == 0 {
-                break;
-            }
-            hasher.update(&buffer[..count]);
-        }
-
-        let actual = hex::encode(hasher.finalize());
+        let actual = sha256_hex(file)?;
         if actual != expected {
             return Err(Error::ChecksumMismatch {
                 expected: expected.to_string(),
@@ -212,46 +346,300 @@ impl WasmEdgeApiClient {
     /// this call — pass something descriptive of *what* the caller is
     /// downloading (e.g. `"plugin download"`) so user-facing errors stay
     /// specific instead of collapsing every download into a generic label.
+    ///
+    /// When `expected` is set the downloaded file is checked against it.
+    /// If the client has a download cache, a cached archive with that
+    /// checksum (keyed by the URL's file name) is copied instead of hitting
+    /// the network, and fresh downloads are added to the cache.
     pub async fn download_to_path(
         &self,
         url: Url,
         to: &Path,
         no_progress: bool,
         resource: &'static str,
+        expected: Option<&str>,
+    ) -> Result<()> {
+        let archive_name = url_file_name(&url);
+        self.download_verified(url, to, &archive_name, no_progress, resource, expected)
+            .await
+    }
+
+    async fn download_verified(
+        &self,
+        url: Url,
+        to: &Path,
+        archive_name: &str,
+        no_progress: bool,
+        resource: &'static str,
+        expected: Option<&str>,
+    ) -> Result<()> {
+        let cache = self.cache.as_ref().zip(expected);
+        if let Some((cache, sha256)) = cache {
+            if let Some(hit) = cache.get(sha256, archive_name)? {
+                tracing::info!(archive = archive_name, cached = %hit.display(), "Using cached archive");
+                tokio::fs::copy(&hit, to)
+                    .await
+                    .map_err(|source| Error::Io {
+                        action: "copy cached archive".to_string(),
+                        path: to.display().to_string(),
+                        source,
+                    })?;
+                return Ok(());
+            }
+        }
+
+        // With a cache and a known checksum the download goes to a partial
+        // file in the cache first, so an interrupted download can be resumed
+        // by the next attempt. A cache that cannot be written must not fail
+        // the install, and a partial file another process is writing must
+        // not be appended to, so the download then goes straight to `to`.
+        let partial = cache.and_then(|(cache, sha256)| {
+            let locked = cache.partial_path(sha256, archive_name).and_then(|partial| {
+                match cache.lock_partial(&partial)? {
+                    Some(lock) => open_partial(&partial).map(|()| Some((partial, lock))),
+                    None => Ok(None),
+                }
+            });
+            match locked {
+                Ok(Some((partial, lock))) => Some((cache, sha256, partial, lock)),
+                Ok(None) => {
+                    tracing::info!(archive = archive_name, "Another wasmedgeup process is downloading this archive into the cache, downloading without it");
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, archive = archive_name, "Download cache is not writable, downloading without it");
+                    None
+                }
+            }
+        });
+        // The lock is held until the download is in the cache.
+        let Some((cache, sha256, partial, _lock)) = partial else {
+            self.retry_policy()
+                .retry(is_interrupted_download, || {
+                    self.fetch_to_path(url.clone(), to, no_progress, resource)
+                })
+                .await?;
+            if let Some(sha256) = expected {
+                let mut file = std::fs::File::open(to)?;
+                Self::verify_file_checksum(&mut file, sha256)
+                    .await
+                    .inspect_err(
+                        |e| tracing::error!(error = %e.to_string(), "Checksum verification failed"),
+                    )?;
+                tracing::debug!(archive = archive_name, "Checksum verified successfully");
+            }
+            return Ok(());
+        };
+
+        // A download that breaks off is retried from where it stopped, and
+        // only verified once complete.
+        self.retry_policy()
+            .retry(is_interrupted_download, || {
+                self.fetch_resumable(url.clone(), &partial, no_progress, resource)
+            })
+            .await?;
+        let mut file = std::fs::File::open(&partial)?;
+        if let Err(e) = Self::verify_file_checksum(&mut file, sha256).await {
+            tracing::error!(error = %e.to_string(), "Checksum verification failed");
+            drop(file);
+            cache.remove_partial(&partial);
+            return Err(e);
+        }
+        drop(file);
+        tracing::debug!(archive = archive_name, "Checksum verified successfully");
+        let downloaded = match cache.store_partial(&partial, sha256, archive_name) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!(error = %e, archive = archive_name, "Failed to add archive to download cache");
+                partial.clone()
+            }
+        };
+        let copied = tokio::fs::copy(&downloaded, to)
+            .await
+            .map_err(|source| Error::Io {
+                action: "copy downloaded archive".to_string(),
+                path: to.display().to_string(),
+                source,
+            });
+        if downloaded == partial {
+            cache.remove_partial(&partial);
+        }
+        copied?;
+        Ok(())
+    }
+
+    async fn fetch_to_path(
+        &self,
+        url: Url,
+        to: &Path,
+        no_progress: bool,
+        resource: &'static str,
     ) -> Result<()> {
         tracing::debug!(%url, target = %to.display(), %resource, "Starting download to path");
 
+        let name = url_file_name(&url);
         let client = self.http_client()?;
-        let response = client
-            .get(url)
-            .send()
+        let response = self
+            .send(client.get(url))
             .await
             .context(RequestSnafu { resource })?
             .error_for_status()
             .context(RequestSnafu { resource })?;
 
+        let pb = self.progress_bar(&name, response.content_length(), 0, no_progress);
         let mut async_file = OpenOptions::new()
             .create(true)
             .write(true)
             .truncate(true)
             .open(to)
             .await?;
-        stream_response_to_file(no_progress, response, &mut async_file).await?;
+        stream_response_to_file(pb, response, &mut async_file).await?;
         Ok(())
     }
 
+    /// Download `url` into `partial`, resuming from its current length when
+    /// an earlier attempt was interrupted and the server supports it.
+    ///
+    /// The server's strong `ETag` is kept next to the partial file and sent
+    /// as `If-Range`, so a changed artifact is downloaded from scratch
+    /// rather than spliced onto stale bytes. Partial files without a
+    /// recorded `ETag`, e.g. from servers that do not advertise
+    /// `Accept-Ranges: bytes`, are restarted.
+    async fn fetch_resumable(
+        &self,
+        url: Url,
+        partial: &Path,
+        no_progress: bool,
+        resource: &'static str,
+    ) -> Result<()> {
+        let etag_path = crate::cache::partial_etag_path(partial);
+        let offset = std::fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+        let etag = std::fs::read_to_string(&etag_path)
+            .ok()
+            .filter(|etag| offset > 0 && !etag.is_empty());
+        tracing::debug!(%url, target = %partial.display(), offset, %resource, "Starting resumable download");
+
+        let name = url_file_name(&url);
+        let client = self.http_client()?;
+        let mut request = client.get(url);
+        if let Some(etag) = &etag {
+            tracing::info!(archive = %name, offset, "Resuming interrupted download");
+            request = request
+                .header(RANGE, format!("bytes={offset}-"))
+                .header(IF_RANGE, etag.as_str());
+        }
+        let response = self
+            .send(request)
+            .await
+            .context(RequestSnafu { resource })?;
+        if etag.is_some() && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            tracing::debug!(archive = %name, "Partial download is already complete");
+            return Ok(());
+        }
+        let response = response
+            .error_for_status()
+            .context(RequestSnafu { resource })?;
+
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        if resumed {
+            let expected_range = format!("bytes {offset}-");
+            let range_matches = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.starts_with(&expected_range));
+            if !range_matches {
+                let _ = std::fs::remove_file(partial);
+                let _ = std::fs::remove_file(&etag_path);
+                return Err(Error::Io {
+                    action: "resume download".to_string(),
+                    path: partial.display().to_string(),
+                    source: std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "server returned an unexpected byte range",
+                    ),
+                });
+            }
+        } else {
+            let accepts_ranges = response
+                .headers()
+                .get(ACCEPT_RANGES)
+                .is_some_and(|v| v.as_bytes() == b"bytes");
+            let strong_etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|v| v.to_str().ok())
+                .filter(|etag| accepts_ranges && !etag.starts_with("W/"));
+            // Without a recorded ETag the download still succeeds; an
+            // interruption just restarts it.
+            let recorded = strong_etag.is_some_and(|etag| {
+                std::fs::write(&etag_path, etag)
+                    .inspect_err(|e| tracing::warn!(error = %e, path = %etag_path.display(), "Failed to record download ETag"))
+                    .is_ok()
+            });
+            if !recorded {
+                let _ = std::fs::remove_file(&etag_path);
+            }
+        }
+
+        let offset = if resumed { offset } else { 0 };
+        let pb = self.progress_bar(&name, response.content_length(), offset, no_progress);
+        let mut async_file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(partial)
+            .await?;
+        stream_response_to_file(pb, response, &mut async_file).await?;
+        Ok(())
+    }
+
+    /// Progress bar for downloading `size` more bytes of `name` after
+    /// `offset` bytes already on disk, added to the shared display if any.
+    fn progress_bar(
+        &self,
+        name: &str,
+        size: Option<u64>,
+        offset: u64,
+        no_progress: bool,
+    ) -> Option<ProgressBar> {
+        let size = size.filter(|size| !no_progress && *size > 0)?;
+        let pb = download_progress_bar(offset + size);
+        pb.set_position(offset);
+        pb.set_message(name.to_string());
+        Some(match &self.progress {
+            Some(multi) => multi.add(pb),
+            None => pb,
+        })
+    }
+
     /// Fetch plugin asset metadata from the GitHub Releases API for `tag`.
     ///
     /// A 404 (tag doesn't exist or has no published assets) yields an empty
     /// Vec rather than an error — callers treat "no assets" and "tag not
     /// found" the same way. Other non-2xx statuses (403 rate-limit, 5xx
     /// outage, etc.) and JSON parse failures are surfaced as typed errors.
+    ///
+    /// Non-empty lists are kept in the metadata cache.
     pub async fn github_release_assets(&self, tag: &str) -> Result<Vec<PluginAssetInfo>> {
-        let url = format!("{WASMEDGE_GH_RELEASE_TAG_API}/{tag}");
-        let client = self.http_client()?;
-        let resp = client.get(&url).send().await.context(RequestSnafu {
-            resource: "plugin release metadata",
-        })?;
+        let url = self.endpoints.release_api_url(tag);
+        if let Some(metadata) = &self.metadata {
+            if let Some(assets) = metadata.assets(tag, url.as_str())? {
+                return Ok(assets);
+            }
+        }
+        let assets = self.fetch_release_assets(tag, url.clone()).await?;
+        if let Some(metadata) = &self.metadata {
+            if !assets.is_empty() {
+                metadata.store_assets(tag, url.as_str(), &assets);
+            }
+        }
+        Ok(assets)
+    }
+
+    async fn fetch_release_assets(&self, tag: &str, url: Url) -> Result<Vec<PluginAssetInfo>> {
+        let resp = self.github_api_get(url, "plugin release metadata").await?;
         if resp.status() == reqwest::StatusCode::NOT_FOUND {
             tracing::debug!(tag, "release metadata 404 — tag has no published assets");
             return Ok(Vec::new());
@@ -290,12 +678,12 @@ impl WasmEdgeApiClient {
         let Ok(client) = self.http_client() else {
             return false;
         };
-        if let Ok(resp) = client.head(url.clone()).send().await {
+        if let Ok(resp) = self.send(client.head(url.clone())).await {
             if resp.status().is_success() {
                 return true;
             }
         }
-        if let Ok(resp) = client.get(url).send().await {
+        if let Ok(resp) = self.send(client.get(url)).await {
             return resp.status().is_success();
         }
         false
@@ -307,9 +695,33 @@ impl WasmEdgeApiClient {
         Self {
             connect_timeout: DEFAULT_CONNECT_TIMEOUT_SECS,
             request_timeout: DEFAULT_REQUEST_TIMEOUT_SECS,
+            retries: DEFAULT_RETRIES,
+            cache: None,
+            endpoints: Endpoints::default(),
+            progress: None,
+            github_token: None,
+            metadata: None,
+            proxy: None,
+            cacert: None,
+            signatures: SignatureVerifier::default(),
         }
     }
 
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<DownloadCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: MultiProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     pub fn with_connect_timeout(mut self, timeout: u64) -> Self {
         self.connect_timeout = timeout;
         self
@@ -319,6 +731,36 @@ impl WasmEdgeApiClient {
         self.request_timeout = timeout;
         self
     }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with_github_token(mut self, token: Option<GitHubToken>) -> Self {
+        self.github_token = token;
+        self
+    }
+
+    pub fn with_metadata_cache(mut self, metadata: Option<MetadataCache>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    pub fn with_cacert(mut self, cacert: Option<PathBuf>) -> Self {
+        self.cacert = cacert;
+        self
+    }
+
+    pub fn with_signature_verifier(mut self, signatures: SignatureVerifier) -> Self {
+        self.signatures = signatures;
+        self
+    }
 }
 
 impl Default for WasmEdgeApiClient {
@@ -327,22 +769,66 @@ impl Default for WasmEdgeApiClient {
     }
 }
 
-#[tracing::instrument(level = tracing::Level::DEBUG, skip(response, target_file), fields(size = response.content_length()))]
+/// Whether `e` is a download that broke off while the body was streamed.
+/// Failures to send the request are already retried by [`RetryPolicy::send`].
+fn is_interrupted_download(e: &Error) -> bool {
+    matches!(e, Error::Request { resource: "chunk", source } if crate::http::is_transient(source))
+}
+
+/// Open the partial download at `partial`, creating it but keeping any
+/// bytes from an earlier attempt, to check that it can be written.
+fn open_partial(partial: &Path) -> Result<()> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(partial)
+        .map(drop)
+        .map_err(|source| Error::Io {
+            action: "open partial download".to_string(),
+            path: partial.display().to_string(),
+            source,
+        })
+}
+
+/// Last path segment of `url`, i.e. the name of the file it serves.
+fn url_file_name(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut s| s.next_back())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Look up `archive_name` in the contents of a `SHA256SUM` file, whose lines
+/// have the `sha256sum` format `<hex digest>  <file name>`.
+pub fn find_checksum<'a>(content: &'a str, archive_name: &str) -> Option<&'a str> {
+    content.lines().find_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        (parts.len() == 2 && parts[1] == archive_name).then_some(parts[0])
+    })
+}
+
+/// Hex-encoded SHA256 of everything readable from `reader`.
+pub(crate) fn sha256_hex(reader: &mut impl Read) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; DOWNLOAD_BUFFER_SIZE];
+
+    loop {
+        let count = reader.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[tracing::instrument(level = tracing::Level::DEBUG, skip(pb, response, target_file), fields(size = response.content_length()))]
 async fn stream_response_to_file(
-    no_progress: bool,
+    pb: Option<ProgressBar>,
     mut response: Response,
     target_file: &mut File,
 ) -> Result<()> {
-    let content_length = response.content_length().unwrap_or(0);
-
-    let pb = if !no_progress && content_length > 0 {
-        Some(download_progress_bar(
-            response.content_length().unwrap_or_default(),
-        ))
-    } else {
-        None
-    };
-
     while let Some(mut chunk) = response
         .chunk()
         .await
@@ -379,15 +865,8 @@ impl Asset {
         }
     }
 
-    pub fn url(&self) -> Result<Url> {
-        let mut url = Url::parse(WASMEDGE_RELEASE_BASE_URL)
-            .expect("WASMEDGE_RELEASE_BASE_URL must be a valid URL");
-
-        url.path_segments_mut()
-            .expect("base is valid URL")
-            .extend(&[&self.version.to_string(), &self.archive_name]);
-
-        Ok(url)
+    pub fn url(&self, endpoints: &Endpoints) -> Result<Url> {
+        Ok(endpoints.release_file_url(&self.version.to_string(), &self.archive_name))
     }
 
     fn format_archive_name(version: &Version, os: &TargetOS, arch: &TargetArch) -> String {
@@ -461,7 +940,7 @@ fn download_progress_bar(size: u64) -> ProgressBar {
     let pb = ProgressBar::new(size);
     pb.set_style(
         ProgressStyle::with_template(
-            "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})",
+            "{spinner:.green} {msg} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})",
         )
         .expect("progress bar template is valid")
         .with_key("eta", |state: &ProgressState, w: &mut dyn Write| {
@@ -474,8 +953,28 @@ fn download_progress_bar(size: u64) -> ProgressBar {
 }
 
 pub fn latest_installed_version(versions_dir: &Path) -> Result<Option<Version>> {
+    Ok(installed_versions(versions_dir)?.into_iter().next())
+}
+
+/// The newest version installed under `versions_dir` that satisfies
+/// `spec`, a [`VersionSpec`] such as `0.14.1` or `~0.14`. As for `use`,
+/// `latest` is the newest installed version, prereleases included.
+pub fn select_installed_version(versions_dir: &Path, spec: &str) -> Result<Version> {
+    let installed = installed_versions(versions_dir)?;
+    let selected = if spec == "latest" {
+        installed.first().cloned()
+    } else {
+        VersionSpec::parse(spec)?.select(&installed)
+    };
+    selected.ok_or_else(|| Error::VersionNotFound {
+        version: spec.to_string(),
+    })
+}
+
+/// Versions installed under `versions_dir`, sorted newest-first.
+pub fn installed_versions(versions_dir: &Path) -> Result<Vec<Version>> {
     if !versions_dir.exists() {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
     let mut versions = Vec::new();
@@ -491,7 +990,7 @@ pub fn latest_installed_version(versions_dir: &Path) -> Result<Option<Version>>
     }
 
     versions.sort_by(|a, b| b.cmp(a));
-    Ok(versions.into_iter().next())
+    Ok(versions)
 }
 
 pub fn runtime_ge_015(runtime: &str) -> bool {
@@ -506,12 +1005,17 @@ pub fn runtime_ge_015(runtime: &str) -> bool {
 /// `Remote::list`, both of which perform blocking network I/O. Running them
 /// on the tokio runtime directly would stall other async tasks for the
 /// duration of the git protocol handshake, so we hop to a blocking thread.
-async fn fetch_releases_blocking(filter: ReleasesFilter) -> Result<Vec<Version>> {
-    match tokio::task::spawn_blocking(move || releases::get_all(WASMEDGE_GIT_URL, filter)).await {
+async fn fetch_releases_blocking(
+    git_url: &str,
+    filter: ReleasesFilter,
+    http: HttpClientConfig,
+) -> Result<Vec<Version>> {
+    let url = git_url.to_string();
+    match tokio::task::spawn_blocking(move || releases::get_all_with(&url, filter, &http)).await {
         Ok(inner) => inner,
         Err(join_err) => Err(Error::Io {
             action: "release-enumeration task".to_string(),
-            path: WASMEDGE_GIT_URL.to_string(),
+            path: git_url.to_string(),
             source: crate::error::join_err_to_io_error(join_err),
         }),
     }
@@ -519,7 +1023,7 @@ async fn fetch_releases_blocking(filter: ReleasesFilter) -> Result<Vec<Version>>
 
 /// Metadata describing a single plugin release asset as published on
 /// GitHub's release API.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PluginAssetInfo {
     pub plugin: String,
     pub version: String,
@@ -568,14 +1072,15 @@ pub fn plugin_archive_name(plugin: &str, runtime: &str, platform: &str, is_zip:
 /// host OS (Windows → zip, others → tar.gz), but the `plugin list --all`
 /// probe builds *both* variants per host to discover whichever exists on
 /// the release.
-pub fn plugin_asset_url(plugin: &str, runtime: &str, platform: &str, is_zip: bool) -> Result<Url> {
+pub fn plugin_asset_url(
+    endpoints: &Endpoints,
+    plugin: &str,
+    runtime: &str,
+    platform: &str,
+    is_zip: bool,
+) -> Result<Url> {
     let filename = plugin_archive_name(plugin, runtime, platform, is_zip);
-    let mut url = Url::parse(WASMEDGE_RELEASE_BASE_URL)
-        .expect("WASMEDGE_RELEASE_BASE_URL must be a valid URL");
-    url.path_segments_mut()
-        .expect("base is valid URL")
-        .extend(&[runtime, &filename]);
-    Ok(url)
+    Ok(endpoints.release_file_url(runtime, &filename))
 }
 
 #[cfg(test)]
@@ -667,7 +1172,7 @@ mod tests {
     #[test]
     fn asset_url_is_valid() {
         let a = Asset::new(&v("0.15.0"), &TargetOS::Linux, &TargetArch::X86_64);
-        let url = a.url().expect("url builds");
+        let url = a.url(&Endpoints::default()).expect("url builds");
         assert_eq!(
             url.as_str(),
             "https://github.com/WasmEdge/WasmEdge/releases/download/0.15.0/WasmEdge-0.15.0-manylinux_2_28_x86_64.tar.gz"
@@ -700,6 +1205,35 @@ mod tests {
         assert_eq!(picked, Some(v("0.15.0")));
     }
 
+    #[test]
+    fn latest_installed_version_ignores_interrupted_installs() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for name in ["0.14.1", ".0.16.0.partial", ".0.15.0.backup"] {
+            std::fs::create_dir(tmp.path().join(name)).expect("mkdir");
+        }
+        assert_eq!(
+            latest_installed_version(tmp.path()).unwrap(),
+            Some(v("0.14.1"))
+        );
+    }
+
+    #[test]
+    fn selects_installed_version_by_spec() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for name in ["0.13.5", "0.14.0", "0.14.1", "0.15.0-rc.1"] {
+            std::fs::create_dir(tmp.path().join(name)).expect("mkdir");
+        }
+        let select = |spec| select_installed_version(tmp.path(), spec).ok();
+        assert_eq!(select("~0.14"), Some(v("0.14.1")));
+        assert_eq!(select("0.14.0"), Some(v("0.14.0")));
+        assert_eq!(select("stable"), Some(v("0.14.1")));
+        assert_eq!(select("latest"), Some(v("0.15.0-rc.1")));
+        assert!(matches!(
+            select_installed_version(tmp.path(), "^1"),
+            Err(Error::VersionNotFound { .. })
+        ));
+    }
+
     #[test]
     fn runtime_ge_015_boundaries() {
         assert!(!runtime_ge_015("0.14.99"));
@@ -784,8 +1318,14 @@ mod tests {
 
     #[test]
     fn plugin_asset_url_targz() {
-        let url = plugin_asset_url("wasi_nn-ggml", "0.15.0", "manylinux_2_28_x86_64", false)
-            .expect("url builds");
+        let url = plugin_asset_url(
+            &Endpoints::default(),
+            "wasi_nn-ggml",
+            "0.15.0",
+            "manylinux_2_28_x86_64",
+            false,
+        )
+        .expect("url builds");
         assert_eq!(
             url.as_str(),
             "https://github.com/WasmEdge/WasmEdge/releases/download/0.15.0/WasmEdge-plugin-wasi_nn-ggml-0.15.0-manylinux_2_28_x86_64.tar.gz"
@@ -794,8 +1334,14 @@ mod tests {
 
     #[test]
     fn plugin_asset_url_zip() {
-        let url =
-            plugin_asset_url("wasi_crypto", "0.14.1", "windows_x86_64", true).expect("url builds");
+        let url = plugin_asset_url(
+            &Endpoints::default(),
+            "wasi_crypto",
+            "0.14.1",
+            "windows_x86_64",
+            true,
+        )
+        .expect("url builds");
         assert_eq!(
             url.as_str(),
             "https://github.com/WasmEdge/WasmEdge/releases/download/0.14.1/WasmEdge-plugin-wasi_crypto-0.14.1-windows_x86_64.zip"
@@ -1,6 +1,12 @@
+use gix::protocol::transport::client::blocking_io::{http, Transport};
+use gix::protocol::transport::Protocol;
 use gix::remote::Direction;
-use semver::Version;
+use gix::url::Scheme;
+use semver::{Version, VersionReq};
+use snafu::ResultExt;
 
+use super::git_http::BlockingHttp;
+use crate::http::HttpClientConfig;
 use crate::prelude::*;
 
 #[derive(Debug, Clone, Copy)]
@@ -18,6 +24,53 @@ impl ReleasesFilter {
     }
 }
 
+/// A requested runtime version: a release channel, an exact version, or a
+/// semver requirement such as `~0.14`, `^0.15` or `0.14.x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSpec {
+    /// `latest` or `stable`: the newest stable release.
+    Stable,
+    /// `prerelease` or `nightly`: the newest release, prereleases included.
+    Prerelease,
+    Exact(Version),
+    Req(VersionReq),
+}
+
+impl VersionSpec {
+    /// Parse a version argument. Exact versions take precedence over
+    /// requirements, so `0.14.1` means that release rather than `^0.14.1`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        match spec {
+            "latest" | "stable" => Ok(Self::Stable),
+            "prerelease" | "nightly" => Ok(Self::Prerelease),
+            _ => match Version::parse(spec) {
+                Ok(version) => Ok(Self::Exact(version)),
+                Err(_) => VersionReq::parse(spec)
+                    .map(Self::Req)
+                    .context(SemVerSnafu {}),
+            },
+        }
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Stable => version.pre.is_empty(),
+            Self::Prerelease => true,
+            Self::Exact(exact) => version == exact,
+            Self::Req(req) => req.matches(version),
+        }
+    }
+
+    /// The newest of `versions` satisfying this spec.
+    pub fn select<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Option<Version> {
+        versions
+            .into_iter()
+            .filter(|version| self.matches(version))
+            .max()
+            .cloned()
+    }
+}
+
 /// List every release tag advertised by the remote, sorted newest-first.
 ///
 /// Performs a `git ls-remote`-style ref discovery against `url` using the
@@ -25,6 +78,33 @@ impl ReleasesFilter {
 /// we only consume the ref advertisement returned during the protocol
 /// handshake.
 pub fn get_all(url: &str, filter: ReleasesFilter) -> Result<Vec<Version>> {
+    get_all_with(url, filter, &HttpClientConfig::default())
+}
+
+/// [`get_all`], reaching `http(s)://` remotes through a client built from
+/// `http` so they honour its proxy, CA certificates and timeouts.
+///
+/// The blocking reqwest client runs its own tokio runtime, which must not
+/// be created or dropped on an async worker thread, so the discovery runs
+/// on a dedicated thread. That keeps this safe to call from async code.
+pub fn get_all_with(
+    url: &str,
+    filter: ReleasesFilter,
+    http: &HttpClientConfig,
+) -> Result<Vec<Version>> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| list_remote(url, filter, http))
+            .join()
+            .map_err(|_| Error::Io {
+                action: "list remote releases".to_string(),
+                path: url.to_string(),
+                source: std::io::Error::other("release listing thread panicked"),
+            })?
+    })
+}
+
+fn list_remote(url: &str, filter: ReleasesFilter, http: &HttpClientConfig) -> Result<Vec<Version>> {
     // gix's high-level Connection API requires a repository handle, so we
     // initialise an ephemeral bare repo skeleton in a tempdir. `init_bare`
     // does write the empty repo metadata (HEAD, config, refs/, objects/)
@@ -68,20 +148,20 @@ pub fn get_all(url: &str, filter: ReleasesFilter) -> Result<Vec<Version>> {
         })?
         .with_fetch_tags(gix::remote::fetch::Tags::All);
 
-    let connection = remote.connect(Direction::Fetch).map_err(|e| Error::Git {
-        source: Box::new(e),
-        resource: "remote/connect",
-    })?;
-
-    let (ref_map, _handshake) = connection
-        .ref_map(
-            gix::progress::Discard,
-            gix::remote::ref_map::Options::default(),
-        )
-        .map_err(|e| Error::Git {
+    let fetch_url = remote.url(Direction::Fetch).cloned();
+    let ref_map = match fetch_url {
+        Some(url) if matches!(url.scheme, Scheme::Http | Scheme::Https) => {
+            // Protocol V1 advertises every ref in the response to a single
+            // GET, which is all `BlockingHttp` implements.
+            let http = BlockingHttp::new(http.build_blocking()?);
+            let transport = http::connect_http(http, url, Protocol::V1, false);
+            ref_map(remote.to_connection_with_transport(transport))?
+        }
+        _ => ref_map(remote.connect(Direction::Fetch).map_err(|e| Error::Git {
             source: Box::new(e),
-            resource: "remote/ref_map",
-        })?;
+            resource: "remote/connect",
+        })?)?,
+    };
 
     let mut heads: Vec<Version> = ref_map
         .remote_refs
@@ -94,6 +174,22 @@ pub fn get_all(url: &str, filter: ReleasesFilter) -> Result<Vec<Version>> {
     Ok(heads)
 }
 
+/// Run ref discovery on `connection` without fetching any objects.
+fn ref_map<T: Transport>(
+    connection: gix::remote::Connection<'_, '_, '_, T>,
+) -> Result<gix::remote::fetch::RefMap> {
+    let (ref_map, _handshake) = connection
+        .ref_map(
+            gix::progress::Discard,
+            gix::remote::ref_map::Options::default(),
+        )
+        .map_err(|e| Error::Git {
+            source: Box::new(e),
+            resource: "remote/ref_map",
+        })?;
+    Ok(ref_map)
+}
+
 fn remote_ref_to_version(r: &gix::protocol::handshake::Ref) -> Option<Version> {
     let (name_bstr, _target, _peeled) = r.unpack();
     let name = std::str::from_utf8(name_bstr.as_ref()).ok()?;
@@ -147,6 +243,55 @@ mod tests {
         assert!(parse_tag_ref("refs/tags/v0.14.1").is_none());
     }
 
+    fn versions(list: &[&str]) -> Vec<Version> {
+        list.iter().map(|v| Version::parse(v).unwrap()).collect()
+    }
+
+    #[test]
+    fn parses_version_specs() {
+        assert_eq!(VersionSpec::parse("latest").unwrap(), VersionSpec::Stable);
+        assert_eq!(VersionSpec::parse("stable").unwrap(), VersionSpec::Stable);
+        assert_eq!(
+            VersionSpec::parse("nightly").unwrap(),
+            VersionSpec::Prerelease
+        );
+        assert_eq!(
+            VersionSpec::parse("0.14.1").unwrap(),
+            VersionSpec::Exact(Version::new(0, 14, 1))
+        );
+        assert!(matches!(
+            VersionSpec::parse("~0.14").unwrap(),
+            VersionSpec::Req(_)
+        ));
+        assert!(VersionSpec::parse("not-a-version").is_err());
+    }
+
+    #[test]
+    fn selects_newest_matching_version() {
+        let all = versions(&[
+            "0.13.5",
+            "0.14.0",
+            "0.14.1",
+            "0.15.0-rc.1",
+            "0.15.0",
+            "0.16.0-alpha.1",
+        ]);
+        let select = |spec: &str| {
+            VersionSpec::parse(spec)
+                .unwrap()
+                .select(&all)
+                .map(|v| v.to_string())
+        };
+        assert_eq!(select("latest").as_deref(), Some("0.15.0"));
+        assert_eq!(select("prerelease").as_deref(), Some("0.16.0-alpha.1"));
+        assert_eq!(select("~0.14").as_deref(), Some("0.14.1"));
+        assert_eq!(select("0.14.x").as_deref(), Some("0.14.1"));
+        assert_eq!(select("0.14").as_deref(), Some("0.14.1"));
+        assert_eq!(select("^0.15").as_deref(), Some("0.15.0"));
+        assert_eq!(select("0.14.0").as_deref(), Some("0.14.0"));
+        assert_eq!(select("~0.12"), None);
+    }
+
     #[test]
     fn rejects_empty() {
         assert!(parse_tag_ref("").is_none());
@@ -6,10 +6,29 @@ use wasmedgeup::prelude::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Started through a `wasmedge`/`wasmedgec` shim: forward to the selected
+    // runtime instead of parsing wasmedgeup's own CLI.
+    let mut args = std::env::args_os();
+    if let Some(name) = args.next().as_deref().and_then(wasmedgeup::shim::shim_name) {
+        match wasmedgeup::shim::run(name, args.collect()).await {
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("wasmedgeup ({name} shim): {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     let cli = Cli::parse();
-    let ctx = cli.context();
+    init_tracing(cli.verbose, cli.output.is_json());
 
-    init_tracing(cli.verbose);
+    let ctx = match cli.context() {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
 
     if let Some(command) = cli.commands {
         if let Err(e) = command.execute(ctx).await {
@@ -21,11 +40,17 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn init_tracing(verbosity: u8) {
+fn init_tracing(verbosity: u8, to_stderr: bool) {
     let level = match verbosity {
         0 => Level::INFO,
         1 => Level::DEBUG,
         2.. => Level::TRACE,
     };
-    tracing_subscriber::fmt().with_max_level(level).init();
+    let builder = tracing_subscriber::fmt().with_max_level(level);
+    // Keep stdout clean for the JSON document in `--output json` mode.
+    if to_stderr {
+        builder.with_writer(std::io::stderr).init();
+    } else {
+        builder.init();
+    }
 }
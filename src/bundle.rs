@@ -0,0 +1,366 @@
+//! Installing from local archives instead of the WasmEdge release server.
+//!
+//! A bundle directory mirrors the release layout: one directory per release
+//! tag holding the archives and that release's `SHA256SUM`,
+//!
+//! ```text
+//! <bundle>/0.14.1/WasmEdge-0.14.1-manylinux2014_x86_64.tar.gz
+//! <bundle>/0.14.1/WasmEdge-plugin-wasi_logging-0.14.1-manylinux2014_x86_64.tar.gz
+//! <bundle>/0.14.1/SHA256SUM
+//! ```
+//!
+//! A flat directory with the archives and a single `SHA256SUM` next to them
+//! works too. A lone archive passed with `--from-file` is checked against the
+//! `SHA256SUM` in its own directory.
+//!
+//! `wasmedgeup bundle` writes the tagged layout, with a `SHA256SUM` per tag
+//! holding only the bundled archives and a [`BundleManifest`] at the root
+//! describing what was packed for which target.
+
+use std::path::{Path, PathBuf};
+
+use semver::Version;
+use serde::Serialize;
+use snafu::ResultExt;
+
+use crate::api::{find_checksum, VersionSpec, WasmEdgeApiClient};
+use crate::constants::CHECKSUM_FILE_NAME;
+use crate::prelude::*;
+use crate::signature::{self, SignatureVerifier};
+use crate::target::{TargetArch, TargetOS};
+
+/// Name of the manifest written at the root of a bundle.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// An archive on the local file system, with the `SHA256SUM` file that
+/// vouches for it.
+#[derive(Debug, Clone)]
+pub struct LocalArchive {
+    pub path: PathBuf,
+    pub checksum_file: PathBuf,
+    /// Release tag reported in errors.
+    tag: String,
+}
+
+impl LocalArchive {
+    /// An archive given directly by path.
+    pub fn from_file(path: &Path, tag: &str) -> Result<Self> {
+        if !path.is_file() {
+            return Err(Error::LocalArchiveNotFound {
+                archive: path.display().to_string(),
+            });
+        }
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Ok(Self {
+            path: path.to_path_buf(),
+            checksum_file: dir.join(CHECKSUM_FILE_NAME),
+            tag: tag.to_string(),
+        })
+    }
+
+    /// Find `archive_name` for release `tag` in `bundle`, trying
+    /// `<bundle>/<tag>/` before the flat layout.
+    pub fn find(bundle: &Path, tag: &str, archive_name: &str) -> Result<Self> {
+        [bundle.join(tag), bundle.to_path_buf()]
+            .into_iter()
+            .map(|dir| dir.join(archive_name))
+            .find(|p| p.is_file())
+            .ok_or_else(|| Error::LocalArchiveNotFound {
+                archive: bundle.join(tag).join(archive_name).display().to_string(),
+            })
+            .and_then(|path| Self::from_file(&path, tag))
+    }
+
+    fn archive_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Expected SHA256 of the archive according to its `SHA256SUM`, once
+    /// `signatures` accepts the `SHA256SUM.minisig` next to it.
+    pub fn expected_checksum(&self, signatures: &SignatureVerifier) -> Result<String> {
+        let content = signature::read_verified(&self.checksum_file, signatures)?;
+        let archive_name = self.archive_name();
+        find_checksum(&content, &archive_name)
+            .map(str::to_string)
+            .ok_or(Error::ChecksumNotFound {
+                version: self.tag.clone(),
+                asset: archive_name,
+            })
+    }
+
+    /// Open the archive for extraction, verifying it first unless
+    /// `no_verify` is set.
+    pub async fn open_verified(
+        &self,
+        no_verify: bool,
+        signatures: &SignatureVerifier,
+    ) -> Result<std::fs::File> {
+        let mut file = std::fs::File::open(&self.path).map_err(|source| Error::Io {
+            action: "open archive".to_string(),
+            path: self.path.display().to_string(),
+            source,
+        })?;
+        if no_verify {
+            tracing::warn!(archive = %self.path.display(), "Skipping checksum verification due to --no-verify flag");
+            return Ok(file);
+        }
+        let expected = self.expected_checksum(signatures)?;
+        tracing::debug!(archive = %self.path.display(), %expected, "Got checksum from local SHA256SUM");
+        WasmEdgeApiClient::verify_file_checksum(&mut file, &expected).await?;
+        tracing::debug!(archive = %self.path.display(), "Checksum verified successfully");
+        Ok(file)
+    }
+}
+
+/// Highest stable release tag with a directory in `bundle`.
+pub fn latest_tag(bundle: &Path) -> Result<Option<Version>> {
+    Ok(VersionSpec::Stable.select(&tags(bundle)?))
+}
+
+/// Release tags with a directory in `bundle`.
+pub fn tags(bundle: &Path) -> Result<Vec<Version>> {
+    let entries = std::fs::read_dir(bundle).map_err(|source| Error::Io {
+        action: "read bundle directory".to_string(),
+        path: bundle.display().to_string(),
+        source,
+    })?;
+    Ok(entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| Version::parse(&e.file_name().to_string_lossy()).ok())
+        .collect())
+}
+
+/// Self-description of a bundle produced by `wasmedgeup bundle`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleManifest {
+    /// Runtime release the bundle was resolved for.
+    pub version: String,
+    /// `wasmedgeup` version that wrote the bundle.
+    pub created_by: String,
+    pub targets: Vec<BundleTarget>,
+}
+
+/// Archives bundled for one `(os, arch)` pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleTarget {
+    pub os: TargetOS,
+    pub arch: TargetArch,
+    pub archives: Vec<BundledArchive>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BundledArchive {
+    /// `runtime`, or the plugin name.
+    pub component: String,
+    /// Release tag, i.e. the directory the archive lives in.
+    pub tag: String,
+    pub archive_name: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl BundleManifest {
+    /// Write the manifest and one `SHA256SUM` per release tag into `bundle`.
+    ///
+    /// Archives shared between targets (plugins for `linux` and `ubuntu`,
+    /// say) are listed once per `SHA256SUM`.
+    pub fn write(&self, bundle: &Path) -> Result<()> {
+        let mut sums =
+            std::collections::BTreeMap::<&str, std::collections::BTreeSet<String>>::new();
+        for archive in self.targets.iter().flat_map(|t| &t.archives) {
+            sums.entry(&archive.tag)
+                .or_default()
+                .insert(format!("{}  {}\n", archive.sha256, archive.archive_name));
+        }
+        for (tag, lines) in sums {
+            let path = bundle.join(tag).join(CHECKSUM_FILE_NAME);
+            std::fs::write(&path, lines.into_iter().collect::<String>()).map_err(|source| {
+                Error::Io {
+                    action: "write checksum file".to_string(),
+                    path: path.display().to_string(),
+                    source,
+                }
+            })?;
+        }
+
+        let path = bundle.join(MANIFEST_FILE_NAME);
+        let json = serde_json::to_string_pretty(self).context(JsonSnafu {
+            resource: "bundle manifest",
+        })?;
+        std::fs::write(&path, json + "\n").map_err(|source| Error::Io {
+            action: "write bundle manifest".to_string(),
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+/// Pack the contents of `bundle` into the gzip-compressed tarball `dest`.
+///
+/// Entries sit at the root of the tarball, so extracting it anywhere (or
+/// `ADD`-ing it in a Dockerfile) yields a directory usable with `--from-dir`.
+#[cfg(unix)]
+pub fn pack_tarball(bundle: &Path, dest: &Path) -> Result<()> {
+    use flate2::{write::GzEncoder, Compression};
+
+    let io_err = |action: &str| {
+        let path = dest.display().to_string();
+        let action = action.to_string();
+        move |source| Error::Io {
+            action,
+            path,
+            source,
+        }
+    };
+    let file = std::fs::File::create(dest).map_err(io_err("create bundle tarball"))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    tar.append_dir_all(".", bundle)
+        .map_err(io_err("write bundle tarball"))?;
+    tar.into_inner()
+        .and_then(|gz| gz.finish())
+        .map_err(io_err("finish bundle tarball"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCHIVE: &str = "WasmEdge-0.14.1-manylinux2014_x86_64.tar.gz";
+
+    fn write_archive(dir: &Path, content: &[u8]) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(ARCHIVE), content).unwrap();
+        let sha = crate::api::sha256_hex(&mut &content[..]).unwrap();
+        std::fs::write(
+            dir.join(CHECKSUM_FILE_NAME),
+            format!("0000  other.tar.gz\n{sha}  {ARCHIVE}\n"),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn finds_and_verifies_tagged_and_flat_layouts() {
+        let tagged = tempfile::tempdir().unwrap();
+        write_archive(&tagged.path().join("0.14.1"), b"tagged");
+        let archive = LocalArchive::find(tagged.path(), "0.14.1", ARCHIVE).unwrap();
+        assert!(archive.path.starts_with(tagged.path().join("0.14.1")));
+        archive
+            .open_verified(false, &SignatureVerifier::default())
+            .await
+            .unwrap();
+
+        let flat = tempfile::tempdir().unwrap();
+        write_archive(flat.path(), b"flat");
+        let archive = LocalArchive::find(flat.path(), "0.14.1", ARCHIVE).unwrap();
+        archive
+            .open_verified(false, &SignatureVerifier::default())
+            .await
+            .unwrap();
+
+        let err = LocalArchive::find(flat.path(), "0.14.1", "missing.tar.gz").unwrap_err();
+        assert!(matches!(err, Error::LocalArchiveNotFound { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn tampered_archive_fails_unless_no_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        write_archive(dir.path(), b"original");
+        std::fs::write(dir.path().join(ARCHIVE), b"tampered").unwrap();
+
+        let archive = LocalArchive::from_file(&dir.path().join(ARCHIVE), "0.14.1").unwrap();
+        let err = archive
+            .open_verified(false, &SignatureVerifier::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ChecksumMismatch { .. }), "{err:?}");
+        archive
+            .open_verified(true, &SignatureVerifier::default())
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn latest_tag_ignores_prereleases_and_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for tag in ["0.13.5", "0.14.1", "0.15.0-rc.1"] {
+            std::fs::create_dir_all(dir.path().join(tag)).unwrap();
+        }
+        std::fs::write(dir.path().join("0.99.0"), "").unwrap();
+        assert_eq!(
+            latest_tag(dir.path()).unwrap(),
+            Some(Version::new(0, 14, 1))
+        );
+    }
+
+    #[tokio::test]
+    async fn written_bundle_is_installable_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let tag_dir = dir.path().join("0.14.1");
+        std::fs::create_dir_all(&tag_dir).unwrap();
+        std::fs::write(tag_dir.join(ARCHIVE), b"runtime").unwrap();
+        let archive = BundledArchive {
+            component: "runtime".to_string(),
+            tag: "0.14.1".to_string(),
+            archive_name: ARCHIVE.to_string(),
+            sha256: crate::api::sha256_hex(&mut &b"runtime"[..]).unwrap(),
+            size: 7,
+        };
+        let manifest = BundleManifest {
+            version: "0.14.1".to_string(),
+            created_by: "test".to_string(),
+            targets: [TargetOS::Linux, TargetOS::Ubuntu]
+                .into_iter()
+                .map(|os| BundleTarget {
+                    os,
+                    arch: TargetArch::X86_64,
+                    archives: vec![archive.clone()],
+                })
+                .collect(),
+        };
+        manifest.write(dir.path()).unwrap();
+
+        let sums = std::fs::read_to_string(tag_dir.join(CHECKSUM_FILE_NAME)).unwrap();
+        assert_eq!(sums.lines().count(), 1);
+        let found = LocalArchive::find(dir.path(), "0.14.1", ARCHIVE).unwrap();
+        found
+            .open_verified(false, &SignatureVerifier::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            latest_tag(dir.path()).unwrap(),
+            Some(Version::new(0, 14, 1))
+        );
+
+        let json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join(MANIFEST_FILE_NAME)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["targets"].as_array().unwrap().len(), 2);
+        assert_eq!(json["targets"][0]["archives"][0]["sha256"], archive.sha256);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn tarball_extracts_to_bundle_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("bundle");
+        write_archive(&bundle.join("0.14.1"), b"runtime");
+        let tarball = dir.path().join("bundle.tar.gz");
+        pack_tarball(&bundle, &tarball).unwrap();
+
+        let out = dir.path().join("out");
+        crate::fs::extract_archive(std::fs::File::open(&tarball).unwrap(), &out)
+            .await
+            .unwrap();
+        let found = LocalArchive::find(&out, "0.14.1", ARCHIVE).unwrap();
+        found
+            .open_verified(false, &SignatureVerifier::default())
+            .await
+            .unwrap();
+    }
+}
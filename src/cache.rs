@@ -0,0 +1,429 @@
+//! Persistent, content-addressed cache of downloaded release archives.
+//!
+//! Archives are stored as `<root>/<sha256>/<archive name>`, where the
+//! SHA256 is the one published in the release's `SHA256SUM` file. A lookup
+//! therefore needs the expected checksum up front, and every hit is
+//! re-hashed before it is served so a corrupted or tampered entry is
+//! dropped rather than installed. Downloads made with `--no-verify` have no
+//! trusted checksum and bypass the cache.
+//!
+//! Downloads into the cache go through `<root>/<sha256>/<archive name>.part`
+//! first, together with the server's `ETag` in `.part.etag`, so an
+//! interrupted download is resumed with an HTTP `Range` request by the next
+//! attempt instead of starting over. A process writing a `.part` file holds
+//! an exclusive lock on `.part.lock`; another one downloading the same
+//! archive meanwhile bypasses the cache rather than appending to it too.
+//!
+//! The default root is `<user cache dir>/wasmedgeup/downloads` (e.g.
+//! `~/.cache/wasmedgeup/downloads` on Linux); `WASMEDGEUP_CACHE_DIR`
+//! overrides it.
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use fs4::{FileExt, TryLockError};
+use serde::Serialize;
+
+use crate::api::sha256_hex;
+use crate::prelude::*;
+
+/// Environment variable overriding the cache root.
+pub const CACHE_DIR_ENV: &str = "WASMEDGEUP_CACHE_DIR";
+
+/// Suffix of partially downloaded archives.
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// Suffix of the file holding a partial download's `ETag`.
+const ETAG_SUFFIX: &str = ".etag";
+
+/// Suffix of the file locked while a partial download is written.
+const LOCK_SUFFIX: &str = ".lock";
+
+/// Path of the file recording the `ETag` of the partial download at
+/// `partial`.
+pub fn partial_etag_path(partial: &Path) -> PathBuf {
+    let mut name = partial.as_os_str().to_owned();
+    name.push(ETAG_SUFFIX);
+    PathBuf::from(name)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadCache {
+    root: PathBuf,
+}
+
+/// A cached archive, as reported by `wasmedgeup cache list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheEntry {
+    pub archive_name: String,
+    pub sha256: String,
+    pub size: u64,
+    pub path: PathBuf,
+    /// Seconds since the entry was stored or last served.
+    pub age_secs: u64,
+}
+
+impl DownloadCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The cache at `WASMEDGEUP_CACHE_DIR`, or under the user's cache
+    /// directory. `None` if neither is available.
+    pub fn default_location() -> Option<Self> {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|d| !d.is_empty()) {
+            return Some(Self::new(dir));
+        }
+        dirs::cache_dir().map(|d| Self::new(d.join("wasmedgeup").join("downloads")))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn entry_path(&self, sha256: &str, archive_name: &str) -> Result<PathBuf> {
+        // Both parts become path components; refuse anything that could
+        // escape the cache root.
+        let is_component =
+            |s: &str| !s.is_empty() && s != "." && s != ".." && !s.contains(['/', '\\']);
+        if !sha256.bytes().all(|b| b.is_ascii_hexdigit()) || !is_component(sha256) {
+            return Err(Error::InvalidPath {
+                path: sha256.to_string(),
+                reason: "cache key must be a hex SHA256 digest".to_string(),
+            });
+        }
+        if !is_component(archive_name) {
+            return Err(Error::InvalidPath {
+                path: archive_name.to_string(),
+                reason: "cached archive name must be a single path component".to_string(),
+            });
+        }
+        Ok(self
+            .root
+            .join(sha256.to_ascii_lowercase())
+            .join(archive_name))
+    }
+
+    /// Path of the cached `archive_name` with checksum `sha256`, if present
+    /// and intact. A corrupted entry is removed.
+    pub fn get(&self, sha256: &str, archive_name: &str) -> Result<Option<PathBuf>> {
+        let path = self.entry_path(sha256, archive_name)?;
+        let Ok(mut file) = std::fs::File::open(&path) else {
+            return Ok(None);
+        };
+        let actual = sha256_hex(&mut file)?;
+        if !actual.eq_ignore_ascii_case(sha256) {
+            tracing::warn!(path = %path.display(), expected = sha256, %actual, "Dropping corrupted cache entry");
+            drop(file);
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+            return Ok(None);
+        }
+        // Record the hit so `cache prune --older-than` keeps archives that
+        // are still in use.
+        if let Err(e) = file.set_modified(SystemTime::now()) {
+            tracing::debug!(error = %e, path = %path.display(), "Failed to touch cache entry");
+        }
+        Ok(Some(path))
+    }
+
+    /// Copy the verified archive at `src` into the cache.
+    ///
+    /// The copy goes through a temporary file in the entry directory and is
+    /// renamed into place, so concurrent installs never observe a partial
+    /// entry.
+    pub fn store(&self, src: &Path, sha256: &str, archive_name: &str) -> Result<PathBuf> {
+        let path = self.entry_path(sha256, archive_name)?;
+        let dir = path.parent().expect("entry path has a parent");
+        std::fs::create_dir_all(dir).map_err(|source| Error::Io {
+            action: "create cache directory".to_string(),
+            path: dir.display().to_string(),
+            source,
+        })?;
+
+        let tmp = tempfile::NamedTempFile::new_in(dir)?;
+        std::fs::copy(src, tmp.path()).map_err(|source| Error::Io {
+            action: "copy archive into cache".to_string(),
+            path: tmp.path().display().to_string(),
+            source,
+        })?;
+        tmp.persist(&path).map_err(|e| Error::Io {
+            action: "persist cache entry".to_string(),
+            path: path.display().to_string(),
+            source: e.error,
+        })?;
+        tracing::debug!(path = %path.display(), "Stored archive in download cache");
+        Ok(path)
+    }
+
+    /// Path where an in-progress download of `archive_name` with checksum
+    /// `sha256` is kept, creating its directory.
+    pub fn partial_path(&self, sha256: &str, archive_name: &str) -> Result<PathBuf> {
+        let path = self.entry_path(sha256, archive_name)?;
+        let dir = path.parent().expect("entry path has a parent");
+        std::fs::create_dir_all(dir).map_err(|source| Error::Io {
+            action: "create cache directory".to_string(),
+            path: dir.display().to_string(),
+            source,
+        })?;
+        let mut name = path.into_os_string();
+        name.push(PARTIAL_SUFFIX);
+        Ok(PathBuf::from(name))
+    }
+
+    /// Lock the partial download at `partial` so no other process appends
+    /// to it, until the returned file is dropped. `None` if another process
+    /// holds the lock.
+    ///
+    /// The lock is on a sibling `.lock` file, which is left in place:
+    /// `partial` itself is renamed into the cache once complete.
+    pub fn lock_partial(&self, partial: &Path) -> Result<Option<File>> {
+        let mut name = partial.as_os_str().to_owned();
+        name.push(LOCK_SUFFIX);
+        let path = PathBuf::from(name);
+        let io_err = |action: &str, source| Error::Io {
+            action: action.to_string(),
+            path: path.display().to_string(),
+            source,
+        };
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| io_err("open partial download lock", e))?;
+        match FileExt::try_lock(&file) {
+            Ok(()) => Ok(Some(file)),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(io_err("lock partial download", e)),
+        }
+    }
+
+    /// Move the completed and verified download at `partial` into place as
+    /// the cache entry for `archive_name`, dropping its recorded `ETag`.
+    ///
+    /// The partial file lives in the entry directory, so this is a rename
+    /// and concurrent installs never observe a partial entry.
+    pub fn store_partial(
+        &self,
+        partial: &Path,
+        sha256: &str,
+        archive_name: &str,
+    ) -> Result<PathBuf> {
+        let path = self.entry_path(sha256, archive_name)?;
+        std::fs::rename(partial, &path).map_err(|source| Error::Io {
+            action: "move download into cache".to_string(),
+            path: path.display().to_string(),
+            source,
+        })?;
+        self.remove_partial(partial);
+        tracing::debug!(path = %path.display(), "Stored archive in download cache");
+        Ok(path)
+    }
+
+    /// Remove the partial download at `partial` and its recorded `ETag`.
+    pub fn remove_partial(&self, partial: &Path) {
+        for path in [partial.to_path_buf(), partial_etag_path(partial)] {
+            if let Err(e) = std::fs::remove_file(&path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::debug!(error = %e, path = %path.display(), "Failed to remove partial download");
+                }
+            }
+        }
+    }
+
+    /// All entries currently in the cache, sorted by archive name.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        let Ok(dirs) = std::fs::read_dir(&self.root) else {
+            return Ok(entries);
+        };
+        let now = SystemTime::now();
+        for dir in dirs.flatten() {
+            let sha256 = dir.file_name().to_string_lossy().to_string();
+            let Ok(files) = std::fs::read_dir(dir.path()) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let Ok(meta) = file.metadata() else {
+                    continue;
+                };
+                if !meta.is_file() {
+                    continue;
+                }
+                let archive_name = file.file_name().to_string_lossy().to_string();
+                // Leftover temporaries from an interrupted `store`, and
+                // downloads still in progress.
+                if archive_name.starts_with(".tmp")
+                    || archive_name.ends_with(PARTIAL_SUFFIX)
+                    || archive_name.ends_with(ETAG_SUFFIX)
+                    || archive_name.ends_with(LOCK_SUFFIX)
+                {
+                    continue;
+                }
+                let age_secs = meta
+                    .modified()
+                    .ok()
+                    .and_then(|m| now.duration_since(m).ok())
+                    .unwrap_or_default()
+                    .as_secs();
+                entries.push(CacheEntry {
+                    archive_name,
+                    sha256: sha256.clone(),
+                    size: meta.len(),
+                    path: file.path(),
+                    age_secs,
+                });
+            }
+        }
+        entries.sort_by(|a, b| a.archive_name.cmp(&b.archive_name));
+        Ok(entries)
+    }
+
+    /// Remove every cached archive, returning what was removed.
+    pub fn clean(&self) -> Result<Vec<CacheEntry>> {
+        let entries = self.entries()?;
+        if self.root.exists() {
+            std::fs::remove_dir_all(&self.root).map_err(|source| Error::Io {
+                action: "remove download cache".to_string(),
+                path: self.root.display().to_string(),
+                source,
+            })?;
+        }
+        Ok(entries)
+    }
+
+    /// Remove archives not stored or used within `max_age`, returning what
+    /// was removed.
+    pub fn prune(&self, max_age: Duration) -> Result<Vec<CacheEntry>> {
+        let mut removed = Vec::new();
+        for entry in self.entries()? {
+            if entry.age_secs < max_age.as_secs() {
+                continue;
+            }
+            std::fs::remove_file(&entry.path).map_err(|source| Error::Io {
+                action: "remove cache entry".to_string(),
+                path: entry.path.display().to_string(),
+                source,
+            })?;
+            if let Some(dir) = entry.path.parent() {
+                // Only succeeds once the digest directory is empty.
+                let _ = std::fs::remove_dir(dir);
+            }
+            removed.push(entry);
+        }
+        Ok(removed)
+    }
+}
+
+/// Parse a duration such as `30d`, `12h`, `45m`, `90s` or `2w`. A bare
+/// number is taken as days.
+pub fn parse_age(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let num: u64 = num
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}` (expected e.g. 30d, 12h, 45m)"))?;
+    let secs_per_unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "" | "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid duration unit `{unit}` (use s, m, h, d or w)"
+            ))
+        }
+    };
+    Ok(Duration::from_secs(num.saturating_mul(secs_per_unit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &[u8] = b"archive bytes";
+
+    fn content_sha() -> String {
+        sha256_hex(&mut &CONTENT[..]).unwrap()
+    }
+
+    fn stored_cache() -> (tempfile::TempDir, DownloadCache, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DownloadCache::new(dir.path().join("downloads"));
+        let src = dir.path().join("src.tar.gz");
+        std::fs::write(&src, CONTENT).unwrap();
+        let sha = content_sha();
+        cache.store(&src, &sha, "WasmEdge-0.14.1.tar.gz").unwrap();
+        (dir, cache, sha)
+    }
+
+    #[test]
+    fn store_then_get_round_trips() {
+        let (_dir, cache, sha) = stored_cache();
+        let hit = cache.get(&sha, "WasmEdge-0.14.1.tar.gz").unwrap().unwrap();
+        assert_eq!(std::fs::read(hit).unwrap(), CONTENT);
+        assert!(cache.get(&sha, "other.tar.gz").unwrap().is_none());
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sha256, sha);
+        assert_eq!(entries[0].size, CONTENT.len() as u64);
+    }
+
+    #[test]
+    fn corrupted_entry_is_dropped() {
+        let (_dir, cache, sha) = stored_cache();
+        let path = cache.entry_path(&sha, "WasmEdge-0.14.1.tar.gz").unwrap();
+        std::fs::write(&path, b"tampered").unwrap();
+
+        assert!(cache.get(&sha, "WasmEdge-0.14.1.tar.gz").unwrap().is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn rejects_keys_that_escape_the_root() {
+        let cache = DownloadCache::new("/cache");
+        assert!(cache.entry_path("..", "a.tar.gz").is_err());
+        assert!(cache.entry_path("abc", "../a.tar.gz").is_err());
+        assert!(cache.entry_path("not-hex", "a.tar.gz").is_err());
+    }
+
+    #[test]
+    fn prune_and_clean() {
+        let (_dir, cache, _sha) = stored_cache();
+        assert!(cache.prune(Duration::from_secs(3600)).unwrap().is_empty());
+        assert_eq!(cache.prune(Duration::ZERO).unwrap().len(), 1);
+        assert!(cache.entries().unwrap().is_empty());
+
+        let (_dir, cache, _sha) = stored_cache();
+        assert_eq!(cache.clean().unwrap().len(), 1);
+        assert!(!cache.root().exists());
+    }
+
+    #[test]
+    fn partial_lock_is_exclusive_and_hidden_from_entries() {
+        let (_dir, cache, sha) = stored_cache();
+        let partial = cache.partial_path(&sha, "plugin.tar.gz").unwrap();
+        let held = cache.lock_partial(&partial).unwrap();
+        assert!(held.is_some());
+        assert!(cache.lock_partial(&partial).unwrap().is_none());
+        drop(held);
+        assert!(cache.lock_partial(&partial).unwrap().is_some());
+        assert_eq!(cache.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age("30d").unwrap(), Duration::from_secs(30 * 86400));
+        assert_eq!(parse_age("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert_eq!(parse_age("7").unwrap(), Duration::from_secs(7 * 86400));
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+    }
+}
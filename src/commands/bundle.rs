@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use clap::{value_parser, Args, ValueEnum};
+use semver::Version;
+use url::Url;
+
+use crate::{
+    api::{find_checksum, plugin_archive_name, plugin_asset_url, Asset, Endpoints},
+    bundle::{BundleManifest, BundleTarget, BundledArchive},
+    cli::{CommandContext, CommandExecutor},
+    commands::plugin::version::PluginVersion,
+    constants::CHECKSUM_FILE_NAME,
+    output::print_json,
+    prelude::*,
+    signature::SIGNATURE_FILE_NAME,
+    system::{plugins::plugin_platform_key, LibcKind, LibcSpec, OsSpec},
+    target::{TargetArch, TargetOS},
+};
+
+#[derive(Debug, Args)]
+pub struct BundleArgs {
+    /// WasmEdge version to bundle, e.g. `latest`, `0.14.1`, `0.14.1-rc.1`, etc.
+    pub version: String,
+
+    /// Plugin to bundle alongside the runtime, as `name` or `name@version`; repeatable
+    #[arg(long = "plugin", value_name = "PLUGIN", value_parser = value_parser!(PluginVersion))]
+    pub plugins: Vec<PluginVersion>,
+
+    /// Platform to bundle for, as `<os>:<arch>` (e.g. `linux:x86_64`, `darwin:arm64`); repeatable
+    ///
+    /// Defaults to the host platform.
+    #[arg(long = "target", value_name = "OS:ARCH")]
+    pub targets: Vec<Platform>,
+
+    /// Directory to write the bundle to; it must not exist or be empty
+    ///
+    /// With `--tarball`, the path of the `.tar.gz` file to write instead.
+    #[arg(short, long)]
+    pub dest: PathBuf,
+
+    /// Write a gzip-compressed tarball instead of a directory
+    #[arg(long)]
+    pub tarball: bool,
+}
+
+/// A `(TargetOS, TargetArch)` pair given on the command line as `os:arch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Platform {
+    pub os: TargetOS,
+    pub arch: TargetArch,
+}
+
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (os, arch) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected `<os>:<arch>`, got `{s}`"))?;
+        Ok(Self {
+            os: TargetOS::from_str(os, true)?,
+            arch: TargetArch::from_str(arch, true)?,
+        })
+    }
+}
+
+/// An archive to fetch into the bundle.
+struct Planned {
+    component: String,
+    tag: String,
+    archive_name: String,
+    url: Url,
+}
+
+impl Platform {
+    /// Runtime and plugin archives bundled for this platform.
+    fn plan(
+        &self,
+        endpoints: &Endpoints,
+        version: &Version,
+        plugins: &[PluginVersion],
+        host: &OsSpec,
+    ) -> Result<Vec<Planned>> {
+        let asset = Asset::new(version, &self.os, &self.arch);
+        let mut planned = vec![Planned {
+            component: "runtime".to_string(),
+            tag: version.to_string(),
+            url: asset.url(endpoints)?,
+            archive_name: asset.archive_name,
+        }];
+
+        let spec = self.os_spec(host);
+        let is_zip = matches!(self.os, TargetOS::Windows);
+        for plugin in plugins {
+            let (name, pver) = match plugin {
+                PluginVersion::Name(n) => (n.as_str(), version.clone()),
+                PluginVersion::NameAndVersion(n, v) => (n.as_str(), v.clone()),
+            };
+            let tag = pver.to_string();
+            let key = plugin_platform_key(&spec, &pver)?;
+            planned.push(Planned {
+                component: name.to_string(),
+                archive_name: plugin_archive_name(name, &tag, &key, is_zip),
+                url: plugin_asset_url(endpoints, name, &tag, &key, is_zip)?,
+                tag,
+            });
+        }
+        Ok(planned)
+    }
+
+    /// OS spec used to pick plugin platform keys. The host's own spec is
+    /// used when it matches, so macOS bundles get the Darwin-major-specific
+    /// key; other macOS targets fall back to the generic `darwin_<arch>`.
+    fn os_spec(&self, host: &OsSpec) -> OsSpec {
+        let same_os = host.os_type == self.os
+            || matches!(
+                (host.os_type, self.os),
+                (
+                    TargetOS::Linux | TargetOS::Ubuntu,
+                    TargetOS::Linux | TargetOS::Ubuntu
+                )
+            );
+        if same_os && host.arch == self.arch {
+            return OsSpec {
+                os_type: self.os,
+                ..host.clone()
+            };
+        }
+        OsSpec {
+            os_type: self.os,
+            arch: self.arch,
+            distro: None,
+            version: None,
+            kernel: None,
+            libc: LibcSpec {
+                kind: LibcKind::Glibc,
+                version: None,
+            },
+        }
+    }
+}
+
+impl CommandExecutor for BundleArgs {
+    /// Downloads the runtime and plugin archives for every requested platform
+    /// into a bundle directory laid out for `install --from-dir`, together
+    /// with per-release `SHA256SUM` files and a manifest.
+    ///
+    /// Every archive is verified against the checksum published with its
+    /// release before it is added to the bundle.
+    #[tracing::instrument(name = "bundle", skip_all, fields(version = self.version))]
+    async fn execute(mut self, ctx: CommandContext) -> Result<()> {
+        if !cfg!(unix) && self.tarball {
+            return Err(Error::InvalidPath {
+                path: self.dest.display().to_string(),
+                reason: "--tarball is only supported on Unix-like hosts".to_string(),
+            });
+        }
+        if self.targets.is_empty() {
+            self.targets.push(Platform::default());
+        }
+
+        let version = ctx.client.resolve_version(&self.version).await?;
+        tracing::debug!(%version, targets = ?self.targets, "Resolved version for bundle");
+
+        // Tarballs are assembled in a scratch directory and packed at the end.
+        let scratch = if self.tarball {
+            Some(crate::fs::create_temp_workspace(
+                &std::env::temp_dir(),
+                "wasmedgeup-bundle",
+            )?)
+        } else {
+            ensure_empty_dir(&self.dest)?;
+            None
+        };
+        let bundle_dir = scratch
+            .as_ref()
+            .map_or(self.dest.as_path(), |s| s.path())
+            .to_path_buf();
+
+        let (host, _, _) = crate::system::os::detect_os();
+        let mut sums = HashMap::<String, (String, Option<String>)>::new();
+        let mut targets = Vec::new();
+        for platform in &self.targets {
+            let mut archives = Vec::new();
+            for planned in platform.plan(&ctx.client.endpoints, &version, &self.plugins, &host)? {
+                archives.push(fetch(&ctx, &bundle_dir, planned, &mut sums).await?);
+            }
+            targets.push(BundleTarget {
+                os: platform.os,
+                arch: platform.arch,
+                archives,
+            });
+        }
+
+        let manifest = BundleManifest {
+            version: version.to_string(),
+            created_by: format!("wasmedgeup {}", env!("CARGO_PKG_VERSION")),
+            targets,
+        };
+        manifest.write(&bundle_dir)?;
+        keep_signed_checksums(&bundle_dir, &sums)?;
+
+        #[cfg(unix)]
+        if self.tarball {
+            crate::bundle::pack_tarball(&bundle_dir, &self.dest)?;
+        }
+        tracing::info!(dest = %self.dest.display(), "Bundle written");
+
+        if ctx.output.is_json() {
+            return print_json(&serde_json::json!({
+                "dest": self.dest,
+                "manifest": manifest,
+            }));
+        }
+        let count: usize = manifest.targets.iter().map(|t| t.archives.len()).sum();
+        println!(
+            "Bundled WasmEdge {version} ({count} archives for {} targets) into {}",
+            manifest.targets.len(),
+            self.dest.display()
+        );
+        Ok(())
+    }
+}
+
+/// Download `planned` into `<bundle>/<tag>/`, verified against the release's
+/// `SHA256SUM`. Each release's `SHA256SUM` is fetched once and kept in `sums`,
+/// with its signature if it has one.
+async fn fetch(
+    ctx: &CommandContext,
+    bundle: &Path,
+    planned: Planned,
+    sums: &mut HashMap<String, (String, Option<String>)>,
+) -> Result<BundledArchive> {
+    if !sums.contains_key(&planned.tag) {
+        let signed = ctx
+            .client
+            .signed_release_checksums(&planned.tag)
+            .await?
+            .unwrap_or_default();
+        sums.insert(planned.tag.clone(), signed);
+    }
+    let sha256 = find_checksum(&sums[&planned.tag].0, &planned.archive_name)
+        .ok_or_else(|| Error::ChecksumNotFound {
+            version: planned.tag.clone(),
+            asset: planned.archive_name.clone(),
+        })?
+        .to_string();
+
+    let dir = bundle.join(&planned.tag);
+    std::fs::create_dir_all(&dir).map_err(|source| Error::Io {
+        action: "create bundle directory".to_string(),
+        path: dir.display().to_string(),
+        source,
+    })?;
+    let path = dir.join(&planned.archive_name);
+    if path.is_file() {
+        // Shared between targets, e.g. plugins for `linux` and `ubuntu`.
+        tracing::debug!(archive = %planned.archive_name, "Archive already bundled");
+    } else {
+        tracing::info!(archive = %planned.archive_name, "Downloading archive into bundle");
+        ctx.client
+            .download_to_path(
+                planned.url,
+                &path,
+                ctx.no_progress,
+                "bundle download",
+                Some(&sha256),
+            )
+            .await?;
+    }
+
+    let size = std::fs::metadata(&path)?.len();
+    Ok(BundledArchive {
+        component: planned.component,
+        tag: planned.tag,
+        archive_name: planned.archive_name,
+        sha256,
+        size,
+    })
+}
+
+/// Replace the `SHA256SUM` [`BundleManifest::write`] wrote for each signed
+/// release with the release's own, next to its signature: the signature only
+/// covers the original file, and installing from the bundle checks it.
+fn keep_signed_checksums(
+    bundle: &Path,
+    sums: &HashMap<String, (String, Option<String>)>,
+) -> Result<()> {
+    for (tag, (content, signature)) in sums {
+        let Some(signature) = signature else {
+            continue;
+        };
+        for (name, data) in [
+            (CHECKSUM_FILE_NAME, content),
+            (SIGNATURE_FILE_NAME, signature),
+        ] {
+            let path = bundle.join(tag).join(name);
+            std::fs::write(&path, data).map_err(|source| Error::Io {
+                action: "write checksum file".to_string(),
+                path: path.display().to_string(),
+                source,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Create `dir` if needed, refusing to write into a non-empty directory so a
+/// bundle never mixes with unrelated files.
+fn ensure_empty_dir(dir: &Path) -> Result<()> {
+    if let Ok(mut entries) = std::fs::read_dir(dir) {
+        if entries.next().is_some() {
+            return Err(Error::InvalidPath {
+                path: dir.display().to_string(),
+                reason: "bundle destination must be empty".to_string(),
+            });
+        }
+    }
+    std::fs::create_dir_all(dir).map_err(|source| Error::Io {
+        action: "create bundle directory".to_string(),
+        path: dir.display().to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_platforms() {
+        let p: Platform = "linux:x86_64".parse().unwrap();
+        assert_eq!(p.os, TargetOS::Linux);
+        assert_eq!(p.arch, TargetArch::X86_64);
+        let p: Platform = "macos:arm64".parse().unwrap();
+        assert_eq!(p.os, TargetOS::Darwin);
+        assert_eq!(p.arch, TargetArch::Aarch64);
+        assert!("linux".parse::<Platform>().is_err());
+        assert!("beos:x86_64".parse::<Platform>().is_err());
+    }
+
+    #[test]
+    fn plans_runtime_and_plugins_per_platform() {
+        // A host that matches none of the bundled platforms.
+        let host = Platform {
+            os: TargetOS::Windows,
+            arch: TargetArch::X86_64,
+        }
+        .os_spec(&crate::system::os::detect_os().0);
+        let plugins = vec![
+            "wasi_logging".parse().unwrap(),
+            "wasi_nn@0.15.0".parse().unwrap(),
+        ];
+        let planned = Platform {
+            os: TargetOS::Linux,
+            arch: TargetArch::Aarch64,
+        }
+        .plan(
+            &Endpoints::default(),
+            &Version::new(0, 14, 1),
+            &plugins,
+            &host,
+        )
+        .unwrap();
+
+        let names: Vec<_> = planned
+            .iter()
+            .map(|p| (p.tag.as_str(), p.archive_name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("0.14.1", "WasmEdge-0.14.1-manylinux2014_aarch64.tar.gz"),
+                (
+                    "0.14.1",
+                    "WasmEdge-plugin-wasi_logging-0.14.1-manylinux2014_aarch64.tar.gz"
+                ),
+                (
+                    "0.15.0",
+                    "WasmEdge-plugin-wasi_nn-0.15.0-manylinux_2_28_aarch64.tar.gz"
+                ),
+            ]
+        );
+        assert!(planned[1]
+            .url
+            .as_str()
+            .ends_with("/0.14.1/WasmEdge-plugin-wasi_logging-0.14.1-manylinux2014_aarch64.tar.gz"));
+    }
+
+    #[test]
+    fn refuses_non_empty_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        ensure_empty_dir(&dir.path().join("new")).unwrap();
+        std::fs::write(dir.path().join("file"), "").unwrap();
+        let err = ensure_empty_dir(dir.path()).unwrap_err();
+        assert!(matches!(err, Error::InvalidPath { .. }), "{err:?}");
+    }
+}
@@ -0,0 +1,134 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{
+    cache::{parse_age, CacheEntry, DownloadCache},
+    cli::{CommandContext, CommandExecutor},
+    output::print_json,
+    prelude::*,
+};
+
+#[derive(Debug, Parser)]
+pub struct CacheCli {
+    #[command(subcommand)]
+    commands: CacheCommands,
+
+    /// Use this cache directory instead of the default
+    ///
+    /// Defaults to `$WASMEDGEUP_CACHE_DIR`, else `wasmedgeup/downloads` in the user cache directory.
+    #[arg(long, global = true)]
+    dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommands {
+    /// List cached archives
+    List,
+    /// Remove every cached archive
+    Clean,
+    /// Remove cached archives that have not been used recently
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PruneArgs {
+    /// Remove archives not used within this long, e.g. `30d`, `12h`, `2w`
+    #[arg(long, value_parser = parse_age)]
+    older_than: Duration,
+}
+
+impl CommandExecutor for CacheCli {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let cache = match self.dir {
+            Some(dir) => DownloadCache::new(dir),
+            None => DownloadCache::default_location().ok_or(Error::HomeDirNotFound)?,
+        };
+
+        match self.commands {
+            CacheCommands::List => {
+                let entries = cache.entries()?;
+                if ctx.output.is_json() {
+                    return print_json(&serde_json::json!({
+                        "cache_dir": cache.root(),
+                        "entries": entries,
+                    }));
+                }
+                print_entries(&cache, &entries);
+            }
+            CacheCommands::Clean => {
+                let removed = cache.clean()?;
+                report_removed(&ctx, &cache, &removed)?;
+            }
+            CacheCommands::Prune(args) => {
+                let removed = cache.prune(args.older_than)?;
+                report_removed(&ctx, &cache, &removed)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_entries(cache: &DownloadCache, entries: &[CacheEntry]) {
+    println!("Cache: {}", cache.root().display());
+    if entries.is_empty() {
+        println!("\nNo cached archives.");
+        return;
+    }
+    println!();
+    for e in entries {
+        println!(
+            "{:<56} {:>10}  {:>6}  {}",
+            e.archive_name,
+            human_size(e.size),
+            human_age(e.age_secs),
+            &e.sha256[..e.sha256.len().min(12)]
+        );
+    }
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    println!("\n{} archive(s), {}", entries.len(), human_size(total));
+}
+
+fn report_removed(
+    ctx: &CommandContext,
+    cache: &DownloadCache,
+    removed: &[CacheEntry],
+) -> Result<()> {
+    let freed: u64 = removed.iter().map(|e| e.size).sum();
+    if ctx.output.is_json() {
+        return print_json(&serde_json::json!({
+            "cache_dir": cache.root(),
+            "removed": removed,
+            "freed_bytes": freed,
+        }));
+    }
+    println!(
+        "Removed {} archive(s), freed {}",
+        removed.len(),
+        human_size(freed)
+    );
+    Ok(())
+}
+
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn human_age(secs: u64) -> String {
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
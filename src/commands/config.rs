@@ -0,0 +1,134 @@
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    cli::{CommandContext, CommandExecutor},
+    config::{mask_secret, Config, RawConfig, KEYS, SECRET_KEYS},
+    output::print_json,
+    prelude::*,
+};
+
+#[derive(Debug, Parser)]
+pub struct ConfigCli {
+    #[command(subcommand)]
+    commands: ConfigCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommands {
+    /// List the settings in the config file
+    List,
+    /// Print the value of a setting
+    Get(KeyArgs),
+    /// Change a setting in the config file
+    Set(SetArgs),
+    /// Remove a setting from the config file, restoring its default
+    Unset(KeyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct KeyArgs {
+    /// Setting name, e.g. `path` or `mirror.dist_server`
+    key: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SetArgs {
+    /// Setting name, e.g. `path` or `mirror.dist_server`
+    key: String,
+
+    /// New value, e.g. `30`, `false`, `'["wasi_logging"]'` or a plain string
+    value: String,
+}
+
+impl CommandExecutor for ConfigCli {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let path = Config::default_path().ok_or(Error::HomeDirNotFound)?;
+        // Read the file again rather than using `ctx.config`, so `set` and
+        // `unset` rewrite exactly what is on disk, and a file other commands
+        // ignore as invalid can still be listed and fixed.
+        let mut config = RawConfig::load(&path)?;
+
+        match self.commands {
+            ConfigCommands::List => {
+                let entries = config.entries();
+                let problem = config.to_config(&path).err().map(|e| {
+                    std::error::Error::source(&e).map_or_else(|| e.to_string(), ToString::to_string)
+                });
+                if ctx.output.is_json() {
+                    return print_json(&serde_json::json!({
+                        "config_file": path,
+                        "settings": masked_table(config.table()),
+                        "error": problem,
+                    }));
+                }
+                println!("Config: {}", path.display());
+                if let Some(problem) = problem {
+                    println!("\nThis file is invalid and ignored by other commands:\n{problem}");
+                }
+                if entries.is_empty() {
+                    println!("\nNo settings. Known keys: {}", KEYS.join(", "));
+                    return Ok(());
+                }
+                println!();
+                for (key, value) in entries {
+                    let value = mask_secret(&key, value);
+                    println!("{key} = {value}");
+                }
+            }
+            ConfigCommands::Get(KeyArgs { key }) => {
+                let value = config.get(&key)?.map(|v| mask_secret(&key, v));
+                if ctx.output.is_json() {
+                    return print_json(&serde_json::json!({ "key": key, "value": value }));
+                }
+                match value {
+                    Some(toml::Value::String(s)) => println!("{s}"),
+                    Some(value) => println!("{value}"),
+                    None => {}
+                }
+            }
+            ConfigCommands::Set(SetArgs { key, value }) => {
+                config.set(&key, &value)?;
+                config.save(&path)?;
+                let value = config.get(&key)?.map(|v| mask_secret(&key, v));
+                report(&ctx, &path, &key, value)?;
+            }
+            ConfigCommands::Unset(KeyArgs { key }) => {
+                config.unset(&key)?;
+                config.save(&path)?;
+                report(&ctx, &path, &key, None)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `table` with the values of [`SECRET_KEYS`] masked.
+fn masked_table(table: &toml::Table) -> toml::Table {
+    let mut table = table.clone();
+    for key in SECRET_KEYS {
+        if let Some(value) = table.remove(*key) {
+            table.insert(key.to_string(), mask_secret(key, value));
+        }
+    }
+    table
+}
+
+fn report(
+    ctx: &CommandContext,
+    path: &std::path::Path,
+    key: &str,
+    value: Option<toml::Value>,
+) -> Result<()> {
+    if ctx.output.is_json() {
+        return print_json(&serde_json::json!({
+            "config_file": path,
+            "key": key,
+            "value": value,
+        }));
+    }
+    match value {
+        Some(value) => println!("Set {key} = {value} in {}", path.display()),
+        None => println!("Unset {key} in {}", path.display()),
+    }
+    Ok(())
+}
@@ -0,0 +1,526 @@
+use clap::Parser;
+use serde::Serialize;
+use snafu::ResultExt;
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    api::latest_installed_version,
+    cli::{CommandContext, CommandExecutor},
+    commands::{default_path, plugin::utils::extract_plugin_name},
+    output::print_json,
+    prelude::*,
+    shim,
+    system::{self, SystemSpec},
+};
+
+const STABLE_LINKS: [&str; 4] = ["bin", "include", "lib", "plugin"];
+
+#[derive(Debug, Parser)]
+pub struct DoctorArgs {
+    /// Set the install location for the WasmEdge runtime
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single diagnostic, with a suggested fix when there is one.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Stable identifier of the check that produced the finding.
+    pub check: &'static str,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn new(severity: Severity, check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            check,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    system: &'a SystemSpec,
+    install_root: &'a Path,
+    current_version: Option<&'a str>,
+    findings: &'a [Finding],
+}
+
+impl CommandExecutor for DoctorArgs {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let target_dir = match self.path {
+            Some(p) => p,
+            None => default_path()?,
+        };
+        let spec = system::detect();
+        let current = crate::fs::current_version(&target_dir).await;
+
+        let mut findings: Vec<Finding> = spec
+            .detection_errors
+            .iter()
+            .map(|e| {
+                Finding::new(Severity::Warning, "system-detection", e.clone()).with_fix(
+                    "Plugin variant selection may be off; choose the variant explicitly \
+                     (e.g. `wasmedgeup plugin install wasi_nn-ggml-noavx`)",
+                )
+            })
+            .collect();
+        findings.extend(check_links(&target_dir, current.as_deref())?);
+        #[cfg(unix)]
+        findings.extend(check_shell_setup(&target_dir, current.as_deref()));
+        findings.extend(check_path_resolution(
+            &target_dir,
+            system::which_bin("wasmedge"),
+        ));
+        findings.extend(check_plugin_path(
+            &target_dir,
+            current.as_deref(),
+            std::env::var_os("WASMEDGE_PLUGIN_PATH"),
+        ));
+
+        if ctx.output.is_json() {
+            print_json(&Report {
+                system: &spec,
+                install_root: &target_dir,
+                current_version: current.as_deref(),
+                findings: &findings,
+            })?;
+        } else {
+            print_report(&spec, &target_dir, current.as_deref(), &findings)?;
+        }
+
+        let problems = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        if problems > 0 {
+            return Err(Error::DoctorFoundProblems { count: problems });
+        }
+        Ok(())
+    }
+}
+
+fn print_report(
+    spec: &SystemSpec,
+    install_root: &Path,
+    current: Option<&str>,
+    findings: &[Finding],
+) -> Result<()> {
+    let system = serde_json::to_string_pretty(spec).context(JsonSnafu {
+        resource: "system spec",
+    })?;
+    println!("System:\n{system}\n");
+    println!("Install root: {}", install_root.display());
+    println!("Current version: {}\n", current.unwrap_or("<none>"));
+
+    if findings.is_empty() {
+        println!("No problems found.");
+        return Ok(());
+    }
+    for f in findings {
+        let label = match f.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        println!("[{label}] {}: {}", f.check, f.message);
+        if let Some(fix) = &f.fix {
+            println!("    fix: {fix}");
+        }
+    }
+    Ok(())
+}
+
+/// Check the stable `bin`/`include`/`lib`/`plugin` links of `root`: they
+/// must exist, resolve, and agree on a single version.
+pub fn check_links(root: &Path, current: Option<&str>) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let versions_dir = root.join("versions");
+
+    let suggested = match current {
+        Some(v) if versions_dir.join(v).is_dir() => Some(v.to_string()),
+        _ => latest_installed_version(&versions_dir)?.map(|v| v.to_string()),
+    };
+    let Some(suggested) = suggested else {
+        findings.push(
+            Finding::new(
+                Severity::Warning,
+                "install-root",
+                format!("No WasmEdge runtime is installed under {}", root.display()),
+            )
+            .with_fix("Run `wasmedgeup install latest`"),
+        );
+        return Ok(findings);
+    };
+    let use_cmd = format!("`wasmedgeup use {suggested}`");
+    let use_fix = format!("Run {use_cmd}");
+
+    let mut link_versions: Vec<(&str, String)> = Vec::new();
+    for dir in STABLE_LINKS {
+        let link_path = root.join(dir);
+        let Ok(meta) = std::fs::symlink_metadata(&link_path) else {
+            findings.push(
+                Finding::new(
+                    Severity::Error,
+                    "links",
+                    format!("`{}` is missing", link_path.display()),
+                )
+                .with_fix(use_fix.clone()),
+            );
+            continue;
+        };
+
+        if meta.file_type().is_symlink() {
+            let target = std::fs::read_link(&link_path)?;
+            if !root.join(&target).exists() {
+                findings.push(
+                    Finding::new(
+                        Severity::Error,
+                        "links",
+                        format!(
+                            "`{}` points to `{}`, which does not exist",
+                            link_path.display(),
+                            target.display()
+                        ),
+                    )
+                    .with_fix(use_fix.clone()),
+                );
+            } else if let Some(v) = crate::fs::link_version(root, &target) {
+                link_versions.push((dir, v));
+            }
+        } else if dir == "bin" && shim::is_shim_dir(&link_path) {
+            findings.push(Finding::new(
+                Severity::Info,
+                "links",
+                format!(
+                    "Shims are enabled in `{}`; the runtime version is resolved per invocation",
+                    link_path.display()
+                ),
+            ));
+        } else {
+            findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    "links",
+                    format!(
+                        "`{}` is not a link managed by wasmedgeup",
+                        link_path.display()
+                    ),
+                )
+                .with_fix(format!(
+                    "Move `{}` aside, then run {use_cmd}",
+                    link_path.display()
+                )),
+            );
+        }
+    }
+
+    if let Some((_, first)) = link_versions.first() {
+        if link_versions.iter().any(|(_, v)| v != first) {
+            let detail = link_versions
+                .iter()
+                .map(|(dir, v)| format!("{dir} -> {v}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            findings.push(
+                Finding::new(
+                    Severity::Error,
+                    "links",
+                    format!("Links point at different versions ({detail})"),
+                )
+                .with_fix(use_fix),
+            );
+        }
+    }
+    Ok(findings)
+}
+
+/// Check that the env scripts exist and the shell rc files source them.
+#[cfg(unix)]
+fn check_shell_setup(root: &Path, current: Option<&str>) -> Vec<Finding> {
+    if !root.join("versions").is_dir() {
+        return Vec::new();
+    }
+    let status = crate::shell_utils::setup_status(root);
+    let reinstall = format!(
+        "Run `wasmedgeup install {}` to regenerate it",
+        current.unwrap_or("latest")
+    );
+
+    let mut findings: Vec<Finding> = status
+        .missing_env_scripts
+        .into_iter()
+        .map(|script| {
+            Finding::new(
+                Severity::Error,
+                "env-script",
+                format!("`{}` is missing", script.display()),
+            )
+            .with_fix(reinstall.clone())
+        })
+        .collect();
+    findings.extend(status.unsourced_rc_files.into_iter().map(|(rc, line)| {
+        Finding::new(
+            Severity::Warning,
+            "shell-rc",
+            format!("`{}` does not load the WasmEdge environment", rc.display()),
+        )
+        .with_fix(format!("Add `{line}` to `{}`", rc.display()))
+    }));
+    findings
+}
+
+/// Check that `wasmedge` on PATH (`resolved`) comes from this install root.
+pub fn check_path_resolution(root: &Path, resolved: Option<PathBuf>) -> Vec<Finding> {
+    if !root.join("versions").is_dir() {
+        return Vec::new();
+    }
+    let bin = root.join("bin");
+    let Some(resolved) = resolved else {
+        return vec![
+            Finding::new(Severity::Warning, "path", "`wasmedge` is not on PATH").with_fix(format!(
+                "Add `{}` to PATH, or open a new shell so the env script is loaded",
+                bin.display()
+            )),
+        ];
+    };
+
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let resolved_dir = resolved.parent().map(canonical);
+    let ours = [canonical(&bin), canonical(&root.join("versions"))];
+    if resolved_dir
+        .as_deref()
+        .is_some_and(|dir| ours.iter().any(|o| dir.starts_with(o)))
+    {
+        return Vec::new();
+    }
+    vec![Finding::new(
+        Severity::Warning,
+        "path",
+        format!(
+            "`wasmedge` resolves to `{}`, outside {}",
+            resolved.display(),
+            root.display()
+        ),
+    )
+    .with_fix(format!(
+        "Remove the other installation or put `{}` first on PATH",
+        bin.display()
+    ))]
+}
+
+/// Check the plugins the runtime loads: the shared objects in
+/// `<root>/plugin` and in each `WASMEDGE_PLUGIN_PATH` entry. A plugin only
+/// works with the runtime version it was built for, so one that resolves
+/// into another `versions/<v>` is an error; one from outside the install
+/// root has no known version and is a warning.
+pub fn check_plugin_path(
+    root: &Path,
+    current: Option<&str>,
+    plugin_path: Option<OsString>,
+) -> Vec<Finding> {
+    let Some(current) = current else {
+        return Vec::new();
+    };
+    let stable = root.join("plugin");
+    let real_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let dirs =
+        std::iter::once(stable.clone()).chain(plugin_path.iter().flat_map(std::env::split_paths));
+
+    let mut seen = HashSet::new();
+    let mut findings = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut plugins: Vec<(PathBuf, String)> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter_map(|path| extract_plugin_name(&path).map(|name| (path, name)))
+            .collect();
+        plugins.sort();
+        for (path, name) in plugins {
+            let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+            // `<root>/plugin` usually appears in the variable too.
+            if !seen.insert(real.clone()) {
+                continue;
+            }
+            let version = real
+                .strip_prefix(&real_root)
+                .ok()
+                .and_then(|rel| crate::fs::link_version(root, rel));
+            let install = format!("wasmedgeup plugin install {name} --runtime {current}");
+            let finding = match version {
+                Some(version) if version == current => continue,
+                Some(version) => Finding::new(
+                    Severity::Error,
+                    "plugins",
+                    format!(
+                        "`{}` is a plugin for {version}, but the active version is {current}",
+                        path.display()
+                    ),
+                ),
+                None => Finding::new(
+                    Severity::Warning,
+                    "plugins",
+                    format!(
+                        "`{}` was not installed by wasmedgeup and may not match the active version {current}",
+                        path.display()
+                    ),
+                ),
+            };
+            findings.push(if dir == stable {
+                finding.with_fix(format!("Run `{install}`"))
+            } else {
+                finding.with_fix(format!(
+                    "Remove `{}` from WASMEDGE_PLUGIN_PATH, or run `{install}` and use `{}`",
+                    dir.display(),
+                    stable.display()
+                ))
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::plugin::utils::plugin_filename;
+
+    fn mock_version(root: &Path, version: &str) {
+        for dir in STABLE_LINKS {
+            std::fs::create_dir_all(root.join("versions").join(version).join(dir)).unwrap();
+        }
+    }
+
+    #[test]
+    fn empty_root_suggests_install() {
+        let root = tempfile::tempdir().unwrap();
+        let findings = check_links(root.path(), None).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].check, "install-root");
+        assert!(findings[0]
+            .fix
+            .as_deref()
+            .unwrap()
+            .contains("install latest"));
+    }
+
+    #[tokio::test]
+    async fn healthy_links_have_no_findings() {
+        let root = tempfile::tempdir().unwrap();
+        mock_version(root.path(), "0.15.0");
+        crate::fs::create_version_symlinks(root.path(), "0.15.0")
+            .await
+            .unwrap();
+        let findings = check_links(root.path(), Some("0.15.0")).unwrap();
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn broken_and_mismatched_links_are_errors() {
+        let root = tempfile::tempdir().unwrap();
+        mock_version(root.path(), "0.14.1");
+        mock_version(root.path(), "0.15.0");
+        crate::fs::create_version_symlinks(root.path(), "0.15.0")
+            .await
+            .unwrap();
+
+        std::fs::remove_file(root.path().join("plugin")).unwrap();
+        std::os::unix::fs::symlink("versions/0.14.1/plugin", root.path().join("plugin")).unwrap();
+        std::fs::remove_file(root.path().join("include")).unwrap();
+        std::os::unix::fs::symlink("versions/0.13.0/include", root.path().join("include")).unwrap();
+
+        let findings = check_links(root.path(), Some("0.15.0")).unwrap();
+        assert!(findings.iter().all(|f| f.severity == Severity::Error));
+        assert!(findings
+            .iter()
+            .any(|f| f.message.contains("does not exist")));
+        assert!(findings
+            .iter()
+            .any(|f| f.message.contains("plugin -> 0.14.1")));
+        assert!(findings
+            .iter()
+            .all(|f| f.fix.as_deref() == Some("Run `wasmedgeup use 0.15.0`")));
+    }
+
+    #[test]
+    fn path_outside_root_is_reported() {
+        let root = tempfile::tempdir().unwrap();
+        mock_version(root.path(), "0.15.0");
+
+        let ours = root.path().join("bin").join("wasmedge");
+        assert!(check_path_resolution(root.path(), Some(ours)).is_empty());
+
+        let other = PathBuf::from("/usr/local/bin/wasmedge");
+        let findings = check_path_resolution(root.path(), Some(other));
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("/usr/local/bin/wasmedge"));
+
+        assert_eq!(check_path_resolution(root.path(), None).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn plugins_for_other_versions_are_reported() {
+        let root = tempfile::tempdir().unwrap();
+        let plugin = plugin_filename("WasiLogging");
+        for version in ["0.14.1", "0.15.0"] {
+            mock_version(root.path(), version);
+            let dir = root.path().join("versions").join(version).join("plugin");
+            std::fs::write(dir.join(&plugin), "").unwrap();
+            std::fs::write(dir.join("README.md"), "").unwrap();
+        }
+        crate::fs::create_version_symlinks(root.path(), "0.15.0")
+            .await
+            .unwrap();
+        let foreign = tempfile::tempdir().unwrap();
+        std::fs::write(foreign.path().join(&plugin), "").unwrap();
+
+        let other = root.path().join("versions").join("0.14.1").join("plugin");
+        let stable = root.path().join("plugin");
+        let plugin_path = std::env::join_paths([&other, &stable, foreign.path()]).unwrap();
+
+        let findings = check_plugin_path(root.path(), Some("0.15.0"), Some(plugin_path));
+        assert_eq!(findings.len(), 2, "{findings:?}");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert!(findings[0].message.contains("0.14.1"));
+        assert_eq!(findings[1].severity, Severity::Warning);
+        assert!(findings[1]
+            .message
+            .contains(&foreign.path().display().to_string()));
+
+        assert!(check_plugin_path(root.path(), Some("0.15.0"), None).is_empty());
+        // The plugin link pointing at another version is caught too.
+        crate::fs::create_version_symlinks(root.path(), "0.14.1")
+            .await
+            .unwrap();
+        assert_eq!(
+            check_plugin_path(root.path(), Some("0.15.0"), None).len(),
+            1
+        );
+    }
+}
@@ -1,15 +1,24 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
-use tokio::fs;
+use indicatif::MultiProgress;
+use semver::Version;
+use snafu::ResultExt;
+use tempfile::TempDir;
+use tokio::{fs, task::JoinSet};
 
 use crate::{
-    api::{Asset, WasmEdgeApiClient},
+    api::{Asset, VersionSpec, WasmEdgeApiClient},
+    bundle::LocalArchive,
     cli::{CommandContext, CommandExecutor},
     commands::default_path,
+    lock::InstallLock,
+    manifest::InstallManifest,
+    output::print_json,
     prelude::*,
     shell_utils,
     target::{TargetArch, TargetOS},
+    transaction::InstallTransaction,
 };
 
 fn default_tmpdir() -> PathBuf {
@@ -18,8 +27,18 @@ fn default_tmpdir() -> PathBuf {
 
 #[derive(Debug, Parser)]
 pub struct InstallArgs {
-    /// WasmEdge version to install, e.g. `latest`, `0.14.1`, `0.14.1-rc.1`, etc.
-    pub version: String,
+    /// WasmEdge versions to install, e.g. `latest`, `0.14.1`, `0.14.1-rc.1`, `~0.14`, `0.14.x`, `prerelease`, etc.
+    ///
+    /// Requirements and channels resolve to the newest matching release. Several versions are
+    /// downloaded concurrently.
+    #[arg(required = true, num_args = 1..)]
+    pub versions: Vec<String>,
+
+    /// Version to make the default when installing several, as given on the command line
+    ///
+    /// Defaults to the first version given.
+    #[arg(long, value_name = "VERSION")]
+    pub default: Option<String>,
 
     /// Set the install location for the WasmEdge runtime
     ///
@@ -29,7 +48,7 @@ pub struct InstallArgs {
 
     /// Set the temporary directory for staging downloaded assets
     ///
-    /// Defaults to the system temporary directory, this differs between operating systems.
+    /// Defaults to `tmpdir` from the config file, else the system temporary directory, this differs between operating systems.
     #[arg(short, long)]
     pub tmpdir: Option<PathBuf>,
 
@@ -47,21 +66,81 @@ pub struct InstallArgs {
 
     /// Skip checksum retrieval and verification for the downloaded asset
     ///
-    /// This option disables integrity verification.
-    #[arg(long)]
+    /// This option disables integrity verification. Implied by `verify = false` in the config file.
+    #[arg(long, overrides_with = "verify")]
     pub no_verify: bool,
+
+    /// Verify checksums even if `verify = false` is set in the config file
+    #[arg(long, overrides_with = "no_verify")]
+    pub verify: bool,
+
+    /// Keep the current default version instead of switching to the installed one
+    ///
+    /// The installed version still becomes the default if none is set yet.
+    #[arg(long)]
+    pub no_switch: bool,
+
+    /// Install from this local archive instead of downloading it
+    ///
+    /// Requires a single, exact version. The archive is verified against the `SHA256SUM` file in its directory.
+    #[arg(long, value_name = "ARCHIVE", conflicts_with = "from_dir")]
+    pub from_file: Option<PathBuf>,
+
+    /// Install from a local bundle directory instead of downloading
+    ///
+    /// The archive is looked up in `<dir>/<version>/` or `<dir>/` and verified against the `SHA256SUM` next to it; channels and requirements resolve against the releases in the bundle.
+    #[arg(long, value_name = "DIR")]
+    pub from_dir: Option<PathBuf>,
+}
+
+impl InstallArgs {
+    /// Resolve a requested version. Local installs never touch the
+    /// network: `--from-file` needs an exact version, and `--from-dir`
+    /// resolves channels and requirements against the release directories
+    /// in the bundle.
+    async fn resolve_version(&self, ctx: &CommandContext, requested: &str) -> Result<Version> {
+        if self.from_file.is_none() && self.from_dir.is_none() {
+            return ctx.client.resolve_version(requested).await;
+        }
+        match (VersionSpec::parse(requested)?, &self.from_dir) {
+            (VersionSpec::Exact(version), _) => Ok(version),
+            (spec, Some(bundle)) => {
+                spec.select(&crate::bundle::tags(bundle)?)
+                    .ok_or(Error::VersionNotFound {
+                        version: requested.to_string(),
+                    })
+            }
+            (_, None) => Version::parse(requested).context(SemVerSnafu {}),
+        }
+    }
+
+    /// Index into `versions` of the version to make the default.
+    fn default_index(&self) -> Result<usize> {
+        let Some(default) = &self.default else {
+            return Ok(0);
+        };
+        self.versions
+            .iter()
+            .position(|v| v == default)
+            .ok_or(Error::DefaultVersionNotRequested {
+                version: default.clone(),
+            })
+    }
 }
 
 impl CommandExecutor for InstallArgs {
-    /// Executes the installation process by resolving the version, downloading the asset,
-    /// unpacking it, and copying the extracted files to the target directory.
+    /// Executes the installation process by resolving the versions, downloading the assets,
+    /// unpacking them, and copying the extracted files to the target directory.
     ///
     /// # Steps:
-    /// 1. Resolves the version (either a specific version or the latest).
-    /// 2. Downloads the asset for the appropriate OS and architecture.
-    /// 3. Unpacks the asset to a temporary directory.
+    /// 1. Resolves each version (either a specific version, a requirement or the latest).
+    /// 2. Downloads and verifies the assets for the appropriate OS and architecture
+    ///    concurrently, or takes them from `--from-file`/`--from-dir` without touching
+    ///    the network.
+    /// 3. Unpacks each asset to a temporary directory, one version after another.
     /// 4. Copies the extracted files to the target directory.
-    /// 5. Add the installed bin directory to PATH
+    /// 5. Switches the symlinks to the default version.
+    /// 6. Add the installed bin directory to PATH
     ///
     /// # Arguments
     ///
@@ -71,155 +150,372 @@ impl CommandExecutor for InstallArgs {
     ///
     /// Returns an error if any step fails, such as download failure, extraction issues,
     /// or copying issues.
-    #[tracing::instrument(name = "install", skip_all, fields(version = self.version))]
-    async fn execute(mut self, ctx: CommandContext) -> Result<()> {
-        let version = ctx
-            .client
-            .resolve_version(&self.version)
-            .await
-            .inspect_err(
+    #[tracing::instrument(name = "install", skip_all, fields(versions = ?self.versions))]
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let installed = self.install(&ctx).await?;
+        let Some(default) = installed.iter().find(|i| i.default) else {
+            return Err(Error::NoVersionSpecified);
+        };
+
+        if ctx.output.is_json() {
+            return print_json(&serde_json::json!({
+                "version": default.version.to_string(),
+                "install_root": default.install_root,
+                "version_dir": default.version_dir,
+                "versions": installed.iter().map(|i| i.version.to_string()).collect::<Vec<_>>(),
+            }));
+        }
+        for i in &installed {
+            println!("Installed WasmEdge {}", i.version);
+        }
+        println!("Install root: {}", default.install_root.display());
+
+        Ok(())
+    }
+}
+
+/// A runtime version installed by [`InstallArgs::install`].
+pub(crate) struct Installed {
+    pub version: Version,
+    pub install_root: PathBuf,
+    pub version_dir: PathBuf,
+    /// Whether this is the version requested as the default.
+    pub default: bool,
+}
+
+/// A downloaded and verified runtime archive waiting to be unpacked.
+struct Staged {
+    version: Version,
+    workspace: TempDir,
+    file: std::fs::File,
+    /// Where the archive came from: its URL or local path.
+    source: String,
+    sha256: String,
+}
+
+impl InstallArgs {
+    /// Install the runtimes without printing a result, so commands built on
+    /// top of `install` (e.g. `update`) can report once for the whole run.
+    ///
+    /// Returns the installed versions in the order they were requested,
+    /// with versions requested more than once installed once.
+    pub(crate) async fn install(mut self, ctx: &CommandContext) -> Result<Vec<Installed>> {
+        if self.tmpdir.is_none() {
+            self.tmpdir = ctx.config.tmpdir.clone();
+        }
+        self.no_verify = ctx.config.skip_verification(self.verify, self.no_verify);
+        if let (Some(archive), true) = (&self.from_file, self.versions.len() > 1) {
+            return Err(Error::InvalidPath {
+                path: archive.display().to_string(),
+                reason: "--from-file installs a single version".to_string(),
+            });
+        }
+        let default_index = self.default_index()?;
+
+        let mut versions: Vec<Version> = Vec::new();
+        let mut default = None;
+        for (i, requested) in self.versions.iter().enumerate() {
+            let version = self.resolve_version(ctx, requested).await.inspect_err(
                 |e| tracing::error!(error = %e.to_string(), "Failed to resolve version"),
             )?;
-        tracing::debug!(%version, "Resolved version for installation");
+            tracing::debug!(%version, "Resolved version for installation");
+            if i == default_index {
+                default = Some(version.clone());
+            }
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+        let Some(default) = default else {
+            return Err(Error::NoVersionSpecified);
+        };
 
-        let os = self.os.get_or_insert_default();
-        let arch = self.arch.get_or_insert_default();
+        let os = *self.os.get_or_insert_default();
+        let arch = *self.arch.get_or_insert_default();
         tracing::debug!(?os, ?arch, "Host OS and architecture detected");
 
-        let asset = Asset::new(&version, os, arch);
+        let target_dir = match self.path.take() {
+            Some(p) => p,
+            None => default_path()?,
+        };
+        ensure_writable(&target_dir, &default).await?;
+        let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
 
-        // Stage this installation in an isolated temporary workspace with a
-        // randomized name (see `create_temp_workspace`) for isolation between
-        // concurrent installs and consistent handling of archive structures.
-        // The source path for copying is either:
-        //   - <workspace>/ (for archives with root-level files)
-        //   - <workspace>/WasmEdge-<version>-<os>/ (for nested archives)
-        let tmpbase = self.tmpdir.unwrap_or_else(default_tmpdir);
-        let workspace = crate::fs::create_temp_workspace(&tmpbase, &asset.install_name)
-            .inspect_err(
-                |e| tracing::error!(error = %e.to_string(), "Failed to create temporary workspace"),
-            )?;
-        let tmpdir = workspace.path().to_path_buf();
-        tracing::debug!(tmpdir = %tmpdir.display(), "Created temporary workspace directory");
+        // Each version is staged in its own isolated temporary workspace
+        // with a randomized name (see `create_temp_workspace`), so the
+        // concurrent downloads below never share a directory.
+        let tmpbase = self.tmpdir.clone().unwrap_or_else(default_tmpdir);
+        let client = if versions.len() > 1 && !ctx.no_progress {
+            ctx.client.clone().with_progress(MultiProgress::new())
+        } else {
+            ctx.client.clone()
+        };
+
+        let mut tasks = JoinSet::new();
+        for (i, version) in versions.iter().enumerate() {
+            let asset = Asset::new(version, &os, &arch);
+            let local = match (&self.from_file, &self.from_dir) {
+                (Some(archive), _) => Some(LocalArchive::from_file(archive, &version.to_string())?),
+                (None, Some(bundle)) => Some(LocalArchive::find(
+                    bundle,
+                    &version.to_string(),
+                    &asset.archive_name,
+                )?),
+                (None, None) => None,
+            };
+            let client = client.clone();
+            let tmpbase = tmpbase.clone();
+            let no_progress = ctx.no_progress;
+            let no_verify = self.no_verify;
+            tasks.spawn(async move {
+                let staged = stage(&client, asset, &tmpbase, local, no_progress, no_verify).await;
+                (i, staged)
+            });
+        }
+
+        // Dropping `tasks` on the first failure aborts the other downloads.
+        let mut staged: Vec<Option<Staged>> = versions.iter().map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            let (i, result) = joined.map_err(|e| Error::Io {
+                action: "runtime download task".to_string(),
+                path: tmpbase.display().to_string(),
+                source: crate::error::join_err_to_io_error(e),
+            })?;
+            staged[i] = Some(result?);
+        }
 
-        let mut file = ctx
-            .client
-            .download_asset(&asset, &tmpdir, ctx.no_progress)
+        let mut txn = InstallTransaction::new(&target_dir);
+        match self
+            .finish(
+                staged.into_iter().flatten(),
+                &default,
+                &target_dir,
+                &mut txn,
+            )
             .await
-            .inspect_err(|e| tracing::error!(error = %e.to_string(), "Failed to download asset"))?
-            .into_file();
+        {
+            Ok(installed) => {
+                txn.commit();
+                Ok(installed)
+            }
+            Err(e) => {
+                tracing::error!(error = %e.to_string(), "Install failed; rolling back");
+                txn.rollback();
+                Err(e)
+            }
+        }
+    }
 
-        if self.no_verify {
+    /// Unpack the staged versions into place, switch the links and set up
+    /// PATH, recording every change in `txn`.
+    async fn finish(
+        &self,
+        staged: impl Iterator<Item = Staged>,
+        default: &Version,
+        target_dir: &Path,
+        txn: &mut InstallTransaction,
+    ) -> Result<Vec<Installed>> {
+        let mut installed = Vec::new();
+        for staged in staged {
+            let version = staged.version.clone();
+            let version_dir = unpack(staged, txn).await?;
+            installed.push(Installed {
+                default: version == *default,
+                version,
+                install_root: target_dir.to_path_buf(),
+                version_dir,
+            });
+        }
+
+        if self.no_switch && crate::fs::current_version(target_dir).await.is_some() {
+            tracing::debug!("Keeping the current default version due to --no-switch flag");
+        } else {
+            tracing::debug!(version = %default, "Creating version symlinks");
+            txn.record_links();
+            crate::fs::create_version_symlinks(target_dir, &default.to_string()).await?;
+        }
+        txn.record_files(shell_utils::setup_files(target_dir));
+        shell_utils::setup_path(target_dir)?;
+
+        Ok(installed)
+    }
+}
+
+/// Download and verify the runtime `asset` into a fresh workspace under
+/// `tmpbase`, or verify the `local` archive instead when one is given.
+async fn stage(
+    client: &WasmEdgeApiClient,
+    asset: Asset,
+    tmpbase: &Path,
+    local: Option<LocalArchive>,
+    no_progress: bool,
+    no_verify: bool,
+) -> Result<Staged> {
+    let version = asset.version.clone();
+    let workspace = crate::fs::create_temp_workspace(tmpbase, &asset.install_name).inspect_err(
+        |e| tracing::error!(error = %e.to_string(), "Failed to create temporary workspace"),
+    )?;
+    let tmpdir = workspace.path().to_path_buf();
+    tracing::debug!(%version, tmpdir = %tmpdir.display(), "Created temporary workspace directory");
+
+    let (mut file, source, expected_checksum) = if let Some(local) = local {
+        tracing::debug!(archive = %local.path.display(), "Installing from local archive");
+        let file = local
+            .open_verified(no_verify, &client.signatures)
+            .await
+            .inspect_err(
+                |e| tracing::error!(error = %e.to_string(), "Failed to verify local archive"),
+            )?;
+        (file, local.path.display().to_string(), None)
+    } else {
+        let expected_checksum = if no_verify {
             tracing::warn!("Skipping checksum retrieval and verification due to --no-verify flag");
+            None
         } else {
-            let expected_checksum = ctx
-                .client
+            let checksum = client
                 .get_release_checksum(&version, &asset)
                 .await
                 .inspect_err(
                     |e| tracing::error!(error = %e.to_string(), "Failed to get checksum"),
                 )?;
-            tracing::debug!(%expected_checksum, "Got release checksum");
-
-            WasmEdgeApiClient::verify_file_checksum(&mut file, &expected_checksum)
-                .await
-                .inspect_err(
-                    |e| tracing::error!(error = %e.to_string(), "Checksum verification failed"),
-                )?;
-            tracing::debug!("Checksum verified successfully");
-        }
+            tracing::debug!(expected_checksum = %checksum, "Got release checksum");
+            Some(checksum)
+        };
 
-        tracing::debug!(dest = %tmpdir.display(), "Starting extraction of asset");
-        crate::fs::extract_archive(file, &tmpdir)
+        // Verifies against `expected_checksum` (and consults the download
+        // cache) when one is given.
+        let file = client
+            .download_asset(&asset, &tmpdir, no_progress, expected_checksum.as_deref())
             .await
-            .inspect_err(|e| tracing::error!(error = %e.to_string(), "Failed to extract asset"))?;
-        tracing::debug!(dest = %tmpdir.display(), "Extraction completed successfully");
+            .inspect_err(|e| tracing::error!(error = %e.to_string(), "Failed to download asset"))?
+            .into_file();
+        let source = asset.url(&client.endpoints)?.to_string();
+        (file, source, expected_checksum)
+    };
+    let sha256 = match expected_checksum {
+        Some(sha256) => sha256,
+        None => crate::fs::file_sha256(&mut file)?,
+    };
 
-        let target_dir = match self.path {
-            Some(p) => p,
-            None => default_path()?,
-        };
+    Ok(Staged {
+        version,
+        workspace,
+        file,
+        source,
+        sha256,
+    })
+}
 
-        if target_dir.exists() {
-            if crate::fs::can_write_to_directory(&target_dir) {
-                tracing::debug!(target_dir = %target_dir.display(), "Verified write permissions");
-            } else {
+/// Check that `target_dir` can be written, creating it if needed.
+async fn ensure_writable(target_dir: &Path, version: &Version) -> Result<()> {
+    if target_dir.exists() {
+        if crate::fs::can_write_to_directory(target_dir) {
+            tracing::debug!(target_dir = %target_dir.display(), "Verified write permissions");
+            return Ok(());
+        }
+        return Err(crate::commands::insufficient_permissions(
+            target_dir,
+            "write to target directory",
+            &version.to_string(),
+        ));
+    }
+    match fs::create_dir_all(target_dir).await {
+        Ok(_) => {
+            if !crate::fs::can_write_to_directory(target_dir) {
+                tracing::debug!(path = %target_dir.display(), "Created directory but cannot write to it");
                 return Err(crate::commands::insufficient_permissions(
-                    &target_dir,
+                    target_dir,
                     "write to target directory",
                     &version.to_string(),
                 ));
             }
-        } else {
-            match fs::create_dir_all(&target_dir).await {
-                Ok(_) => {
-                    if !crate::fs::can_write_to_directory(&target_dir) {
-                        tracing::debug!(path = %target_dir.display(), "Created directory but cannot write to it");
-                        return Err(crate::commands::insufficient_permissions(
-                            &target_dir,
-                            "write to target directory",
-                            &version.to_string(),
-                        ));
-                    }
-                    tracing::debug!(target_dir = %target_dir.display(), "Created target directory");
-                }
-                Err(e) => {
-                    tracing::debug!(error = %e, path = %target_dir.display(), "Failed to create directory");
-                    return Err(crate::commands::insufficient_permissions(
-                        &target_dir,
-                        "create directory",
-                        &version.to_string(),
-                    ));
-                }
-            }
+            tracing::debug!(target_dir = %target_dir.display(), "Created target directory");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::debug!(error = %e, path = %target_dir.display(), "Failed to create directory");
+            Err(crate::commands::insufficient_permissions(
+                target_dir,
+                "create directory",
+                &version.to_string(),
+            ))
         }
+    }
+}
 
-        let version_dir = target_dir.join("versions").join(version.to_string());
-        fs::create_dir_all(&version_dir).await.inspect_err(
-            |e| tracing::error!(error = %e.to_string(), "Failed to create version directory"),
-        )?;
-        tracing::debug!(version_dir = %version_dir.display(), "Created version directory");
-
-        let mut read_dir = fs::read_dir(&tmpdir).await?;
-        let mut source_dir = tmpdir.clone();
-
-        if let Some(entry) = read_dir.next_entry().await? {
-            let file_name = entry.file_name().into_string().unwrap_or_default();
-            if file_name.starts_with("WasmEdge-") && entry.file_type().await?.is_dir() {
-                source_dir = entry.path();
-            } else if !matches!(file_name.as_str(), "bin" | "lib64" | "include" | "lib") {
-                tracing::debug!(found_file = %file_name, "Unexpected file found in archive");
-                return Err(Error::InvalidArchiveStructure {
-                    found_file: file_name,
-                });
-            }
-        } else {
-            tracing::debug!(dir = %tmpdir.display(), "Archive directory is empty");
+/// Extract a staged archive into a staging directory of `txn` and move it
+/// into place as `versions/<version>`, returning that directory.
+async fn unpack(staged: Staged, txn: &mut InstallTransaction) -> Result<PathBuf> {
+    let Staged {
+        version,
+        workspace,
+        file,
+        source,
+        sha256,
+    } = staged;
+    let tmpdir = workspace.path().to_path_buf();
+
+    tracing::debug!(dest = %tmpdir.display(), "Starting extraction of asset");
+    crate::fs::extract_archive(file, &tmpdir)
+        .await
+        .inspect_err(|e| tracing::error!(error = %e.to_string(), "Failed to extract asset"))?;
+    tracing::debug!(dest = %tmpdir.display(), "Extraction completed successfully");
+
+    // The source path for copying is either:
+    //   - <workspace>/ (for archives with root-level files)
+    //   - <workspace>/WasmEdge-<version>-<os>/ (for nested archives)
+    let mut read_dir = fs::read_dir(&tmpdir).await?;
+    let mut source_dir = tmpdir.clone();
+
+    if let Some(entry) = read_dir.next_entry().await? {
+        let file_name = entry.file_name().into_string().unwrap_or_default();
+        if file_name.starts_with("WasmEdge-") && entry.file_type().await?.is_dir() {
+            source_dir = entry.path();
+        } else if !matches!(file_name.as_str(), "bin" | "lib64" | "include" | "lib") {
+            tracing::debug!(found_file = %file_name, "Unexpected file found in archive");
             return Err(Error::InvalidArchiveStructure {
-                found_file: "<empty directory>".to_string(),
+                found_file: file_name,
             });
         }
+    } else {
+        tracing::debug!(dir = %tmpdir.display(), "Archive directory is empty");
+        return Err(Error::InvalidArchiveStructure {
+            found_file: "<empty directory>".to_string(),
+        });
+    }
 
-        tracing::debug!(source_dir = %source_dir.display(), "Start copying files to version directory");
-        crate::fs::copy_tree(&source_dir, &version_dir).await?;
-        tracing::debug!(version_dir = %version_dir.display(), "Copying files to version directory completed");
-
-        // The runtime is already copied into `version_dir`, so failing to remove
-        // the staging workspace must not abort the install or skip the symlink/
-        // PATH setup below. Log and continue, leaving the dir for the temp reaper.
-        if let Err(e) = workspace.close() {
-            tracing::warn!(error = %e.to_string(), tmpdir = %tmpdir.display(), "Failed to clean up temporary workspace; continuing");
-        }
+    let staging_dir = txn.staging_dir(&version.to_string()).inspect_err(
+        |e| tracing::error!(error = %e.to_string(), "Failed to create staging directory"),
+    )?;
+    tracing::debug!(staging_dir = %staging_dir.display(), "Created staging directory");
 
-        tracing::debug!("Creating version symlinks");
-        crate::fs::create_version_symlinks(&target_dir, &version.to_string()).await?;
-        shell_utils::setup_path(&target_dir)?;
+    // Reinstalling keeps what was added to the version since, such as
+    // plugins: the staged copy starts from the installed one.
+    let existing = txn.version_dir(&version.to_string());
+    if existing.is_dir() {
+        tracing::debug!(existing = %existing.display(), "Carrying over existing version directory");
+        crate::fs::copy_tree(&existing, &staging_dir).await?;
+    }
 
-        println!(
-            "Installed WasmEdge {version}\nInstall root: {}",
-            target_dir.display()
-        );
+    tracing::debug!(source_dir = %source_dir.display(), "Start copying files to staging directory");
+    let files = crate::fs::copy_tree(&source_dir, &staging_dir).await?;
+    let mut manifest = InstallManifest::new(&version.to_string());
+    manifest.source = Some(source);
+    manifest.sha256 = Some(sha256);
+    manifest.files = files;
+    if let Some(previous) = InstallManifest::load(&existing) {
+        manifest.plugins = previous.plugins;
+    }
+    manifest.save(&staging_dir)?;
+    let version_dir = txn.place(&staging_dir, &version.to_string())?;
+    tracing::debug!(version_dir = %version_dir.display(), "Moved version directory into place");
 
-        Ok(())
+    // The runtime is already copied into `version_dir`, so failing to remove
+    // the staging workspace must not abort the install or skip the symlink/
+    // PATH setup. Log and continue, leaving the dir for the temp reaper.
+    if let Err(e) = workspace.close() {
+        tracing::warn!(error = %e.to_string(), tmpdir = %tmpdir.display(), "Failed to clean up temporary workspace; continuing");
     }
+
+    Ok(version_dir)
 }
@@ -1,5 +1,12 @@
-use crate::{api::ReleasesFilter, cli::CommandContext, prelude::*};
+use crate::{
+    api::{ReleasesFilter, VersionSpec},
+    cli::CommandContext,
+    output::print_json,
+    prelude::*,
+    transaction::{is_staging_name, stale_dirs},
+};
 use clap::Parser;
+use serde::Serialize;
 use std::path::PathBuf;
 use tokio::fs;
 
@@ -22,6 +29,35 @@ pub struct ListArgs {
     path: Option<PathBuf>,
 }
 
+#[derive(Debug, Serialize)]
+struct RemoteRelease {
+    version: String,
+    latest: bool,
+    prerelease: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RemoteListing {
+    latest: String,
+    releases: Vec<RemoteRelease>,
+}
+
+#[derive(Debug, Serialize)]
+struct InstalledVersion {
+    version: String,
+    current: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct InstalledListing {
+    install_root: PathBuf,
+    current: Option<String>,
+    versions: Vec<InstalledVersion>,
+    /// Staging directories left by interrupted installs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    incomplete: Vec<PathBuf>,
+}
+
 impl CommandExecutor for ListArgs {
     async fn execute(self, ctx: CommandContext) -> Result<()> {
         if self.remote {
@@ -31,12 +67,31 @@ impl CommandExecutor for ListArgs {
                 ReleasesFilter::Stable
             };
 
-            let releases = ctx.client.releases(filter, 10).await?;
-            let latest_release = ctx.client.latest_release().await?;
+            // One listing answers both questions; the latest release is
+            // stable even when `--all` shows prereleases.
+            let all = ctx.client.releases(ReleasesFilter::All, usize::MAX).await?;
+            let latest_release = VersionSpec::Stable
+                .select(&all)
+                .ok_or(Error::NoReleasesFound)?;
+            let releases = all.into_iter().filter(|v| filter.matches(v)).take(10);
+
+            let listing = RemoteListing {
+                latest: latest_release.to_string(),
+                releases: releases
+                    .map(|gh_release| RemoteRelease {
+                        latest: gh_release == latest_release,
+                        prerelease: !gh_release.pre.is_empty(),
+                        version: gh_release.to_string(),
+                    })
+                    .collect(),
+            };
 
-            for gh_release in releases.into_iter() {
-                print!("{gh_release}");
-                if gh_release == latest_release {
+            if ctx.output.is_json() {
+                return print_json(&listing);
+            }
+            for release in listing.releases {
+                print!("{}", release.version);
+                if release.latest {
                     println!(" <- latest");
                 } else {
                     println!();
@@ -49,44 +104,49 @@ impl CommandExecutor for ListArgs {
             };
             let versions_dir = target_dir.join("versions");
 
-            let current_version =
-                if let Ok(link_target) = fs::read_link(target_dir.join("bin")).await {
-                    let bin_path = target_dir.join("bin");
-                    let resolved = if link_target.is_absolute() {
-                        link_target
-                    } else {
-                        bin_path.parent().unwrap_or(&target_dir).join(link_target)
-                    };
-                    resolved
-                        .strip_prefix(&versions_dir)
-                        .ok()
-                        .and_then(|p| p.components().next())
-                        .map(|c| c.as_os_str().to_string_lossy().to_string())
-                } else {
-                    None
-                };
+            let current_version = crate::fs::current_version(&target_dir).await;
 
+            let mut versions = Vec::new();
             if let Ok(mut entries) = fs::read_dir(&versions_dir).await {
-                let mut versions = Vec::new();
                 while let Ok(Some(entry)) = entries.next_entry().await {
                     if let Ok(file_type) = entry.file_type().await {
                         if file_type.is_dir() {
-                            if let Some(version) = entry.file_name().to_str() {
-                                versions.push(version.to_string());
+                            match entry.file_name().to_str() {
+                                Some(name) if is_staging_name(name) => {}
+                                Some(version) => versions.push(version.to_string()),
+                                None => {}
                             }
                         }
                     }
                 }
+            }
+            versions.sort_by(|a, b| b.cmp(a));
 
-                versions.sort_by(|a, b| b.cmp(a));
+            let listing = InstalledListing {
+                versions: versions
+                    .into_iter()
+                    .map(|version| InstalledVersion {
+                        current: Some(&version) == current_version.as_ref(),
+                        version,
+                    })
+                    .collect(),
+                install_root: target_dir,
+                current: current_version,
+                incomplete: stale_dirs(&versions_dir),
+            };
+            for dir in &listing.incomplete {
+                tracing::warn!(dir = %dir.display(), "Found an interrupted install; it is ignored and can be removed");
+            }
 
-                for version in versions {
-                    print!("{version}");
-                    if Some(version.clone()) == current_version {
-                        println!(" <- current");
-                    } else {
-                        println!();
-                    }
+            if ctx.output.is_json() {
+                return print_json(&listing);
+            }
+            for installed in listing.versions {
+                print!("{}", installed.version);
+                if installed.current {
+                    println!(" <- current");
+                } else {
+                    println!();
                 }
             }
         }
@@ -1,13 +1,31 @@
 use crate::prelude::*;
 use std::path::{Path, PathBuf};
 
+pub mod bundle;
+pub mod cache;
+pub mod config;
+pub mod doctor;
 pub mod install;
 pub mod list;
 pub mod plugin;
 pub mod remove;
+pub mod run;
+pub mod self_cmd;
+pub mod shims;
+pub mod update;
 pub mod use_cmd;
 
+/// Install root used when `--path` is not given: the `path` config
+/// setting, else `$HOME/.wasmedge`.
 fn default_path() -> Result<PathBuf> {
+    // An invalid config file was already reported when the command
+    // context was built.
+    if let Some(path) = crate::config::Config::load_default()
+        .ok()
+        .and_then(|c| c.path)
+    {
+        return Ok(path);
+    }
     let home_dir = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
     Ok(home_dir.join(".wasmedge"))
 }
@@ -1,16 +1,25 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use clap::{value_parser, Args};
-use tokio::fs;
+use indicatif::MultiProgress;
+use tempfile::TempDir;
+use tokio::{fs, task::JoinSet};
 use walkdir::WalkDir;
 
-use crate::api::{plugin_archive_name, plugin_asset_url, WasmEdgeApiClient};
+use crate::api::{plugin_archive_name, plugin_asset_url, select_installed_version};
+use crate::bundle::LocalArchive;
+use crate::constants::DEFAULT_PLUGIN_DOWNLOAD_JOBS;
 use crate::system::plugins::plugin_platform_key;
+use crate::toolchain_file::ToolchainFile;
 use crate::{
     cli::{CommandContext, CommandExecutor},
     commands::default_path,
     error::{Error, Result},
-    fs as wfs, system,
+    fs as wfs,
+    lock::InstallLock,
+    manifest::{InstallManifest, PluginRecord},
+    system,
 };
 
 use super::utils::find_plugin_shared_objects;
@@ -19,14 +28,18 @@ use super::version::PluginVersion;
 #[derive(Debug, Args)]
 pub struct PluginInstallArgs {
     /// Space-separated names and versions of plugins to install, e.g. `plugin1 plugin2@version`
+    ///
+    /// Defaults to the plugins listed in the nearest `wasmedge-toolchain.toml`, else `default_plugins` from the config file.
     #[arg(value_parser = value_parser!(PluginVersion))]
     pub plugins: Vec<PluginVersion>,
 
-    /// Optional temporary directory for staging downloads
+    /// Optional temporary directory for staging downloads (defaults to `tmpdir` from the config file)
     #[arg(short, long)]
     pub tmpdir: Option<PathBuf>,
 
-    /// Install plugins into this runtime version (defaults to latest installed)
+    /// Install plugins into this runtime version, e.g. `0.14.1` or `~0.14`
+    /// (defaults to the version pinned by `wasmedge-toolchain.toml`, then the
+    /// latest installed)
     #[arg(long, value_name = "RUNTIME_VERSION")]
     pub runtime: Option<String>,
 
@@ -37,9 +50,29 @@ pub struct PluginInstallArgs {
     /// Skip checksum retrieval and verification for the downloaded plugin archive.
     ///
     /// This option disables integrity verification against the release-level
-    /// SHA256SUM file.
-    #[arg(long)]
+    /// SHA256SUM file. Implied by `verify = false` in the config file.
+    #[arg(long, overrides_with = "verify")]
     pub no_verify: bool,
+
+    /// Verify checksums even if `verify = false` is set in the config file
+    #[arg(long, overrides_with = "no_verify")]
+    pub verify: bool,
+
+    /// Install a single plugin from this local archive instead of downloading it
+    ///
+    /// The archive is verified against the `SHA256SUM` file in its directory.
+    #[arg(long, value_name = "ARCHIVE", conflicts_with = "from_dir")]
+    pub from_file: Option<PathBuf>,
+
+    /// Install plugins from a local bundle directory instead of downloading
+    ///
+    /// Archives are looked up in `<dir>/<version>/` or `<dir>/` and verified against the `SHA256SUM` next to them.
+    #[arg(long, value_name = "DIR")]
+    pub from_dir: Option<PathBuf>,
+
+    /// Download at most this many plugins at a time (defaults to 4)
+    #[arg(short, long)]
+    pub jobs: Option<usize>,
 }
 
 impl PluginInstallArgs {
@@ -57,32 +90,119 @@ impl PluginInstallArgs {
     fn staging_parent(&self) -> PathBuf {
         self.tmpdir.clone().unwrap_or_else(std::env::temp_dir)
     }
+
+    /// Obtain the verified archive for plugin `name` at version `pver`: from
+    /// `--from-file`/`--from-dir` when given, otherwise downloaded into
+    /// `workspace_dir`. Returns the archive along with its URL or local path.
+    async fn fetch_archive(
+        &self,
+        ctx: &CommandContext,
+        name: &str,
+        pver: &str,
+        os_key: &str,
+        is_zip: bool,
+        workspace_dir: &Path,
+    ) -> Result<(std::fs::File, String)> {
+        let archive_name = plugin_archive_name(name, pver, os_key, is_zip);
+        let local = match (&self.from_file, &self.from_dir) {
+            (Some(archive), _) => Some(LocalArchive::from_file(archive, pver)?),
+            (None, Some(bundle)) => Some(LocalArchive::find(bundle, pver, &archive_name)?),
+            (None, None) => None,
+        };
+        if let Some(local) = local {
+            tracing::debug!(plugin = %name, archive = %local.path.display(), "Installing plugin from local archive");
+            let file = local
+                .open_verified(self.no_verify, &ctx.client.signatures)
+                .await?;
+            return Ok((file, local.path.display().to_string()));
+        }
+
+        let url = plugin_asset_url(&ctx.client.endpoints, name, pver, os_key, is_zip)?;
+        tracing::debug!(%name, %pver, %url, "Downloading plugin");
+
+        let archive_path = if is_zip {
+            workspace_dir.join("plugin.zip")
+        } else {
+            workspace_dir.join("plugin.tar.gz")
+        };
+
+        let expected = if self.no_verify {
+            tracing::warn!(plugin = %name, "Skipping plugin checksum verification due to --no-verify flag");
+            None
+        } else {
+            let expected = ctx
+                .client
+                .get_archive_checksum(pver, &archive_name)
+                .await
+                .inspect_err(|e| tracing::error!(error = %e, "Failed to get plugin checksum"))?;
+            tracing::debug!(plugin = %name, checksum = %expected, "Got plugin checksum");
+            Some(expected)
+        };
+
+        let source = url.to_string();
+        ctx.client
+            .download_to_path(
+                url,
+                &archive_path,
+                ctx.no_progress,
+                "plugin download",
+                expected.as_deref(),
+            )
+            .await?;
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .open(&archive_path)
+            .map_err(|source| Error::Io {
+                action: "open archive".to_string(),
+                path: archive_path.display().to_string(),
+                source,
+            })?;
+        Ok((file, source))
+    }
 }
 
 impl CommandExecutor for PluginInstallArgs {
     /// Executes the plugin installation process by resolving the target runtime version,
-    /// detecting the platform key, downloading the plugin asset, unpacking it, discovering
+    /// detecting the platform key, downloading the plugin assets, unpacking them, discovering
     /// the plugin shared objects, and copying them into the versioned plugin directory.
     ///
     /// # Steps
     /// 1. Resolve the target runtime version (either a specific version or the latest installed one).
     /// 2. Detect the host system specs and compute the plugin platform key (version-aware for Linux manylinux baseline and Darwin major on macOS).
-    /// 3. For each requested plugin, construct the release asset URL and download it to a temporary workspace.
-    /// 4. Unpack the archive into the workspace.
-    /// 5. Discover plugin artifacts and copy them into `versions/<version>/plugin`.
-    /// 6. If no plugin shared objects are found, emit a warning and include a listing of archive contents to aid debugging.
+    /// 3. Download and unpack up to `--jobs` plugin assets at a time, each into its own temporary workspace.
+    /// 4. As each download finishes, discover its plugin artifacts and copy them into `versions/<version>/plugin`.
+    /// 5. If no plugin shared objects are found, record the plugin as failed and log a listing of archive contents to aid debugging.
+    /// 6. Report the outcome of every plugin once all of them were attempted.
     ///
     /// # Arguments
     /// * `ctx` - The command context containing the HTTP client and progress/settings.
     ///
     /// # Errors
-    /// Returns an error if any step fails, such as permissions issues on the version directory,
-    /// unsupported platform determination, download failures, extraction errors, or invalid inputs
+    /// Returns an error if the target version directory is missing or not writable, or once
+    /// every plugin was attempted if any of them failed to download, extract or install.
     #[tracing::instrument(name = "plugin.install", skip_all, fields(plugins = ?self.plugins))]
-    async fn execute(self, ctx: CommandContext) -> Result<()> {
+    async fn execute(mut self, ctx: CommandContext) -> Result<()> {
+        if self.plugins.is_empty() {
+            self.plugins = toolchain_plugins()?;
+        }
+        if self.plugins.is_empty() {
+            tracing::debug!(plugins = ?ctx.config.default_plugins, "Using default plugins from config file");
+            self.plugins = parse_plugins(&ctx.config.default_plugins)?;
+        }
         if self.plugins.is_empty() {
             return Err(Error::NoPluginsSpecified);
         }
+        if self.tmpdir.is_none() {
+            self.tmpdir = ctx.config.tmpdir.clone();
+        }
+        self.no_verify = ctx.config.skip_verification(self.verify, self.no_verify);
+        if let (Some(archive), true) = (&self.from_file, self.plugins.len() > 1) {
+            return Err(Error::InvalidPath {
+                path: archive.display().to_string(),
+                reason: "--from-file installs exactly one plugin".to_string(),
+            });
+        }
 
         let versions_dir = match self.path.clone() {
             Some(p) => p,
@@ -105,6 +225,12 @@ impl CommandExecutor for PluginInstallArgs {
             ));
         }
 
+        let root = versions_dir.parent().unwrap_or(&versions_dir);
+        let _lock = InstallLock::acquire(root, ctx.lock_wait).await?;
+
+        let mut manifest = InstallManifest::load(&version_dir)
+            .unwrap_or_else(|| InstallManifest::new(&runtime_version.to_string()));
+
         let specs = system::detect();
         let dest_plugin = version_dir.join("plugin");
         fs::create_dir_all(&dest_plugin).await?;
@@ -121,118 +247,274 @@ impl CommandExecutor for PluginInstallArgs {
         // end of this method or on any early return.
         let staging_parent = self.staging_parent();
         let plugins_root = wfs::create_temp_workspace(&staging_parent, "wasmedgeup-plugins")?;
-        for plugin in &self.plugins {
-            // Keep the plugin's own version typed: when the user passes
-            // `plugin@version`, the version may differ from `runtime_version`
-            // and `plugin_platform_key` is version-aware (manylinux2014 vs
-            // manylinux_2_28 boundary at 0.15). Computing os_key against the
-            // runtime once would build wrong URLs for `plugin@<older>`
-            // installs.
-            let (name, pver_semver) = match plugin {
-                PluginVersion::Name(n) => (n.as_str(), runtime_version.clone()),
-                PluginVersion::NameAndVersion(n, v) => (n.as_str(), v.clone()),
+
+        let mut ctx = ctx;
+        if self.plugins.len() > 1 && !ctx.no_progress {
+            ctx.client = ctx.client.clone().with_progress(MultiProgress::new());
+        }
+        let jobs = self.jobs.unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_JOBS).max(1);
+        let this = Arc::new(self);
+
+        // Keep the plugin's own version typed: when the user passes
+        // `plugin@version`, the version may differ from `runtime_version`
+        // and `plugin_platform_key` is version-aware (manylinux2014 vs
+        // manylinux_2_28 boundary at 0.15). Computing os_key against the
+        // runtime once would build wrong URLs for `plugin@<older>`
+        // installs.
+        let requests: Vec<(String, semver::Version)> = this
+            .plugins
+            .iter()
+            .map(|plugin| match plugin {
+                PluginVersion::Name(n) => (n.clone(), runtime_version.clone()),
+                PluginVersion::NameAndVersion(n, v) => (n.clone(), v.clone()),
+            })
+            .collect();
+
+        let mut outcomes: Vec<Option<Result<()>>> = requests.iter().map(|_| None).collect();
+        let mut pending = requests.iter().enumerate();
+        let mut tasks = JoinSet::new();
+        loop {
+            while tasks.len() < jobs {
+                let Some((i, (name, pver))) = pending.next() else {
+                    break;
+                };
+                let os_key = match plugin_platform_key(&specs.os, pver) {
+                    Ok(key) => key,
+                    Err(e) => {
+                        outcomes[i] = Some(Err(e));
+                        continue;
+                    }
+                };
+                tracing::debug!(%name, %pver, platform_key = %os_key, "Resolved plugin asset platform key");
+                let this = Arc::clone(&this);
+                let ctx = ctx.clone();
+                let root = plugins_root.path().to_path_buf();
+                let (name, pver) = (name.clone(), pver.to_string());
+                tasks.spawn(async move {
+                    let staged = this
+                        .stage_plugin(&ctx, &name, &pver, &os_key, is_zip, &root)
+                        .await;
+                    (i, staged)
+                });
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
             };
-            let pver = pver_semver.to_string();
-            let os_key = plugin_platform_key(&specs.os, &pver_semver)?;
-            tracing::debug!(%name, %pver, platform_key = %os_key, "Resolved plugin asset platform key");
-
-            let url = plugin_asset_url(name, &pver, &os_key, is_zip)?;
-            tracing::debug!(%name, %pver, %url, "Downloading plugin");
-
-            let workspace =
-                wfs::create_temp_workspace(plugins_root.path(), &format!("{name}-{pver}"))?;
-            let workspace_dir = workspace.path();
-            let archive_path = if is_zip {
-                workspace_dir.join("plugin.zip")
-            } else {
-                workspace_dir.join("plugin.tar.gz")
+            let (i, staged) = joined.map_err(|e| Error::Io {
+                action: "plugin download task".to_string(),
+                path: plugins_root.path().display().to_string(),
+                source: crate::error::join_err_to_io_error(e),
+            })?;
+            let (name, pver) = &requests[i];
+            let outcome = match staged {
+                Ok(staged) => {
+                    let (source, sha256, platform) = (
+                        staged.source.clone(),
+                        staged.sha256.clone(),
+                        staged.platform.clone(),
+                    );
+                    match copy_staged_plugin(staged, name, &pver.to_string(), &dest_plugin).await {
+                        Ok(files) => {
+                            manifest.add_plugin(PluginRecord {
+                                name: name.clone(),
+                                version: pver.to_string(),
+                                platform,
+                                source,
+                                sha256,
+                                files,
+                            });
+                            manifest.save(&version_dir)
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                Err(e) => Err(e),
             };
-
-            ctx.client
-                .download_to_path(url, &archive_path, ctx.no_progress, "plugin download")
-                .await?;
-
-            let mut file = std::fs::OpenOptions::new()
-                .read(true)
-                .open(&archive_path)
-                .map_err(|source| Error::Io {
-                    action: "open archive".to_string(),
-                    path: archive_path.display().to_string(),
-                    source,
-                })?;
-
-            if self.no_verify {
-                tracing::warn!(plugin = %name, "Skipping plugin checksum verification due to --no-verify flag");
-            } else {
-                let archive_name = plugin_archive_name(name, &pver, &os_key, is_zip);
-                let expected = ctx
-                    .client
-                    .get_archive_checksum(&pver, &archive_name)
-                    .await
-                    .inspect_err(
-                        |e| tracing::error!(error = %e, "Failed to get plugin checksum"),
-                    )?;
-                tracing::debug!(plugin = %name, checksum = %expected, "Got plugin checksum");
-                WasmEdgeApiClient::verify_file_checksum(&mut file, &expected).await?;
-                tracing::debug!(plugin = %name, "Plugin checksum verified");
+            if let Err(e) = &outcome {
+                tracing::error!(error = %e, plugin = %name, version = %pver, "Failed to install plugin");
             }
+            outcomes[i] = Some(outcome);
+        }
 
-            wfs::extract_archive(file, workspace_dir).await?;
-
-            let paths = find_plugin_shared_objects(workspace_dir);
-            let copied = copy_plugin_shared_objects(&paths, &dest_plugin).await;
-
-            if copied == 0 {
-                // Nothing landed in `dest_plugin` — either the archive held no
-                // usable shared object or every copy failed. List the archive
-                // contents to aid diagnosis, then fail instead of reporting a
-                // bogus success. `workspace` (a `TempDir`) is dropped on return,
-                // cleaning up the staging directory.
-                let mut entries: Vec<String> = Vec::new();
-                for e in WalkDir::new(workspace_dir)
-                    .into_iter()
-                    .filter_map(|e| e.ok())
-                {
-                    let p = e.path();
-                    if p.is_file() {
-                        let rel = p.strip_prefix(workspace_dir).unwrap_or(p);
-                        entries.push(rel.display().to_string());
+        let mut failed = Vec::new();
+        for ((name, pver), outcome) in requests.iter().zip(outcomes) {
+            match outcome {
+                Some(Ok(())) => {
+                    if !ctx.output.is_json() {
+                        println!("Installed plugin {name} {pver}");
                     }
                 }
-                tracing::error!(
-                    plugin = %name,
-                    root = %workspace_dir.display(),
-                    entries = ?entries,
-                    "No plugin shared object was installed; archive contents listed for diagnosis"
-                );
-                return Err(Error::PluginNotInstalled {
-                    plugin: name.to_string(),
-                    version: pver.clone(),
-                });
+                Some(Err(e)) => {
+                    eprintln!("Failed to install plugin {name} {pver}: {e}");
+                    failed.push((format!("{name}@{pver}"), e));
+                }
+                None => unreachable!("every plugin is attempted"),
             }
+        }
 
-            // The shared objects are already copied into `dest_plugin`, so a
-            // cleanup failure must not abort the remaining plugins. Mirror
-            // install.rs and surface it via `close()` rather than letting
-            // `TempDir`'s Drop swallow the error silently.
-            let workspace_path = workspace_dir.to_path_buf();
-            if let Err(e) = workspace.close() {
-                tracing::warn!(error = %e, plugin = %name, path = %workspace_path.display(), "Failed to clean up plugin workspace; continuing");
-            }
+        match failed.len() {
+            0 => Ok(()),
+            1 => Err(failed.remove(0).1),
+            _ => Err(Error::PluginsNotInstalled {
+                plugins: failed
+                    .into_iter()
+                    .map(|(plugin, _)| plugin)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }),
+        }
+    }
+}
+
+impl PluginInstallArgs {
+    /// Fetch plugin `name` at version `pver` into a fresh workspace under
+    /// `root` and unpack it there.
+    async fn stage_plugin(
+        &self,
+        ctx: &CommandContext,
+        name: &str,
+        pver: &str,
+        os_key: &str,
+        is_zip: bool,
+        root: &Path,
+    ) -> Result<StagedPlugin> {
+        let workspace = wfs::create_temp_workspace(root, &format!("{name}-{pver}"))?;
+        let workspace_dir = workspace.path();
+        let (mut file, source) = self
+            .fetch_archive(ctx, name, pver, os_key, is_zip, workspace_dir)
+            .await?;
+        let sha256 = wfs::file_sha256(&mut file)?;
+        wfs::extract_archive(file, workspace_dir).await?;
+        let paths = find_plugin_shared_objects(workspace_dir);
+        Ok(StagedPlugin {
+            workspace,
+            paths,
+            source,
+            sha256,
+            platform: os_key.to_string(),
+        })
+    }
+}
 
-            tracing::info!(plugin = %name, version = %pver, "Installed plugin successfully");
+/// A downloaded and unpacked plugin archive waiting to be copied.
+struct StagedPlugin {
+    workspace: TempDir,
+    /// Plugin shared objects found in the archive.
+    paths: Vec<PathBuf>,
+    /// Where the archive came from: its URL or local path.
+    source: String,
+    sha256: String,
+    platform: String,
+}
+
+/// Copy the shared objects of a staged plugin into `dest_plugin`, failing
+/// when none of them landed there. Returns the copied files relative to the
+/// version directory.
+async fn copy_staged_plugin(
+    staged: StagedPlugin,
+    name: &str,
+    pver: &str,
+    dest_plugin: &Path,
+) -> Result<Vec<PathBuf>> {
+    let StagedPlugin {
+        workspace, paths, ..
+    } = staged;
+    let workspace_dir = workspace.path();
+    let copied = copy_plugin_shared_objects(&paths, dest_plugin).await;
+
+    if copied.is_empty() {
+        // Nothing landed in `dest_plugin` — either the archive held no
+        // usable shared object or every copy failed. List the archive
+        // contents to aid diagnosis, then fail instead of reporting a
+        // bogus success. `workspace` (a `TempDir`) is dropped on return,
+        // cleaning up the staging directory.
+        let mut entries: Vec<String> = Vec::new();
+        for e in WalkDir::new(workspace_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let p = e.path();
+            if p.is_file() {
+                let rel = p.strip_prefix(workspace_dir).unwrap_or(p);
+                entries.push(rel.display().to_string());
+            }
         }
+        tracing::error!(
+            plugin = %name,
+            root = %workspace_dir.display(),
+            entries = ?entries,
+            "No plugin shared object was installed; archive contents listed for diagnosis"
+        );
+        return Err(Error::PluginNotInstalled {
+            plugin: name.to_string(),
+            version: pver.to_string(),
+        });
+    }
 
-        Ok(())
+    // The shared objects are already copied into `dest_plugin`, so a
+    // cleanup failure must not fail the plugin. Mirror install.rs and
+    // surface it via `close()` rather than letting `TempDir`'s Drop
+    // swallow the error silently.
+    let workspace_path = workspace_dir.to_path_buf();
+    if let Err(e) = workspace.close() {
+        tracing::warn!(error = %e, plugin = %name, path = %workspace_path.display(), "Failed to clean up plugin workspace; continuing");
     }
+
+    tracing::info!(plugin = %name, version = %pver, "Installed plugin successfully");
+    Ok(copied
+        .into_iter()
+        .map(|file| Path::new("plugin").join(file))
+        .collect())
 }
 
+/// Plugins required by the nearest `wasmedge-toolchain.toml`, parsed as
+/// `name` / `name@version` just like command-line arguments.
+fn toolchain_plugins() -> Result<Vec<PluginVersion>> {
+    let Some(file) = ToolchainFile::discover()? else {
+        return Ok(Vec::new());
+    };
+    if !file.plugins.is_empty() {
+        tracing::debug!(path = %file.path.display(), plugins = ?file.plugins, "Using plugins listed in toolchain file");
+    }
+    parse_toolchain_plugins(&file)
+}
+
+/// Parse the plugins listed in `file`, naming the file and the entry when
+/// one is malformed.
+fn parse_toolchain_plugins(file: &ToolchainFile) -> Result<Vec<PluginVersion>> {
+    file.plugins
+        .iter()
+        .map(|p| {
+            p.parse().map_err(|source| Error::ToolchainPlugin {
+                path: file.path.display().to_string(),
+                plugin: p.clone(),
+                source,
+            })
+        })
+        .collect()
+}
+
+fn parse_plugins(plugins: &[String]) -> Result<Vec<PluginVersion>> {
+    plugins
+        .iter()
+        .map(|p| p.parse().map_err(|source| Error::SemVer { source }))
+        .collect()
+}
+
+/// Pick the runtime version plugin commands operate on: an explicit
+/// `--runtime` wins, then the version pinned by `wasmedge-toolchain.toml`,
+/// then the highest installed version. A given version may be a channel or
+/// requirement (e.g. `~0.14`) and picks the newest installed match.
 pub(super) fn select_runtime_version(
     versions_dir: &Path,
     requested: Option<&str>,
 ) -> Result<semver::Version> {
     if let Some(ver) = requested {
-        return semver::Version::parse(ver).map_err(|source| Error::SemVer { source });
+        return select_installed_version(versions_dir, ver);
+    }
+    if let Some((ver, path)) = ToolchainFile::discover_version()? {
+        tracing::debug!(version = %ver, path = %path.display(), "Using runtime version pinned by toolchain file");
+        return select_installed_version(versions_dir, &ver);
     }
     match crate::api::latest_installed_version(versions_dir)? {
         Some(v) => Ok(v),
@@ -243,12 +525,12 @@ pub(super) fn select_runtime_version(
 }
 
 /// Copy each discovered plugin shared object in `paths` into `dest_plugin`,
-/// returning how many were copied successfully. Per-object failures are logged
-/// and counted as failures (not aborts) so one unreadable file does not lose
-/// the rest; the caller treats a zero return as "nothing was installed" rather
+/// returning the names of those copied successfully. Per-object failures are
+/// logged and skipped (not aborts) so one unreadable file does not lose the
+/// rest; the caller treats an empty return as "nothing was installed" rather
 /// than reporting a false success.
-async fn copy_plugin_shared_objects(paths: &[PathBuf], dest_plugin: &Path) -> usize {
-    let mut copied = 0usize;
+async fn copy_plugin_shared_objects(paths: &[PathBuf], dest_plugin: &Path) -> Vec<PathBuf> {
+    let mut copied = Vec::new();
     for src in paths {
         let file_name = src.file_name().unwrap_or_default();
         let dest = dest_plugin.join(file_name);
@@ -260,7 +542,7 @@ async fn copy_plugin_shared_objects(paths: &[PathBuf], dest_plugin: &Path) -> us
         }
         match fs::copy(src, &dest).await {
             Ok(_) => {
-                copied += 1;
+                copied.push(PathBuf::from(file_name));
                 tracing::debug!(from = %src.display(), to = %dest.display(), "Copied plugin shared object");
             }
             Err(e) => {
@@ -287,6 +569,10 @@ mod tests {
             runtime: None,
             path: None,
             no_verify: false,
+            verify: false,
+            from_file: None,
+            from_dir: None,
+            jobs: None,
         };
         assert_eq!(default.staging_parent(), std::env::temp_dir());
 
@@ -298,10 +584,32 @@ mod tests {
             runtime: None,
             path: None,
             no_verify: false,
+            verify: false,
+            from_file: None,
+            from_dir: None,
+            jobs: None,
         };
         assert_eq!(overridden.staging_parent(), custom);
     }
 
+    #[test]
+    fn bad_toolchain_plugin_names_the_file_and_entry() {
+        let file = ToolchainFile {
+            path: PathBuf::from("/project/wasmedge-toolchain.toml"),
+            version: None,
+            plugins: vec!["wasi_logging".to_string(), "wasi_nn@0.x".to_string()],
+        };
+        let err = parse_toolchain_plugins(&file).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                Error::ToolchainPlugin { path, plugin, .. }
+                    if path == "/project/wasmedge-toolchain.toml" && plugin == "wasi_nn@0.x"
+            ),
+            "got {err:?}"
+        );
+    }
+
     #[tokio::test]
     async fn copy_plugin_objects_counts_only_successful_copies() {
         let src_dir = tempfile::tempdir().unwrap();
@@ -313,7 +621,7 @@ mod tests {
 
         let copied = copy_plugin_shared_objects(&[a, b], dest.path()).await;
 
-        assert_eq!(copied, 2);
+        assert_eq!(copied, [PathBuf::from("liba.so"), PathBuf::from("libb.so")]);
         assert!(dest.path().join("liba.so").exists());
         assert!(dest.path().join("libb.so").exists());
     }
@@ -328,7 +636,7 @@ mod tests {
 
         let copied = copy_plugin_shared_objects(std::slice::from_ref(&missing), dest.path()).await;
 
-        assert_eq!(copied, 0);
+        assert!(copied.is_empty());
     }
 
     #[tokio::test]
@@ -341,6 +649,6 @@ mod tests {
 
         let copied = copy_plugin_shared_objects(&[good, missing], dest.path()).await;
 
-        assert_eq!(copied, 1);
+        assert_eq!(copied, [PathBuf::from("libgood.so")]);
     }
 }
@@ -1,12 +1,20 @@
+use super::utils::{extract_plugin_name, normalize_name};
 use crate::api::{plugin_asset_url, runtime_ge_015, PluginAssetInfo, WasmEdgeApiClient};
 use crate::cli::{CommandContext, CommandExecutor};
+use crate::commands::{cache::human_size, default_path};
+use crate::manifest::InstallManifest;
+use crate::output::print_json;
 use crate::prelude::*;
 use crate::system;
 use crate::system::plugins::plugin_platform_key;
 use crate::system::spec::{CpuClass, CpuFeature, SystemSpec};
+use crate::toolchain_file::ToolchainFile;
+use crate::transaction::is_staging_name;
 use clap::Args;
+use serde::Serialize;
 use std::cmp::Ordering;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 const UBUNTU20_PREFIX: &str = "ubuntu20_04_";
 const UBUNTU22_PREFIX: &str = "ubuntu22_04_";
@@ -20,18 +28,34 @@ pub struct PluginListArgs {
     all: bool,
 
     /// Override the WasmEdge runtime version to check (e.g., 0.15.0)
+    ///
+    /// Defaults to the version pinned by `wasmedge-toolchain.toml`, then
+    /// `wasmedge --version` on PATH.
     #[arg(long)]
     runtime: Option<String>,
 
     /// Filter by a single plugin name
     #[arg(long)]
     name: Option<String>,
+
+    /// Show the plugins installed in each runtime version instead of those available
+    ///
+    /// With `--runtime`, only that version is shown.
+    #[arg(long, conflicts_with = "all")]
+    installed: bool,
+
+    /// Install location to look in with `--installed`
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long, requires = "installed")]
+    path: Option<PathBuf>,
 }
 
 impl PluginListArgs {
-    /// Pick the runtime tag: explicit `--runtime` wins; otherwise we ask
-    /// `wasmedge --version` on PATH. Returns `RuntimeNotFound` if neither
-    /// source yields a value so the caller can surface an install hint.
+    /// Pick the runtime tag: explicit `--runtime` wins, then the version
+    /// pinned by `wasmedge-toolchain.toml`; otherwise we ask
+    /// `wasmedge --version` on PATH. Returns `RuntimeNotFound` if no source
+    /// yields a value so the caller can surface an install hint.
     ///
     /// Takes `runtime_arg` by value to preserve the short-circuit
     /// behaviour PR #265 fixed: passing `--runtime` must not pay for a
@@ -40,6 +64,12 @@ impl PluginListArgs {
         if let Some(r) = runtime_arg {
             return Ok(r);
         }
+        if let Some((v, path)) = ToolchainFile::discover_version()? {
+            if v != "latest" {
+                tracing::debug!(version = %v, path = %path.display(), "Using runtime version pinned by toolchain file");
+                return Ok(v);
+            }
+        }
         match system::toolchain::get_installed_wasmedge_version() {
             Some(v) => Ok(v),
             None => {
@@ -55,12 +85,16 @@ impl PluginListArgs {
 
 impl CommandExecutor for PluginListArgs {
     async fn execute(self, ctx: CommandContext) -> Result<()> {
+        if self.installed {
+            return self.list_installed(&ctx).await;
+        }
+
         let spec = system::detect();
         let runtime = Self::resolve_runtime_tag(self.runtime)?;
         let platform = resolve_platform_key(&runtime, &spec)?;
 
         let hints = PluginHints::from_spec(&spec);
-        let assets = fetch_release_assets_or_warn(&ctx.client, &runtime).await;
+        let assets = fetch_release_assets_or_warn(&ctx.client, &runtime).await?;
         let candidates = collect_plugin_candidates(&assets, &runtime, &hints, self.name.as_deref());
         let platform_candidates = platform_fallbacks(&platform, &runtime);
 
@@ -87,16 +121,217 @@ impl CommandExecutor for PluginListArgs {
             other => other,
         });
 
+        if ctx.output.is_json() {
+            return print_json(&PluginListing {
+                runtime: &runtime,
+                platform: &platform,
+                plugins: &rows,
+            });
+        }
         print_plugin_table(&rows, &runtime, &platform, self.all);
         Ok(())
     }
 }
 
-#[derive(Debug)]
+impl PluginListArgs {
+    /// List the plugin shared objects in `versions/*/plugin` and the stable
+    /// `plugin` link of the install root. Never touches the network.
+    async fn list_installed(self, ctx: &CommandContext) -> Result<()> {
+        let install_root = match self.path {
+            Some(p) => p,
+            None => default_path()?,
+        };
+        let current = crate::fs::current_version(&install_root).await;
+        let plugins = installed_plugins(
+            &install_root,
+            current.as_deref(),
+            self.runtime.as_deref(),
+            self.name.as_deref(),
+        );
+
+        let listing = InstalledPluginListing {
+            install_root,
+            current,
+            plugins,
+        };
+        if ctx.output.is_json() {
+            return print_json(&listing);
+        }
+        print_installed_table(&listing);
+        Ok(())
+    }
+}
+
+/// A plugin shared object found in an installed runtime version.
+#[derive(Debug, Serialize)]
+struct InstalledPlugin {
+    name: String,
+    runtime: String,
+    /// Whether `runtime` is the current version, i.e. the plugin is loaded
+    /// through the stable `plugin` link.
+    active: bool,
+    path: PathBuf,
+    size: u64,
+    /// Plugin version, when the install manifest records it.
+    version: Option<String>,
+    /// SHA256 of the plugin archive, when the install manifest records it.
+    sha256: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct InstalledPluginListing {
+    install_root: PathBuf,
+    current: Option<String>,
+    plugins: Vec<InstalledPlugin>,
+}
+
+/// The plugins installed under `install_root`, newest runtime first, limited
+/// to `runtime` and to plugin `name` when given. A file reached through the
+/// stable `plugin` link is listed once, under the version it lives in.
+fn installed_plugins(
+    install_root: &Path,
+    current: Option<&str>,
+    runtime: Option<&str>,
+    name: Option<&str>,
+) -> Vec<InstalledPlugin> {
+    let versions_dir = install_root.join("versions");
+    let mut versions: Vec<String> = std::fs::read_dir(&versions_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .filter(|v| !is_staging_name(v))
+        .collect();
+    versions.sort_by(|a, b| version_desc(a, b));
+
+    // (runtime, version directory, plugin directory)
+    let mut dirs: Vec<(String, PathBuf, PathBuf)> = versions
+        .into_iter()
+        .map(|v| {
+            let version_dir = versions_dir.join(&v);
+            let plugin_dir = version_dir.join("plugin");
+            (v, version_dir, plugin_dir)
+        })
+        .collect();
+    if let Some(current) = current {
+        dirs.push((
+            current.to_string(),
+            versions_dir.join(current),
+            install_root.join("plugin"),
+        ));
+    }
+
+    let wanted = name.map(normalize_name);
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut plugins = Vec::new();
+    for (version, version_dir, plugin_dir) in dirs {
+        if runtime.is_some_and(|r| r != version) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&plugin_dir) else {
+            continue;
+        };
+        let manifest = InstallManifest::load(&version_dir);
+        let mut files: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        files.sort();
+        for path in files {
+            // Follows links, so a plugin symlinked into place is sized by
+            // its target.
+            let Ok(meta) = std::fs::metadata(&path) else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            let Some(plugin) = extract_plugin_name(&path) else {
+                continue;
+            };
+            if !seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+                continue;
+            }
+            let relative = Path::new("plugin").join(path.file_name().unwrap_or_default());
+            let record = manifest
+                .as_ref()
+                .and_then(|m| m.plugins.iter().find(|p| p.files.contains(&relative)));
+            if let Some(wanted) = &wanted {
+                let matches = |n: &str| normalize_name(n) == *wanted;
+                if !matches(&plugin) && !record.is_some_and(|r| matches(&r.name)) {
+                    continue;
+                }
+            }
+            plugins.push(InstalledPlugin {
+                name: plugin,
+                active: current == Some(version.as_str()),
+                runtime: version.clone(),
+                path,
+                size: meta.len(),
+                version: record.map(|r| r.version.clone()),
+                sha256: record.map(|r| r.sha256.clone()),
+            });
+        }
+    }
+    plugins
+}
+
+fn print_installed_table(listing: &InstalledPluginListing) {
+    const RUNTIME_W: usize = 12;
+    const NAME_W: usize = 28;
+    const VER_W: usize = 12;
+    const SIZE_W: usize = 10;
+
+    println!("Install root: {}", listing.install_root.display());
+    if listing.plugins.is_empty() {
+        println!("\nNo plugins installed.");
+        return;
+    }
+
+    println!(
+        "\n{:<RUNTIME_W$} {:<NAME_W$} {:<VER_W$} {:>SIZE_W$}  PATH",
+        "RUNTIME", "PLUGIN", "VERSION", "SIZE"
+    );
+    for p in &listing.plugins {
+        print!(
+            "{:<RUNTIME_W$} {:<NAME_W$} {:<VER_W$} {:>SIZE_W$}  {}",
+            p.runtime,
+            p.name,
+            p.version.as_deref().unwrap_or("-"),
+            human_size(p.size),
+            p.path.display()
+        );
+        if p.active {
+            println!();
+        } else {
+            println!(" (inactive)");
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
 struct Row {
     name: String,
     version: String,
-    status: String,
+    available: bool,
+    /// Platform the row was probed for; `None` for rows taken directly from
+    /// the release assets.
+    platform: Option<String>,
+}
+
+impl Row {
+    fn status(&self) -> String {
+        match (&self.platform, self.available) {
+            (None, _) => "available".to_string(),
+            (Some(plat), true) => format!("available ({plat})"),
+            (Some(plat), false) => format!("not found ({plat})"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PluginListing<'a> {
+    runtime: &'a str,
+    platform: &'a str,
+    plugins: &'a [Row],
 }
 
 /// Host hints that bias plugin ordering: the CUDA-first / noavx-preferred
@@ -126,17 +361,20 @@ fn resolve_platform_key(runtime: &str, spec: &SystemSpec) -> Result<String> {
 }
 
 /// Query GitHub's releases API; a failure is logged but not propagated —
-/// the command degrades gracefully to an empty list in that case.
+/// the command degrades gracefully to an empty list in that case. An
+/// exhausted rate limit is the exception: an empty table would read as
+/// "no plugins", so it is reported with its reset time instead.
 async fn fetch_release_assets_or_warn(
     client: &WasmEdgeApiClient,
     runtime: &str,
-) -> Vec<PluginAssetInfo> {
+) -> Result<Vec<PluginAssetInfo>> {
     match client.github_release_assets(runtime).await {
-        Ok(v) => v,
+        Ok(v) => Ok(v),
+        Err(e @ Error::GitHubRateLimited { .. }) => Err(e),
         Err(e) => {
             tracing::warn!(error = %e, tag = %runtime, "failed to fetch plugin release assets");
             eprintln!("failed to fetch release assets for tag {runtime}: {e}");
-            Vec::new()
+            Ok(Vec::new())
         }
     }
 }
@@ -184,7 +422,8 @@ fn build_direct_rows(
         .map(|a| Row {
             name: a.plugin.clone(),
             version: a.version.clone(),
-            status: "available".to_string(),
+            available: true,
+            platform: None,
         })
         .collect()
 }
@@ -203,17 +442,14 @@ async fn build_probe_rows(
     for name in candidates {
         for probe in probes_for(name, hints) {
             for plat in platform_candidates {
-                let url_targz = plugin_asset_url(probe, runtime, plat, false)?;
-                let url_zip = plugin_asset_url(probe, runtime, plat, true)?;
+                let url_targz = plugin_asset_url(&client.endpoints, probe, runtime, plat, false)?;
+                let url_zip = plugin_asset_url(&client.endpoints, probe, runtime, plat, true)?;
                 let available = client.head_ok(url_targz).await || client.head_ok(url_zip).await;
                 rows.push(Row {
                     name: probe.to_string(),
                     version: runtime.to_string(),
-                    status: if available {
-                        format!("available ({plat})")
-                    } else {
-                        format!("not found ({plat})")
-                    },
+                    available,
+                    platform: Some(plat.clone()),
                 });
             }
         }
@@ -264,7 +500,7 @@ fn print_plugin_table(rows: &[Row], runtime: &str, platform: &str, show_missing_
         "-".repeat(STATUS_W),
     );
     for r in rows {
-        println!("{:<NAME_W$} {:<VER_W$} {}", r.name, r.version, r.status,);
+        println!("{:<NAME_W$} {:<VER_W$} {}", r.name, r.version, r.status());
     }
 }
 
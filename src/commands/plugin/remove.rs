@@ -4,12 +4,14 @@ use std::path::PathBuf;
 use clap::Args;
 
 use super::install::select_runtime_version;
-use super::utils::extract_plugin_name;
+use super::utils::{extract_plugin_name, normalize_name};
 use super::version::PluginVersion;
 use crate::commands::default_path;
 use crate::{
     cli::{CommandContext, CommandExecutor},
     error::{Error, Result},
+    lock::InstallLock,
+    manifest::{remove_files, InstallManifest},
 };
 
 #[derive(Debug, Args)]
@@ -18,7 +20,9 @@ pub struct PluginRemoveArgs {
     #[arg(value_parser = clap::value_parser!(PluginVersion))]
     pub plugins: Vec<PluginVersion>,
 
-    /// Remove plugins from this runtime version (defaults to latest installed)
+    /// Remove plugins from this runtime version, e.g. `0.14.1` or `~0.14`
+    /// (defaults to the version pinned by `wasmedge-toolchain.toml`, then the
+    /// latest installed)
     #[arg(long, value_name = "RUNTIME_VERSION")]
     pub runtime: Option<String>,
 
@@ -27,16 +31,9 @@ pub struct PluginRemoveArgs {
     pub path: Option<PathBuf>,
 }
 
-fn normalize_name(s: &str) -> String {
-    s.chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .flat_map(|c| c.to_lowercase())
-        .collect()
-}
-
 impl CommandExecutor for PluginRemoveArgs {
     #[tracing::instrument(name = "plugin.remove", skip_all, fields(plugins = ?self.plugins))]
-    async fn execute(self, _ctx: CommandContext) -> Result<()> {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
         if self.plugins.is_empty() {
             return Err(Error::NoPluginsSpecified);
         }
@@ -56,97 +53,73 @@ impl CommandExecutor for PluginRemoveArgs {
             });
         }
 
+        let root = versions_dir.parent().unwrap_or(&versions_dir);
+        let _lock = InstallLock::acquire(root, ctx.lock_wait).await?;
+
         let plugin_dir = version_dir.join("plugin");
         let stable_plugin_dir = versions_dir
             .parent()
             .unwrap_or(&versions_dir)
             .join("plugin");
 
-        let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
-
-        let mut searched_dirs: Vec<PathBuf> = Vec::new();
-        if plugin_dir.exists() {
-            searched_dirs.push(plugin_dir.clone());
-        }
-        if stable_plugin_dir.exists() {
-            searched_dirs.push(stable_plugin_dir.clone());
-        }
-
-        for dir in &searched_dirs {
-            let mut rd = tokio::fs::read_dir(dir).await?;
-            while let Some(entry) = rd.next_entry().await? {
-                let path = entry.path();
-                if !entry
-                    .file_type()
-                    .await
-                    .map(|t| t.is_file())
-                    .unwrap_or(false)
-                {
-                    continue;
-                }
-                if let Some(raw_name) = extract_plugin_name(&path) {
-                    let norm = normalize_name(&raw_name);
-                    by_name.entry(raw_name).or_default().push(path.clone());
-                    by_name.entry(norm).or_default().push(path.clone());
-                }
-            }
-        }
-
-        if by_name.is_empty() {
-            tracing::info!(
-                dirs = ?searched_dirs,
-                "No plugin files found to remove in any plugin directory"
-            );
-            return Ok(());
-        }
-
-        let mut requested: Vec<String> = Vec::new();
+        // Plugins recorded in the install manifest are removed by their
+        // recorded files; the rest (installed before manifests existed, or
+        // copied in by hand) are looked up by file name.
+        let mut manifest = InstallManifest::load(&version_dir);
+        let mut removed_any = false;
+        let mut missing: Vec<String> = Vec::new();
+        let mut untracked: Vec<String> = Vec::new();
         for p in self.plugins {
-            match p {
-                PluginVersion::Name(n) => requested.push(n),
-                PluginVersion::NameAndVersion(n, v) => {
+            let (name, version) = match p {
+                PluginVersion::Name(n) => (n, None),
+                PluginVersion::NameAndVersion(n, v) => (n, Some(v.to_string())),
+            };
+            let Some(record) = manifest.as_ref().and_then(|m| m.plugin(&name)) else {
+                if let Some(version) = &version {
                     tracing::warn!(
-                        plugin = %n,
-                        version = %v,
-                        "Plugin remove does not track per-plugin version on disk; removing by name"
+                        plugin = %name,
+                        %version,
+                        "Plugin has no recorded version on disk; removing by name"
                     );
-                    requested.push(n)
                 }
+                untracked.push(name);
+                continue;
+            };
+            if let Some(version) = version.filter(|v| *v != record.version) {
+                tracing::warn!(
+                    plugin = %name,
+                    requested = %version,
+                    installed = %record.version,
+                    "A different version of the plugin is installed; not removing it"
+                );
+                missing.push(format!("{name}@{version}"));
+                continue;
             }
+            let Some(record) = manifest.as_mut().and_then(|m| m.remove_plugin(&name)) else {
+                continue;
+            };
+            let failed = remove_files(&version_dir, &record.files);
+            tracing::info!(
+                plugin = %record.name,
+                version = %record.version,
+                files = record.files.len() - failed,
+                "Removed plugin"
+            );
+            removed_any = true;
+        }
+        if let (Some(manifest), true) = (&manifest, removed_any) {
+            manifest.save(&version_dir)?;
         }
 
-        let mut removed_any = false;
-        let mut removed_targets: HashSet<PathBuf> = HashSet::new();
-        let mut missing: Vec<String> = Vec::new();
-        for want in requested {
-            let key_norm = normalize_name(&want);
-            if let Some(files) = by_name.get(&want).or_else(|| by_name.get(&key_norm)) {
-                for f in files {
-                    let real = tokio::fs::canonicalize(f)
-                        .await
-                        .unwrap_or_else(|_| f.clone());
-                    if removed_targets.contains(&real) {
-                        continue;
-                    }
-                    match tokio::fs::remove_file(f).await {
-                        Ok(_) => {
-                            tracing::info!(plugin = %want, path = %f.display(), "Removed plugin file");
-                            removed_targets.insert(real);
-                            removed_any = true;
-                        }
-                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                            tracing::debug!(path = %f.display(), "Plugin file already removed; skipping");
-                            removed_targets.insert(real);
-                            removed_any = true;
-                        }
-                        Err(e) => {
-                            tracing::warn!(error = %e, path = %f.display(), "Failed to remove plugin file");
-                        }
-                    }
-                }
-            } else {
-                missing.push(want);
+        if !untracked.is_empty() {
+            let mut searched_dirs: Vec<PathBuf> = Vec::new();
+            if plugin_dir.exists() {
+                searched_dirs.push(plugin_dir.clone());
+            }
+            if stable_plugin_dir.exists() {
+                searched_dirs.push(stable_plugin_dir.clone());
             }
+            removed_any |= remove_by_file_name(untracked, &searched_dirs, &mut missing).await?;
         }
 
         if !missing.is_empty() {
@@ -175,3 +148,75 @@ impl CommandExecutor for PluginRemoveArgs {
         Ok(())
     }
 }
+
+/// Remove the `requested` plugins by finding their shared objects in
+/// `searched_dirs` by file name, adding those not found to `missing`.
+/// Returns whether anything was removed.
+async fn remove_by_file_name(
+    requested: Vec<String>,
+    searched_dirs: &[PathBuf],
+    missing: &mut Vec<String>,
+) -> Result<bool> {
+    let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for dir in searched_dirs {
+        let mut rd = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = rd.next_entry().await? {
+            let path = entry.path();
+            if !entry
+                .file_type()
+                .await
+                .map(|t| t.is_file())
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            if let Some(raw_name) = extract_plugin_name(&path) {
+                let norm = normalize_name(&raw_name);
+                by_name.entry(raw_name).or_default().push(path.clone());
+                by_name.entry(norm).or_default().push(path.clone());
+            }
+        }
+    }
+
+    if by_name.is_empty() {
+        tracing::info!(
+            dirs = ?searched_dirs,
+            "No plugin files found to remove in any plugin directory"
+        );
+        return Ok(false);
+    }
+
+    let mut removed_any = false;
+    let mut removed_targets: HashSet<PathBuf> = HashSet::new();
+    for want in requested {
+        let key_norm = normalize_name(&want);
+        if let Some(files) = by_name.get(&want).or_else(|| by_name.get(&key_norm)) {
+            for f in files {
+                let real = tokio::fs::canonicalize(f)
+                    .await
+                    .unwrap_or_else(|_| f.clone());
+                if removed_targets.contains(&real) {
+                    continue;
+                }
+                match tokio::fs::remove_file(f).await {
+                    Ok(_) => {
+                        tracing::info!(plugin = %want, path = %f.display(), "Removed plugin file");
+                        removed_targets.insert(real);
+                        removed_any = true;
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        tracing::debug!(path = %f.display(), "Plugin file already removed; skipping");
+                        removed_targets.insert(real);
+                        removed_any = true;
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, path = %f.display(), "Failed to remove plugin file");
+                    }
+                }
+            }
+        } else {
+            missing.push(want);
+        }
+    }
+    Ok(removed_any)
+}
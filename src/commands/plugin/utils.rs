@@ -54,6 +54,15 @@ pub fn extract_plugin_name(path: &Path) -> Option<String> {
         .map(|core| core.to_string())
 }
 
+/// Lowercased alphanumeric form of a plugin name, so on-disk names such as
+/// `WasiLogging` match release names such as `wasi_logging`.
+pub fn normalize_name(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
 /// Recursively scans a directory for plugin shared objects.
 ///
 /// Patterns per platform:
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use tokio::fs;
@@ -6,9 +6,13 @@ use tokio::fs;
 use crate::{
     api::latest_installed_version,
     cli::{CommandContext, CommandExecutor},
-    commands::{default_path, use_cmd::UseArgs},
+    commands::default_path,
+    lock::InstallLock,
+    manifest::{remove_files, InstallManifest, MANIFEST_FILE_NAME},
+    output::print_json,
     prelude::*,
     shell_utils::uninstall_path,
+    transaction::is_staging_name,
 };
 
 #[derive(Debug, Parser)]
@@ -56,51 +60,18 @@ impl CommandExecutor for RemoveArgs {
             });
         }
 
-        let current_version = if target_dir.join("bin").exists() {
-            let bin_link = fs::read_link(target_dir.join("bin")).await?;
-            tracing::debug!(link = ?bin_link, "Raw symlink path");
-
-            let normalized = if bin_link.is_absolute() {
-                bin_link
-                    .strip_prefix(&target_dir)
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or(bin_link.clone())
-            } else {
-                bin_link.clone()
-            };
-
-            let mut comps = normalized.components().peekable();
-            let mut found: Option<String> = None;
-            while let Some(comp) = comps.next() {
-                if let std::path::Component::Normal(name) = comp {
-                    if name == "versions" {
-                        if let Some(std::path::Component::Normal(ver)) = comps.peek().copied() {
-                            let v = ver.to_string_lossy().to_string();
-                            tracing::debug!(version = %v, "Extracted version from symlink");
-                            found = Some(v);
-                        }
-                        break;
-                    }
-                }
-            }
-
-            if found.is_none() {
-                tracing::debug!(normalized = %normalized.display(), "Could not find versions/<ver> in symlink path");
-            }
-            found
-        } else {
-            tracing::debug!("No bin symlink found");
-            None
-        };
+        let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
+        let current_version = crate::fs::current_version(&target_dir).await;
 
         if self.all {
             tracing::debug!("Removing all installed versions");
+            let removed = installed_versions(&versions_dir).await?;
             if let Err(e) = uninstall_path(&target_dir) {
                 tracing::warn!(error = %e.to_string(), "Failed to update shell rc files during --all removal");
             }
             fs::remove_dir_all(&target_dir).await?;
             tracing::info!("All versions and configuration removed successfully");
-            return Ok(());
+            return report(&ctx, &target_dir, removed, None);
         }
 
         let version = ctx
@@ -114,19 +85,14 @@ impl CommandExecutor for RemoveArgs {
 
         let version_dir = versions_dir.join(version.to_string());
         if version_dir.exists() {
-            fs::remove_dir_all(&version_dir).await?;
+            remove_version_dir(&version_dir).await?;
             tracing::info!(version = %version, "Version removed successfully");
         }
 
         let removed_current = Some(version.to_string()) == current_version;
 
-        let mut remaining_versions = 0;
-        let mut dir_stream = fs::read_dir(&versions_dir).await?;
-        while let Some(entry) = dir_stream.next_entry().await? {
-            if entry.file_type().await?.is_dir() {
-                remaining_versions += 1;
-            }
-        }
+        let removed = vec![version.to_string()];
+        let remaining_versions = installed_versions(&versions_dir).await?.len();
 
         if remaining_versions == 0 {
             tracing::debug!("No versions remaining, cleaning up configuration");
@@ -135,26 +101,87 @@ impl CommandExecutor for RemoveArgs {
             }
             fs::remove_dir_all(&target_dir).await?;
             tracing::info!("All versions and configuration removed successfully");
-            return Ok(());
+            return report(&ctx, &target_dir, removed, None);
         }
 
-        if removed_current && remaining_versions > 0 {
+        let mut switched_to = None;
+        if removed_current {
             tracing::debug!(removed_version = ?current_version, "Current version was removed");
 
             let latest_version = latest_installed_version(&versions_dir)?;
 
             if let Some(version) = latest_version {
                 tracing::info!(version = %version, "Switching to latest version");
-                let use_args = UseArgs {
-                    version: version.to_string(),
-                    path: Some(target_dir),
-                };
-                use_args.execute(ctx).await?;
+                // Re-point the links directly rather than through `UseArgs` so
+                // `--output json` yields a single document for this command.
+                crate::fs::create_version_symlinks(&target_dir, &version.to_string()).await?;
+                if !ctx.output.is_json() {
+                    println!("Switched to WasmEdge runtime version: {version}");
+                }
+                switched_to = Some(version.to_string());
             } else {
                 tracing::warn!("No other versions found to switch to");
             }
         }
 
-        Ok(())
+        report(&ctx, &target_dir, removed, switched_to)
+    }
+}
+
+/// Remove an installed version: the runtime and plugin files its manifest
+/// records, the manifest, then the directories they leave empty. Files the
+/// manifest does not know about were not installed by wasmedgeup; they are
+/// named in a warning and removed along with the directory. A version
+/// installed before manifests existed is removed as a whole.
+async fn remove_version_dir(version_dir: &Path) -> Result<()> {
+    let Some(manifest) = InstallManifest::load(version_dir) else {
+        fs::remove_dir_all(version_dir).await?;
+        return Ok(());
+    };
+    remove_files(version_dir, manifest.all_files());
+    fs::remove_file(version_dir.join(MANIFEST_FILE_NAME)).await?;
+
+    let leftovers: Vec<PathBuf> = walkdir::WalkDir::new(version_dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| !e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .collect();
+    if !leftovers.is_empty() {
+        tracing::warn!(files = ?leftovers, "Removing files not installed by wasmedgeup");
+    }
+    fs::remove_dir_all(version_dir).await?;
+    Ok(())
+}
+
+/// Names of the version directories under `versions_dir`, leaving out
+/// staging directories of interrupted installs.
+async fn installed_versions(versions_dir: &Path) -> Result<Vec<String>> {
+    let mut versions = Vec::new();
+    let mut dir_stream = fs::read_dir(versions_dir).await?;
+    while let Some(entry) = dir_stream.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type().await?.is_dir() && !is_staging_name(&name) {
+            versions.push(name);
+        }
+    }
+    Ok(versions)
+}
+
+/// Print the JSON result for `--output json`; text mode relies on the
+/// messages already logged.
+fn report(
+    ctx: &CommandContext,
+    target_dir: &Path,
+    removed: Vec<String>,
+    switched_to: Option<String>,
+) -> Result<()> {
+    if ctx.output.is_json() {
+        print_json(&serde_json::json!({
+            "install_root": target_dir,
+            "removed": removed,
+            "switched_to": switched_to,
+        }))?;
     }
+    Ok(())
 }
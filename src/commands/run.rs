@@ -0,0 +1,118 @@
+use clap::Parser;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{
+    api::latest_installed_version,
+    cli::{CommandContext, CommandExecutor},
+    commands::{default_path, install::InstallArgs},
+    prelude::*,
+    shim::{self, RuntimeEnv},
+};
+
+#[derive(Debug, Parser)]
+pub struct RunArgs {
+    /// Installed WasmEdge version to run under, e.g. `latest`, `0.14.1`, `0.15.0`, etc.
+    pub version: String,
+
+    /// Install the version first if it is not installed yet
+    ///
+    /// The current default version is left unchanged.
+    #[arg(long)]
+    pub install: bool,
+
+    /// Set the install location for the WasmEdge runtime
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+
+    /// Command to execute, given after `--`
+    #[arg(last = true, required = true)]
+    pub command: Vec<OsString>,
+}
+
+impl RunArgs {
+    /// Resolve the requested version to one that is installed under
+    /// `target_dir`, installing it first when `--install` is set.
+    async fn installed_version(&self, target_dir: &Path, ctx: &CommandContext) -> Result<String> {
+        let versions_dir = target_dir.join("versions");
+
+        // Like `use`, `latest` means the highest locally installed version
+        // unless we were asked to install, in which case it means the latest
+        // release.
+        let version = if self.version == "latest" && !self.install {
+            match latest_installed_version(&versions_dir)? {
+                Some(v) => v.to_string(),
+                None => {
+                    return Err(Error::VersionNotFound {
+                        version: "latest".to_string(),
+                    })
+                }
+            }
+        } else if self.version == "latest" {
+            ctx.client.resolve_version("latest").await?.to_string()
+        } else {
+            self.version.clone()
+        };
+
+        if !versions_dir.join(&version).is_dir() {
+            if !self.install {
+                eprintln!(
+                    "WasmEdge {version} is not installed. Run `wasmedgeup install {version}` \
+                     first, or pass --install."
+                );
+                return Err(Error::VersionNotFound { version });
+            }
+            InstallArgs {
+                versions: vec![version.clone()],
+                default: None,
+                path: Some(target_dir.to_path_buf()),
+                tmpdir: None,
+                os: None,
+                arch: None,
+                no_verify: false,
+                verify: false,
+                no_switch: true,
+                from_file: None,
+                from_dir: None,
+            }
+            .execute(ctx.clone())
+            .await?;
+        }
+        Ok(version)
+    }
+}
+
+impl CommandExecutor for RunArgs {
+    #[tracing::instrument(name = "run", skip_all, fields(version = self.version))]
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let target_dir = match &self.path {
+            Some(p) => p.clone(),
+            None => default_path()?,
+        };
+        let version = self.installed_version(&target_dir, &ctx).await?;
+        let env = RuntimeEnv::new(&target_dir, &version)?;
+
+        let (program, args) = self
+            .command
+            .split_first()
+            .expect("clap requires at least one command argument");
+        tracing::debug!(version = %env.version(), program = %program.to_string_lossy(), "Running command");
+
+        // Resolve a bare `wasmedge` to the selected version up front: Windows
+        // looks programs up in the parent's PATH, not the one we pass down.
+        let mut cmd = match shim::shim_name(program) {
+            Some(name) if env.binary(name).is_file() && program == name => {
+                Command::new(env.binary(name))
+            }
+            _ => Command::new(program),
+        };
+        cmd.args(args);
+        env.apply(&mut cmd);
+
+        let code = shim::exec(cmd)?;
+        std::process::exit(code);
+    }
+}
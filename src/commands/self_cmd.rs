@@ -0,0 +1,238 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+use semver::Version;
+use snafu::ResultExt;
+
+use crate::{
+    cli::{CommandContext, CommandExecutor},
+    commands::default_path,
+    lock::InstallLock,
+    output::print_json,
+    prelude::*,
+    self_update::{self, SelfRelease},
+    shell_utils::uninstall_path,
+    shim,
+};
+
+#[derive(Debug, Parser)]
+pub struct SelfCli {
+    #[command(subcommand)]
+    commands: SelfCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SelfCommands {
+    /// Update wasmedgeup to the latest release
+    Update(UpdateArgs),
+    /// Remove wasmedgeup and the shell setup it wrote
+    Uninstall(PathArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct UpdateArgs {
+    /// Only check whether a newer release exists, without installing it
+    #[arg(long)]
+    pub check: bool,
+
+    /// Skip checksum verification of the downloaded release
+    #[arg(long, overrides_with = "verify")]
+    pub no_verify: bool,
+
+    /// Verify checksums even if `verify = false` is set in the config file
+    #[arg(long, overrides_with = "no_verify")]
+    pub verify: bool,
+
+    #[command(flatten)]
+    pub path: PathArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PathArgs {
+    /// Set the install location for the WasmEdge runtime
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+}
+
+impl PathArgs {
+    fn target_dir(self) -> Result<PathBuf> {
+        match self.path {
+            Some(p) => Ok(p),
+            None => default_path(),
+        }
+    }
+}
+
+impl CommandExecutor for SelfCli {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        match self.commands {
+            SelfCommands::Update(args) => args.update(ctx).await,
+            SelfCommands::Uninstall(args) => uninstall(args, ctx).await,
+        }
+    }
+}
+
+impl UpdateArgs {
+    /// Replaces the running binary with the latest release for this host,
+    /// then refreshes the shims of the install root if they are enabled so
+    /// they run the new binary too.
+    #[tracing::instrument(name = "self_update", skip_all)]
+    async fn update(self, ctx: CommandContext) -> Result<()> {
+        let current = Version::parse(env!("CARGO_PKG_VERSION")).context(SemVerSnafu {})?;
+        let release = SelfRelease::latest(&ctx.client).await?;
+        tracing::debug!(%current, latest = %release.version, "Fetched latest wasmedgeup release");
+
+        let exe = self_update::current_exe()?;
+        let update_available = release.version > current;
+        if !update_available || self.check {
+            if ctx.output.is_json() {
+                return print_json(&serde_json::json!({
+                    "current": current.to_string(),
+                    "latest": release.version.to_string(),
+                    "update_available": update_available,
+                    "updated": false,
+                    "binary": exe,
+                }));
+            }
+            if update_available {
+                println!(
+                    "wasmedgeup {} is available (current: {current}); run `wasmedgeup self update` to install it",
+                    release.version
+                );
+            } else {
+                println!("wasmedgeup {current} is up to date");
+            }
+            return Ok(());
+        }
+
+        let no_verify = ctx.config.skip_verification(self.verify, self.no_verify);
+        install(&ctx, &release, &exe, no_verify).await?;
+        tracing::info!(version = %release.version, binary = %exe.display(), "wasmedgeup updated");
+
+        let root = self.path.target_dir()?;
+        let shims_refreshed = shim::is_shim_dir(&root.join("bin"));
+        if shims_refreshed {
+            let _lock = InstallLock::acquire(&root, ctx.lock_wait).await?;
+            shim::enable_with(&root, &exe).await?;
+        }
+
+        if ctx.output.is_json() {
+            return print_json(&serde_json::json!({
+                "current": current.to_string(),
+                "latest": release.version.to_string(),
+                "update_available": true,
+                "updated": true,
+                "binary": exe,
+                "shims_refreshed": shims_refreshed,
+            }));
+        }
+        println!(
+            "Updated wasmedgeup from {current} to {} at {}",
+            release.version,
+            exe.display()
+        );
+        Ok(())
+    }
+}
+
+/// Download the release archive for this host, verify it and swap its binary
+/// in for `exe`.
+#[cfg(unix)]
+async fn install(
+    ctx: &CommandContext,
+    release: &SelfRelease,
+    exe: &Path,
+    no_verify: bool,
+) -> Result<()> {
+    let (host, _, _) = crate::system::os::detect_os();
+    let triple =
+        crate::system::detector::compute_target_triple(host.os_type, host.arch, host.libc.kind);
+    let asset = release.asset(&triple)?;
+
+    let expected = if no_verify {
+        tracing::warn!("Checksum verification skipped");
+        None
+    } else {
+        Some(release.checksum(&ctx.client, &asset).await?)
+    };
+
+    let tmp = crate::fs::create_temp_workspace(&std::env::temp_dir(), "wasmedgeup-self-update")?;
+    let archive = tmp.path().join(&asset.name);
+    tracing::info!(asset = %asset.name, "Downloading wasmedgeup release");
+    ctx.client
+        .download_to_path(
+            asset.url,
+            &archive,
+            ctx.no_progress,
+            "wasmedgeup download",
+            expected.as_deref(),
+        )
+        .await?;
+
+    let extracted = tmp.path().join("extracted");
+    crate::fs::extract_archive(std::fs::File::open(&archive)?, &extracted).await?;
+    let new = extracted
+        .join(format!("wasmedgeup-{triple}"))
+        .join("wasmedgeup");
+    if !new.is_file() {
+        return Err(Error::InvalidArchiveStructure {
+            found_file: asset.name,
+        });
+    }
+    self_update::replace_exe(&new, exe)
+}
+
+#[cfg(windows)]
+async fn install(
+    _ctx: &CommandContext,
+    _release: &SelfRelease,
+    _exe: &Path,
+    _no_verify: bool,
+) -> Result<()> {
+    // A running executable cannot be replaced on Windows.
+    Err(Error::UnsupportedPlatform {
+        os: "windows (self update; download the new release manually)".to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+    })
+}
+
+/// Removes the shell setup written for the install root and the wasmedgeup
+/// binary. Installed runtimes are kept so uninstalling never deletes data
+/// the user did not ask about.
+#[tracing::instrument(name = "self_uninstall", skip_all)]
+async fn uninstall(args: PathArgs, ctx: CommandContext) -> Result<()> {
+    let root = args.target_dir()?;
+    let exe = self_update::current_exe()?;
+    let _lock = InstallLock::acquire(&root, ctx.lock_wait).await?;
+
+    // The shims are copies of this binary; put the `bin` link back first.
+    shim::disable(&root).await?;
+    uninstall_path(&root)?;
+    let removed = self_update::remove_exe(&exe)?;
+    tracing::info!(binary = %exe.display(), removed, "wasmedgeup uninstalled");
+
+    if ctx.output.is_json() {
+        return print_json(&serde_json::json!({
+            "binary": exe,
+            "binary_removed": removed,
+            "install_root": root,
+        }));
+    }
+    if removed {
+        println!("Removed {}", exe.display());
+    } else {
+        println!(
+            "Shell setup removed; delete {} once wasmedgeup has exited",
+            exe.display()
+        );
+    }
+    if root.join("versions").is_dir() {
+        println!(
+            "Installed runtimes in {} were kept; delete that directory to remove them",
+            root.display()
+        );
+    }
+    Ok(())
+}
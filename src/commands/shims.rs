@@ -0,0 +1,66 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::{
+    cli::{CommandContext, CommandExecutor},
+    commands::default_path,
+    lock::InstallLock,
+    prelude::*,
+    shim,
+};
+
+#[derive(Debug, Parser)]
+pub struct ShimsCli {
+    #[command(subcommand)]
+    commands: ShimsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ShimsCommands {
+    /// Replace the `bin` symlink with shims that pick the runtime version per invocation
+    Enable(ShimsArgs),
+    /// Remove the shims and restore the `bin` symlink to the default version
+    Disable(ShimsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ShimsArgs {
+    /// Set the install location for the WasmEdge runtime
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+}
+
+impl ShimsArgs {
+    fn target_dir(self) -> Result<PathBuf> {
+        match self.path {
+            Some(p) => Ok(p),
+            None => default_path(),
+        }
+    }
+}
+
+impl CommandExecutor for ShimsCli {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        match self.commands {
+            ShimsCommands::Enable(args) => {
+                let target_dir = args.target_dir()?;
+                let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
+                shim::enable(&target_dir).await?;
+                println!(
+                    "Shims enabled in {}. Select a version per shell with `WASMEDGE_VERSION`, \
+                     a `wasmedge-toolchain.toml`, or `wasmedge +<version>`.",
+                    target_dir.join("bin").display()
+                );
+            }
+            ShimsCommands::Disable(args) => {
+                let target_dir = args.target_dir()?;
+                let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
+                shim::disable(&target_dir).await?;
+                println!("Shims disabled in {}", target_dir.join("bin").display());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+
+use crate::{
+    api::latest_installed_version,
+    cli::{CommandContext, CommandExecutor},
+    commands::{
+        default_path,
+        install::InstallArgs,
+        plugin::{
+            install::PluginInstallArgs,
+            utils::{extract_plugin_name, normalize_name},
+            version::PluginVersion,
+        },
+    },
+    lock::InstallLock,
+    manifest::InstallManifest,
+    output::print_json,
+    prelude::*,
+};
+
+#[derive(Debug, Parser)]
+pub struct UpdateArgs {
+    /// Set the install location for the WasmEdge runtime
+    ///
+    /// Defaults to `$HOME/.wasmedge` on Unix-like systems and `%HOME%\.wasmedge` on Windows.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+
+    /// Set the temporary directory for staging downloaded assets
+    ///
+    /// Defaults to `tmpdir` from the config file, else the system temporary directory.
+    #[arg(short, long)]
+    pub tmpdir: Option<PathBuf>,
+
+    /// Skip checksum retrieval and verification for the downloaded runtime and plugins
+    #[arg(long, overrides_with = "verify")]
+    pub no_verify: bool,
+
+    /// Verify checksums even if `verify = false` is set in the config file
+    #[arg(long, overrides_with = "no_verify")]
+    pub verify: bool,
+
+    /// Remove the previous version once the update succeeded
+    #[arg(long)]
+    pub remove_old: bool,
+}
+
+impl CommandExecutor for UpdateArgs {
+    /// Upgrades the newest installed runtime to the latest release.
+    ///
+    /// # Steps:
+    /// 1. Compares the newest installed version with the latest release.
+    /// 2. Installs the latest release without switching to it.
+    /// 3. Installs the plugins of the old version into the new one: those its
+    ///    manifest records, or for versions without one, those found in
+    ///    `versions/<old>/plugin`.
+    /// 4. Switches the symlinks to the new version.
+    /// 5. Removes the old version if `--remove-old` is given.
+    ///
+    /// A failure before step 4 leaves the default version untouched.
+    #[tracing::instrument(name = "update", skip_all)]
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
+        let target_dir = match self.path {
+            Some(p) => p,
+            None => default_path()?,
+        };
+        let versions_dir = target_dir.join("versions");
+        let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
+
+        let old = latest_installed_version(&versions_dir)?.ok_or(Error::VersionNotFound {
+            version: "<none installed>".to_string(),
+        })?;
+        let latest = ctx.client.latest_release().await?;
+        tracing::debug!(%old, %latest, "Compared installed and latest versions");
+
+        if latest <= old {
+            if ctx.output.is_json() {
+                return print_json(&serde_json::json!({
+                    "install_root": target_dir,
+                    "from": old.to_string(),
+                    "to": old.to_string(),
+                    "updated": false,
+                }));
+            }
+            println!("WasmEdge {old} is up to date");
+            return Ok(());
+        }
+
+        let old_dir = versions_dir.join(old.to_string());
+        let found = match recorded_plugins(&old_dir) {
+            Some(recorded) => recorded,
+            None => installed_plugins(&old_dir.join("plugin"))?,
+        };
+        let plugins = if found.is_empty() {
+            found
+        } else {
+            // On-disk names do not always match release names (e.g.
+            // `WasiLogging` vs `wasi_logging`); map them through the new
+            // release's plugin assets when those can be listed.
+            let available = match ctx.client.github_release_assets(&latest.to_string()).await {
+                Ok(assets) => assets.into_iter().map(|a| a.plugin).collect(),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to list plugins of the new release; using on-disk names");
+                    Vec::new()
+                }
+            };
+            resolve_plugin_names(&found, &available)
+        };
+        tracing::debug!(plugins = ?plugins, "Plugins to migrate");
+
+        InstallArgs {
+            versions: vec![latest.to_string()],
+            default: None,
+            path: Some(target_dir.clone()),
+            tmpdir: self.tmpdir.clone(),
+            os: None,
+            arch: None,
+            no_verify: self.no_verify,
+            verify: self.verify,
+            no_switch: true,
+            from_file: None,
+            from_dir: None,
+        }
+        .install(&ctx)
+        .await?;
+
+        if !plugins.is_empty() {
+            PluginInstallArgs {
+                plugins: plugins.iter().cloned().map(PluginVersion::Name).collect(),
+                tmpdir: self.tmpdir,
+                runtime: Some(latest.to_string()),
+                path: Some(target_dir.clone()),
+                no_verify: self.no_verify,
+                verify: self.verify,
+                from_file: None,
+                from_dir: None,
+                jobs: None,
+            }
+            .execute(ctx.clone())
+            .await?;
+        }
+
+        crate::fs::create_version_symlinks(&target_dir, &latest.to_string()).await?;
+        tracing::info!(%old, %latest, "Switched to the updated version");
+
+        if self.remove_old {
+            tokio::fs::remove_dir_all(&old_dir)
+                .await
+                .map_err(|source| Error::Io {
+                    action: "remove previous version".to_string(),
+                    path: old_dir.display().to_string(),
+                    source,
+                })?;
+            tracing::info!(version = %old, "Removed previous version");
+        }
+
+        if ctx.output.is_json() {
+            return print_json(&serde_json::json!({
+                "install_root": target_dir,
+                "from": old.to_string(),
+                "to": latest.to_string(),
+                "updated": true,
+                "plugins": plugins,
+                "removed_old": self.remove_old,
+            }));
+        }
+        println!("Updated WasmEdge from {old} to {latest}");
+        if !plugins.is_empty() {
+            println!("Migrated plugins: {}", plugins.join(", "));
+        }
+        if self.remove_old {
+            println!("Removed WasmEdge {old}");
+        }
+        Ok(())
+    }
+}
+
+/// Names of the plugins the manifest of the version in `version_dir`
+/// records, as installed, e.g. `wasi_nn-ggml`. `None` if the version has no
+/// manifest.
+fn recorded_plugins(version_dir: &Path) -> Option<Vec<String>> {
+    let manifest = InstallManifest::load(version_dir)?;
+    Some(manifest.plugins.into_iter().map(|p| p.name).collect())
+}
+
+/// Names of the plugins installed in `plugin_dir`, as derived from their
+/// shared object file names. Used for versions without a manifest.
+fn installed_plugins(plugin_dir: &Path) -> Result<Vec<String>> {
+    let Ok(entries) = std::fs::read_dir(plugin_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut names = BTreeSet::new();
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(name) = extract_plugin_name(&path) {
+            names.insert(name);
+        }
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Map each name in `found` to the release plugin name in `available` that
+/// it matches after normalization, keeping names without a match as is.
+fn resolve_plugin_names(found: &[String], available: &[String]) -> Vec<String> {
+    let mut resolved = Vec::new();
+    for name in found {
+        let norm = normalize_name(name);
+        let release_name = available
+            .iter()
+            .find(|a| *a == name)
+            .or_else(|| available.iter().find(|a| normalize_name(a) == norm))
+            .unwrap_or(name);
+        if !resolved.contains(release_name) {
+            resolved.push(release_name.clone());
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::plugin::utils::plugin_filename;
+
+    #[test]
+    fn lists_installed_plugins() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["wasi_nn", "WasiLogging"] {
+            std::fs::write(dir.path().join(plugin_filename(name)), "").unwrap();
+        }
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+
+        assert_eq!(
+            installed_plugins(dir.path()).unwrap(),
+            ["WasiLogging", "wasi_nn"]
+        );
+        assert!(installed_plugins(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn prefers_plugin_names_recorded_in_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(recorded_plugins(dir.path()), None);
+
+        let mut manifest = InstallManifest::new("0.14.1");
+        manifest.add_plugin(crate::manifest::PluginRecord {
+            name: "wasi_nn-ggml".to_string(),
+            version: "0.14.1".to_string(),
+            platform: "manylinux_2_28_x86_64".to_string(),
+            source: "wasi_nn-ggml.tar.gz".to_string(),
+            sha256: "abcd".to_string(),
+            files: vec![PathBuf::from("plugin").join(plugin_filename("wasi_nn"))],
+        });
+        manifest.save(dir.path()).unwrap();
+        assert_eq!(recorded_plugins(dir.path()).unwrap(), ["wasi_nn-ggml"]);
+    }
+
+    #[test]
+    fn resolves_release_plugin_names() {
+        let found = vec![
+            "WasiLogging".to_string(),
+            "wasi_nn".to_string(),
+            "wasmedge_custom".to_string(),
+        ];
+        let available = vec![
+            "wasi_logging".to_string(),
+            "wasi_nn".to_string(),
+            "wasi_nn-ggml".to_string(),
+        ];
+        assert_eq!(
+            resolve_plugin_names(&found, &available),
+            ["wasi_logging", "wasi_nn", "wasmedge_custom"]
+        );
+        assert_eq!(resolve_plugin_names(&found[..1], &[]), ["WasiLogging"]);
+    }
+}
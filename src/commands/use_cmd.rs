@@ -1,18 +1,25 @@
 use clap::Parser;
-use snafu::ResultExt;
 use std::path::PathBuf;
 
 use crate::{
-    api::latest_installed_version,
+    api::{installed_versions, latest_installed_version, VersionSpec},
     cli::{CommandContext, CommandExecutor},
     commands::default_path,
     fs,
+    lock::InstallLock,
+    output::print_json,
     prelude::*,
+    toolchain_file::ToolchainFile,
 };
 
 #[derive(Debug, Parser)]
 pub struct UseArgs {
-    /// WasmEdge version to use, e.g. `latest`, `0.14.1`, `0.15.0`, etc.
+    /// WasmEdge version to use, e.g. `latest`, `0.14.1`, `~0.14`, `0.14.x`, etc.
+    ///
+    /// Requirements and channels resolve to the newest matching installed version.
+    ///
+    /// Defaults to the version pinned by the nearest `wasmedge-toolchain.toml`.
+    #[arg(default_value = "")]
     pub version: String,
 
     /// Set the install location for the WasmEdge runtime
@@ -24,19 +31,31 @@ pub struct UseArgs {
 
 impl CommandExecutor for UseArgs {
     #[tracing::instrument(name = "use", skip_all, fields(version = self.version))]
-    async fn execute(self, _ctx: CommandContext) -> Result<()> {
+    async fn execute(self, ctx: CommandContext) -> Result<()> {
         let target_dir = match self.path {
             Some(p) => p,
             None => default_path()?,
         };
         let versions_dir = target_dir.join("versions");
 
+        let requested = if self.version.is_empty() {
+            match ToolchainFile::discover_version()? {
+                Some((version, path)) => {
+                    tracing::debug!(%version, path = %path.display(), "Using version pinned by toolchain file");
+                    version
+                }
+                None => return Err(Error::NoVersionSpecified),
+            }
+        } else {
+            self.version
+        };
+
         // `use` switches between locally installed versions. Resolving "latest"
         // here means the highest locally installed version — hitting the network
         // (as the previous implementation did via `resolve_version`) would make
         // the command require connectivity and silently disagree with what's
         // actually on disk.
-        let version = if self.version == "latest" {
+        let version = if requested == "latest" {
             match latest_installed_version(&versions_dir)? {
                 Some(v) => v,
                 None => {
@@ -50,7 +69,20 @@ impl CommandExecutor for UseArgs {
                 }
             }
         } else {
-            semver::Version::parse(&self.version).context(SemVerSnafu {})?
+            match VersionSpec::parse(&requested)? {
+                VersionSpec::Exact(version) => version,
+                spec => spec
+                    .select(&installed_versions(&versions_dir)?)
+                    .ok_or_else(|| {
+                        eprintln!(
+                            "No installed WasmEdge version matches '{requested}'. \
+                            Run `wasmedgeup install {requested}` to install one."
+                        );
+                        Error::VersionNotFound {
+                            version: requested.clone(),
+                        }
+                    })?,
+            }
         };
         tracing::debug!(%version, "Resolved version for use");
 
@@ -64,8 +96,15 @@ impl CommandExecutor for UseArgs {
             });
         }
 
+        let _lock = InstallLock::acquire(&target_dir, ctx.lock_wait).await?;
         fs::create_version_symlinks(&target_dir, &version.to_string()).await?;
 
+        if ctx.output.is_json() {
+            return print_json(&serde_json::json!({
+                "version": version.to_string(),
+                "install_root": target_dir,
+            }));
+        }
         println!("Switched to WasmEdge runtime version: {version}");
         Ok(())
     }
@@ -6,6 +6,9 @@
 
 /// Git URL for the WasmEdge project, used by `api::releases` to enumerate
 /// released versions via git refs (avoids a GitHub API rate limit hit).
+///
+/// This and the two URLs below are defaults; see `api::Endpoints` for the
+/// mirror overrides.
 pub const WASMEDGE_GIT_URL: &str = "https://github.com/WasmEdge/WasmEdge.git";
 
 /// Base URL for downloading released WasmEdge runtime and plugin archives.
@@ -16,6 +19,11 @@ pub const WASMEDGE_RELEASE_BASE_URL: &str =
 pub const WASMEDGE_GH_RELEASE_TAG_API: &str =
     "https://api.github.com/repos/WasmEdge/WasmEdge/releases/tags";
 
+/// Base URL for the GitHub REST API endpoint listing wasmedgeup's own
+/// releases, used by `wasmedgeup self update`.
+pub const WASMEDGEUP_GH_RELEASES_API: &str =
+    "https://api.github.com/repos/WasmEdge/wasmedgeup/releases";
+
 /// File name of the SHA256 checksum file published alongside runtime releases.
 pub const CHECKSUM_FILE_NAME: &str = "SHA256SUM";
 
@@ -25,5 +33,28 @@ pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 15;
 /// Default request/read timeout (seconds) for all HTTP calls.
 pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 90;
 
+/// Default number of retries for failed network operations.
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a failed network operation, in
+/// milliseconds; it doubles for each further retry.
+pub const RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// Longest wait between two attempts of a network operation, in seconds.
+/// A server asking for a longer wait (e.g. a GitHub rate-limit reset an
+/// hour away) fails the operation instead.
+pub const MAX_RETRY_WAIT_SECS: u64 = 60;
+
 /// Buffer size used when streaming downloads and computing checksums.
 pub const DOWNLOAD_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Default time (seconds) cached release metadata is used before the git
+/// remote and the GitHub API are asked again.
+pub const DEFAULT_METADATA_TTL_SECS: u64 = 60 * 60;
+
+/// Default number of plugin archives `plugin install` downloads at once.
+pub const DEFAULT_PLUGIN_DOWNLOAD_JOBS: usize = 4;
+
+/// File name of the per-directory runtime pin, looked up from the current
+/// directory upwards (see `toolchain_file`).
+pub const TOOLCHAIN_FILE_NAME: &str = "wasmedge-toolchain.toml";
@@ -61,12 +61,26 @@ pub enum Error {
     #[snafu(display("Checksum not found for version {} asset {}", version, asset))]
     ChecksumNotFound { version: String, asset: String },
 
+    #[snafu(display("Release {version} has no asset named {asset}"))]
+    ReleaseAssetNotFound { version: String, asset: String },
+
+    #[snafu(display("Local archive not found: {archive}"))]
+    LocalArchiveNotFound { archive: String },
+
     #[snafu(display("Checksum mismatch. Expected: {}, got: {}", expected, actual))]
     ChecksumMismatch { expected: String, actual: String },
 
     #[snafu(display("Invalid path {path}: {reason}"))]
     InvalidPath { path: String, reason: String },
 
+    #[snafu(display(
+        "`{binary}` is not installed for WasmEdge {version}; run `wasmedgeup install {version}`"
+    ))]
+    ShimTargetNotFound { binary: String, version: String },
+
+    #[snafu(display("wasmedgeup doctor found {count} problem(s)"))]
+    DoctorFoundProblems { count: usize },
+
     #[snafu(display("Failed to {action} at {path}: {source}"))]
     Io {
         action: String,
@@ -85,12 +99,61 @@ pub enum Error {
     #[snafu(display("No WasmEdge releases were found"))]
     NoReleasesFound,
 
+    #[snafu(display("No WasmEdge release matches '{requirement}'"))]
+    NoMatchingRelease { requirement: String },
+
+    #[snafu(display("--default {version} is not one of the versions being installed"))]
+    DefaultVersionNotRequested { version: String },
+
     #[snafu(display("No plugins specified for installation"))]
     NoPluginsSpecified,
 
+    #[snafu(display(
+        "No version specified; provide a version or pin one in a wasmedge-toolchain.toml file"
+    ))]
+    NoVersionSpecified,
+
+    #[snafu(display("Invalid toolchain file {path}"))]
+    ToolchainFile {
+        path: String,
+        source: toml::de::Error,
+    },
+
+    #[snafu(display("Invalid plugin '{plugin}' in toolchain file {path}: {source}"))]
+    ToolchainPlugin {
+        path: String,
+        plugin: String,
+        source: semver::Error,
+    },
+
+    #[snafu(display("Invalid config file {path}"))]
+    ConfigFile {
+        path: String,
+        source: toml::de::Error,
+    },
+
+    #[snafu(display("Unknown config key '{key}'; known keys: {known}"))]
+    UnknownConfigKey { key: String, known: String },
+
+    #[snafu(display("Invalid value for config key {key}: {source}"))]
+    InvalidConfigValue {
+        key: String,
+        source: toml::de::Error,
+    },
+
+    #[snafu(display("Invalid URL '{url}' for {setting}"))]
+    InvalidEndpoint {
+        setting: String,
+        url: String,
+        source: url::ParseError,
+    },
+
     #[snafu(display("No plugin shared object was installed for '{plugin}' {version}: the archive contained no usable shared object, or every copy failed"))]
     PluginNotInstalled { plugin: String, version: String },
 
+    #[snafu(display("Failed to install plugins: {plugins}"))]
+    PluginsNotInstalled { plugins: String },
+
     #[cfg(windows)]
     #[snafu(display("Error: Cannot create symbolic links.\n\nTo enable symlink creation on Windows:\n  1. Run as Administrator, or\n  2. Enable Developer Mode:\n     - Open Windows Settings\n     - Update & Security > For developers\n     - Enable 'Developer Mode'\n"))]
     WindowsSymlinkError { version: String },
@@ -103,8 +166,29 @@ pub enum Error {
     ))]
     HomeDirNotFound,
 
+    #[snafu(display("GitHub API rate limit exceeded; it resets {resets}. Set GITHUB_TOKEN or GH_TOKEN, or `github_token` in the config file, to raise the limit"))]
+    GitHubRateLimited { resets: String },
+
+    #[snafu(display("No cached {what} for {source_url}; run without --offline to fetch it"))]
+    OfflineMetadataMissing { what: String, source_url: String },
+
     #[snafu(display("Failed to build HTTP client: {reason}"))]
     HttpClientBuild { reason: String },
+
+    #[snafu(display("Invalid minisign public key in `signature_public_key`: {reason}"))]
+    InvalidSignaturePublicKey { reason: String },
+
+    #[snafu(display("Signature policy is `require` but no `signature_public_key` is configured"))]
+    SignaturePublicKeyMissing,
+
+    #[snafu(display("No signature found for {file}; signature policy is `require`"))]
+    SignatureMissing { file: String },
+
+    #[snafu(display("Signature verification failed for {file}: {reason}"))]
+    SignatureInvalid { file: String, reason: String },
+
+    #[snafu(display("{root} is in use by another wasmedgeup process ({holder}); try again once it finishes, or pass --wait <SECONDS> to wait for it"))]
+    InstallRootLocked { root: String, holder: String },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
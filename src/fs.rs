@@ -6,7 +6,7 @@ use std::io::Seek;
 #[cfg(unix)]
 use std::os::unix::fs::symlink as symlink_unix;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(windows)]
 use std::os::windows::fs::{symlink_dir, symlink_file};
@@ -98,7 +98,8 @@ pub fn can_write_to_directory(path: &Path) -> bool {
 }
 
 /// Copy every file and symlink reachable from `from_dir` into `to_dir`,
-/// renaming any `lib64` path component to `lib` along the way.
+/// renaming any `lib64` path component to `lib` along the way, and return
+/// the copied paths relative to `to_dir`.
 ///
 /// # Semantics — "walk all, log all, return first"
 ///
@@ -111,7 +112,7 @@ pub fn can_write_to_directory(path: &Path) -> bool {
 ///
 /// Consequences for callers:
 ///
-/// - On success (`Ok(())`), every entry copied cleanly.
+/// - On success (`Ok(_)`), every entry copied cleanly.
 /// - On failure (`Err(_)`), `to_dir` may be **partially populated** with
 ///   whatever entries succeeded before/after the failing ones. Callers that
 ///   need atomic install behavior should layer a tempdir-and-rename strategy
@@ -122,7 +123,8 @@ pub fn can_write_to_directory(path: &Path) -> bool {
 /// Both walker errors (e.g. permission denied descending into a subdir) and
 /// per-entry errors (failed metadata read, failed copy, failed symlink
 /// removal/creation) are counted and considered for `first_error`.
-pub async fn copy_tree(from_dir: &Path, to_dir: &Path) -> Result<()> {
+pub async fn copy_tree(from_dir: &Path, to_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
     let mut first_error: Option<Error> = None;
     let mut failure_count: usize = 0;
 
@@ -132,8 +134,10 @@ pub async fn copy_tree(from_dir: &Path, to_dir: &Path) -> Result<()> {
     // denied while reading a subdir, broken loop detection, etc.).
     for result in WalkDir::new(from_dir) {
         match result {
-            Ok(entry) => {
-                if let Err(e) = copy_entry(&entry, from_dir, to_dir).await {
+            Ok(entry) => match copy_entry(&entry, from_dir, to_dir).await {
+                Ok(Some(path)) => copied.push(path),
+                Ok(None) => {}
+                Err(e) => {
                     tracing::warn!(
                         error = %e,
                         entry = %entry.path().display(),
@@ -144,7 +148,7 @@ pub async fn copy_tree(from_dir: &Path, to_dir: &Path) -> Result<()> {
                         first_error = Some(e);
                     }
                 }
-            }
+            },
             Err(walk_err) => {
                 // Snapshot the original walkdir error message before
                 // into_io_error() consumes it; this preserves loop-detection
@@ -183,15 +187,19 @@ pub async fn copy_tree(from_dir: &Path, to_dir: &Path) -> Result<()> {
         );
         return Err(e);
     }
-    Ok(())
+    Ok(copied)
 }
 
 /// Copy or symlink a single walkdir entry into `to_dir`, mapping `lib64` to
-/// `lib` along the way. Directories are skipped (the walker walks into them
-/// and emits files/symlinks separately); any I/O failure returns a typed
-/// error so `copy_tree` can surface partial installs instead of silently
-/// succeeding.
-async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -> Result<()> {
+/// `lib` along the way, and return its path relative to `to_dir`.
+/// Directories are skipped (the walker walks into them and emits
+/// files/symlinks separately); any I/O failure returns a typed error so
+/// `copy_tree` can surface partial installs instead of silently succeeding.
+async fn copy_entry(
+    entry: &walkdir::DirEntry,
+    from_dir: &Path,
+    to_dir: &Path,
+) -> Result<Option<PathBuf>> {
     tracing::trace!(entry = %entry.path().display(), "Copying entry");
 
     // walkdir::Error wraps an optional io::Error; preserve it (kind /
@@ -211,7 +219,7 @@ async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -
         }
     })?;
     if !metadata.is_file() && !metadata.is_symlink() {
-        return Ok(());
+        return Ok(None);
     }
 
     // Calculate the target location by stripping the source directory
@@ -224,7 +232,7 @@ async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -
     //   entry    = '/from/path/foo/lib64/something.so'
     //   to_dir   = '/to/path'
     //   result   = '/to/path/foo/lib/something.so'
-    let target_loc = to_dir.join(
+    let relative = PathBuf::from(
         entry
             .path()
             .strip_prefix(from_dir)
@@ -232,6 +240,7 @@ async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -
             .to_string_lossy()
             .replace("lib64", LIB_DIR),
     );
+    let target_loc = to_dir.join(&relative);
 
     let parent = target_loc.parent().ok_or_else(|| Error::InvalidPath {
         path: target_loc.display().to_string(),
@@ -246,7 +255,7 @@ async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -
         })?;
 
     if metadata.is_symlink() {
-        copy_symlink_entry(entry.path(), &target_loc).await
+        copy_symlink_entry(entry.path(), &target_loc).await?;
     } else {
         fs::copy(entry.path(), &target_loc)
             .await
@@ -259,8 +268,8 @@ async fn copy_entry(entry: &walkdir::DirEntry, from_dir: &Path, to_dir: &Path) -
                 ),
                 source,
             })?;
-        Ok(())
     }
+    Ok(Some(relative))
 }
 
 /// Recreate a symlink from `src_link` (whose target we follow with
@@ -422,6 +431,14 @@ pub async fn extract_archive(file: std::fs::File, dest: &Path) -> Result<()> {
     }
 }
 
+/// Hex SHA256 of the whole of `file`, which is left rewound.
+pub fn file_sha256(file: &mut std::fs::File) -> Result<String> {
+    file.rewind()?;
+    let sha256 = crate::api::sha256_hex(file)?;
+    file.rewind()?;
+    Ok(sha256)
+}
+
 fn extract_archive_blocking(mut file: std::fs::File, dest: &Path) -> Result<()> {
     file.rewind()?;
 
@@ -458,6 +475,52 @@ fn extract_zip(file: &mut std::fs::File, to: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Return the version the stable links in `base_dir` currently point at.
+///
+/// Reads `bin` first and falls back to `lib`, `include` and `plugin`: when
+/// shims are enabled `bin` is a real directory, but the remaining links
+/// still track the default version.
+pub async fn current_version(base_dir: &Path) -> Option<String> {
+    for dir in ["bin", "lib", "include", "plugin"] {
+        let Ok(link) = fs::read_link(base_dir.join(dir)).await else {
+            continue;
+        };
+        tracing::debug!(link = ?link, dir, "Raw symlink path");
+        if let Some(v) = link_version(base_dir, &link) {
+            tracing::debug!(version = %v, "Extracted version from symlink");
+            return Some(v);
+        }
+    }
+    None
+}
+
+/// Extract the version from a stable link target in `base_dir`.
+///
+/// Both relative (`versions/<v>/bin`, as written on Unix) and absolute (as
+/// written on Windows) targets are understood; the component following
+/// `versions` is the version.
+pub fn link_version(base_dir: &Path, link: &Path) -> Option<String> {
+    let normalized = if link.is_absolute() {
+        link.strip_prefix(base_dir).unwrap_or(link)
+    } else {
+        link
+    };
+
+    let mut comps = normalized.components().peekable();
+    while let Some(comp) = comps.next() {
+        if let std::path::Component::Normal(name) = comp {
+            if name == "versions" {
+                if let Some(std::path::Component::Normal(ver)) = comps.peek().copied() {
+                    return Some(ver.to_string_lossy().to_string());
+                }
+                break;
+            }
+        }
+    }
+    tracing::debug!(normalized = %normalized.display(), "Could not find versions/<ver> in symlink path");
+    None
+}
+
 /// Creates or updates symlinks for a WasmEdge version installation.
 ///
 /// Creates the following symlinks in the base directory:
@@ -474,7 +537,14 @@ fn extract_zip(file: &mut std::fs::File, to: &Path) -> Result<()> {
 ///
 /// Returns an error if creating or updating symlinks fails.
 pub async fn create_version_symlinks(base_dir: &Path, version: &str) -> Result<()> {
-    let symlink_dirs = ["bin", "include", "lib", "plugin"];
+    // With shims enabled `bin` is a real directory of dispatching shims that
+    // resolve the version at run time; leave it alone and only re-point the
+    // remaining links.
+    let shims_enabled = crate::shim::is_shim_dir(&base_dir.join("bin"));
+    let symlink_dirs: Vec<&str> = ["bin", "include", "lib", "plugin"]
+        .into_iter()
+        .filter(|dir| !(shims_enabled && *dir == "bin"))
+        .collect();
 
     // Preflight: refuse *before* mutating anything if any destination is a
     // pre-existing real directory. `base_dir` is user-controlled (`--path`) and
@@ -484,7 +554,7 @@ pub async fn create_version_symlinks(base_dir: &Path, version: &str) -> Result<(
     // entry's symlink is never removed or re-pointed before a later real
     // directory triggers the error. `symlink_metadata` does not follow links,
     // so existing symlinks/files fall through to the mutation loop below.
-    for dir in symlink_dirs {
+    for &dir in &symlink_dirs {
         let symlink_path = base_dir.join(dir);
         if let Ok(meta) = fs::symlink_metadata(&symlink_path).await {
             if meta.file_type().is_dir() {
@@ -501,7 +571,7 @@ pub async fn create_version_symlinks(base_dir: &Path, version: &str) -> Result<(
         }
     }
 
-    for dir in symlink_dirs {
+    for &dir in &symlink_dirs {
         let symlink_path = base_dir.join(dir);
 
         #[cfg(unix)]
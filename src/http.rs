@@ -1,7 +1,20 @@
-use crate::constants::{DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS};
+use crate::constants::{
+    DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS, DEFAULT_RETRIES,
+    MAX_RETRY_WAIT_SECS, RETRY_BASE_DELAY_MS,
+};
 use crate::prelude::*;
-use reqwest::Client;
-use std::time::Duration;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Certificate, Client, NoProxy, Proxy, RequestBuilder, Response, StatusCode,
+};
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Environment variable naming a PEM bundle of extra root certificates,
+/// used when neither `--cacert` nor the `cacert` config key is set.
+pub const CA_CERT_ENV: &str = "SSL_CERT_FILE";
 
 /// Configuration for building HTTP clients with consistent settings.
 #[derive(Debug, Clone)]
@@ -10,6 +23,14 @@ pub struct HttpClientConfig {
     pub connect_timeout: u64,
     /// Request timeout in seconds
     pub request_timeout: u64,
+    /// How many times a failed request is retried
+    pub retries: u32,
+    /// Proxy for every request, as `scheme://[user:password@]host[:port]`;
+    /// `None` uses the `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY` variables.
+    /// Hosts listed in `NO_PROXY` are always reached directly.
+    pub proxy: Option<String>,
+    /// PEM bundle of root certificates trusted in addition to the system's
+    pub cacert: Option<PathBuf>,
 }
 
 impl Default for HttpClientConfig {
@@ -17,6 +38,9 @@ impl Default for HttpClientConfig {
         Self {
             connect_timeout: DEFAULT_CONNECT_TIMEOUT_SECS,
             request_timeout: DEFAULT_REQUEST_TIMEOUT_SECS,
+            retries: DEFAULT_RETRIES,
+            proxy: None,
+            cacert: None,
         }
     }
 }
@@ -39,18 +63,353 @@ impl HttpClientConfig {
         self
     }
 
+    /// Sets how many times a failed request is retried.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// The retry policy for requests made with the built client.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries,
+            base_delay: Duration::from_millis(RETRY_BASE_DELAY_MS),
+            max_wait: Duration::from_secs(MAX_RETRY_WAIT_SECS),
+        }
+    }
+
+    /// Sets the proxy every request goes through.
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Sets a PEM bundle of extra trusted root certificates.
+    pub fn with_cacert(mut self, cacert: Option<PathBuf>) -> Self {
+        self.cacert = cacert;
+        self
+    }
+
     /// Builds a reqwest Client with the configured settings.
     pub fn build(&self) -> Result<Client> {
-        reqwest::ClientBuilder::new()
+        let mut builder = reqwest::ClientBuilder::new()
+            .connect_timeout(Duration::from_secs(self.connect_timeout))
+            .timeout(Duration::from_secs(self.request_timeout))
+            .user_agent(user_agent())
+            .tls_certs_merge(self.root_certificates()?);
+        if let Some(proxy) = self.explicit_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        builder.build().map_err(|e| Error::HttpClientBuild {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Builds a blocking reqwest Client with the same settings, for code
+    /// that runs outside the async runtime.
+    pub fn build_blocking(&self) -> Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::ClientBuilder::new()
             .connect_timeout(Duration::from_secs(self.connect_timeout))
             .timeout(Duration::from_secs(self.request_timeout))
-            .user_agent(format!(
-                "wasmedgeup/{} (+https://github.com/WasmEdge/wasmedgeup)",
-                env!("CARGO_PKG_VERSION")
-            ))
-            .build()
-            .map_err(|e| Error::HttpClientBuild {
-                reason: e.to_string(),
-            })
+            .user_agent(user_agent())
+            .tls_certs_merge(self.root_certificates()?);
+        if let Some(proxy) = self.explicit_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        builder.build().map_err(|e| Error::HttpClientBuild {
+            reason: e.to_string(),
+        })
+    }
+
+    /// The configured proxy, bypassed for the hosts in `NO_PROXY`. Without
+    /// one reqwest falls back to the proxy environment variables itself.
+    fn explicit_proxy(&self) -> Result<Option<Proxy>> {
+        let Some(url) = &self.proxy else {
+            return Ok(None);
+        };
+        let proxy = Proxy::all(url).map_err(|e| Error::HttpClientBuild {
+            reason: format!("invalid proxy URL '{url}': {e}"),
+        })?;
+        Ok(Some(proxy.no_proxy(NoProxy::from_env())))
+    }
+
+    fn root_certificates(&self) -> Result<Vec<Certificate>> {
+        let Some(path) = &self.cacert else {
+            return Ok(Vec::new());
+        };
+        let pem = std::fs::read(path).map_err(|source| Error::Io {
+            action: "read CA certificates".to_string(),
+            path: path.display().to_string(),
+            source,
+        })?;
+        match Certificate::from_pem_bundle(&pem) {
+            Ok(certs) if !certs.is_empty() => Ok(certs),
+            Ok(_) => Err(Error::HttpClientBuild {
+                reason: format!("no PEM certificates found in {}", path.display()),
+            }),
+            Err(e) => Err(Error::HttpClientBuild {
+                reason: format!("invalid CA certificates in {}: {e}", path.display()),
+            }),
+        }
+    }
+}
+
+fn user_agent() -> String {
+    format!(
+        "wasmedgeup/{} (+https://github.com/WasmEdge/wasmedgeup)",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Retries transient network failures with exponential backoff and jitter.
+///
+/// Connection errors, timeouts, `408`, `429` and `5xx` responses are
+/// retried. A `Retry-After` header, or GitHub's `x-ratelimit-reset` on an
+/// exhausted rate limit, replaces the backoff delay; when the server asks
+/// for a longer wait than `max_wait` the response is returned as is rather
+/// than stalling the command.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Extra attempts after the first one
+    pub retries: u32,
+    /// Delay before the first retry; doubled for each further one
+    pub base_delay: Duration,
+    /// Longest delay waited between two attempts
+    pub max_wait: Duration,
+}
+
+impl RetryPolicy {
+    /// Send `request`, retrying it while it fails transiently. Requests
+    /// whose body cannot be cloned are sent once.
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let Some(this_try) = request.try_clone() else {
+                return request.send().await;
+            };
+            let result = this_try.send().await;
+            let wait = match &result {
+                Ok(response) => self.response_wait(response, attempt),
+                Err(e) if is_transient(e) => Some(self.backoff(attempt)),
+                Err(_) => None,
+            };
+            match wait {
+                Some(wait) if attempt < self.retries => {
+                    match &result {
+                        Ok(response) => {
+                            tracing::warn!(status = %response.status(), url = %response.url(), ?wait, "Request failed; retrying")
+                        }
+                        Err(e) => tracing::warn!(error = %e, ?wait, "Request failed; retrying"),
+                    }
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                _ => return result,
+            }
+        }
+    }
+
+    /// Run `op` until it succeeds, fails with an error `is_transient` does
+    /// not accept, or the retries are used up.
+    pub async fn retry<T, F, Fut>(
+        &self,
+        is_transient: impl Fn(&Error) -> bool,
+        mut op: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    let wait = self.backoff(attempt);
+                    tracing::warn!(error = %e, ?wait, "Operation failed; retrying");
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Exponential backoff for retry number `attempt` (from 0), capped at
+    /// `max_wait`, plus up to 50% random jitter so concurrent clients do
+    /// not retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_wait);
+        let jitter = RandomState::new().hash_one(attempt) % 1000;
+        delay + delay.mul_f64(jitter as f64 / 2000.0)
+    }
+
+    /// How long to wait before retrying after `response`, or `None` if it
+    /// should not be retried.
+    fn response_wait(&self, response: &Response, attempt: u32) -> Option<Duration> {
+        let status = response.status();
+        let headers = response.headers();
+        let rate_limited = is_rate_limited(status, headers);
+        let retryable = rate_limited
+            || matches!(
+                status,
+                StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS
+            )
+            || status.is_server_error();
+        if !retryable {
+            return None;
+        }
+        match server_wait(headers) {
+            Some(wait) if wait > self.max_wait => {
+                tracing::debug!(?wait, "Server asked to wait longer than the retry limit");
+                None
+            }
+            Some(wait) => Some(wait),
+            None if rate_limited => None,
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Whether a `403` or `429` response reports an exhausted rate limit, as
+/// GitHub does with `x-ratelimit-remaining: 0` or `Retry-After`.
+pub(crate) fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) && (headers.contains_key(RETRY_AFTER)
+        || headers
+            .get("x-ratelimit-remaining")
+            .is_some_and(|v| v.as_bytes() == b"0"))
+}
+
+/// The wait the server asked for via `Retry-After` (in seconds) or, on an
+/// exhausted GitHub rate limit, until `x-ratelimit-reset`.
+pub(crate) fn server_wait(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+    if let Some(secs) = header(RETRY_AFTER.as_str()) {
+        return Some(Duration::from_secs(secs));
+    }
+    if headers
+        .get("x-ratelimit-remaining")
+        .is_some_and(|v| v.as_bytes() == b"0")
+    {
+        let reset = UNIX_EPOCH + Duration::from_secs(header("x-ratelimit-reset")?);
+        return Some(reset.duration_since(SystemTime::now()).unwrap_or_default());
+    }
+    None
+}
+
+/// Whether a request error is worth retrying: the connection could not be
+/// made, timed out, or broke while the response was read.
+pub fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout() || e.is_request() || e.is_body()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_wait: Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_bounded_jitter() {
+        let p = policy();
+        for (attempt, base) in [(0, 100), (1, 200), (2, 400), (3, 800), (10, 2000)] {
+            let delay = p.backoff(attempt);
+            let base = Duration::from_millis(base);
+            assert!(
+                delay >= base && delay <= base.mul_f64(1.5),
+                "{attempt}: {delay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn server_wait_prefers_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(server_wait(&headers), None);
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        assert_eq!(server_wait(&headers), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn server_wait_uses_rate_limit_reset() {
+        let reset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 30;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from(reset));
+        let wait = server_wait(&headers).unwrap();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        assert_eq!(server_wait(&headers), None);
+    }
+
+    /// Serve `503 Service Unavailable` for the first `failures` requests,
+    /// then `200 OK`; returns the URL and the number of requests served.
+    fn flaky_server(failures: usize) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicUsize::new(0));
+        let count = Arc::clone(&served);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let status = if count.fetch_add(1, Ordering::SeqCst) < failures {
+                    "503 Service Unavailable\r\nRetry-After: 0"
+                } else {
+                    "200 OK"
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+            }
+        });
+        (url, served)
+    }
+
+    #[tokio::test]
+    async fn send_retries_server_errors_until_success() {
+        let (url, served) = flaky_server(2);
+        let client = Client::new();
+        let response = policy().send(client.get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(served.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn send_returns_the_last_response_when_retries_run_out() {
+        let (url, served) = flaky_server(usize::MAX);
+        let client = Client::new();
+        let p = RetryPolicy {
+            retries: 1,
+            ..policy()
+        };
+        let response = p.send(client.get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(served.load(Ordering::SeqCst), 2);
     }
 }
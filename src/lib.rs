@@ -1,11 +1,23 @@
 pub mod api;
+pub mod bundle;
+pub mod cache;
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod constants;
 pub mod error;
 pub mod fs;
 pub mod http;
+pub mod lock;
+pub mod manifest;
+pub mod metadata_cache;
+pub mod output;
 pub mod prelude;
+pub mod self_update;
 pub mod shell_utils;
+pub mod shim;
+pub mod signature;
 pub mod system;
 pub mod target;
+pub mod toolchain_file;
+pub mod transaction;
@@ -0,0 +1,197 @@
+//! Advisory lock serializing commands that change an install root.
+//!
+//! Concurrent runs against one root (parallel CI jobs sharing a volume, say)
+//! would otherwise interleave copies, link swaps and deletions. Every
+//! mutating command holds an exclusive lock on `<root>/.wasmedgeup.lock`,
+//! which records the holder's PID for the error a second run reports.
+//! Read-only commands never take it.
+//!
+//! The lock is reentrant within a process, so `update` can hold it while
+//! running `install` and `plugin install`, which take it themselves.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use fs4::{FileExt, TryLockError};
+
+use crate::prelude::*;
+
+/// Name of the lock file in the install root.
+pub const LOCK_FILE_NAME: &str = ".wasmedgeup.lock";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Roots this process holds the lock on, with how many guards share it.
+static HELD: Mutex<Vec<Held>> = Mutex::new(Vec::new());
+
+struct Held {
+    root: PathBuf,
+    /// Closing the file releases the lock.
+    _file: File,
+    guards: usize,
+}
+
+/// Holds the lock on an install root until dropped.
+#[derive(Debug)]
+pub struct InstallLock {
+    /// `None` when the root did not exist, so there was nothing to lock.
+    root: Option<PathBuf>,
+}
+
+impl InstallLock {
+    /// Lock `root`, waiting up to `wait` for another process to release
+    /// it. A root that does not exist yet is not locked.
+    pub async fn acquire(root: &Path, wait: Duration) -> Result<Self> {
+        let Ok(root) = root.canonicalize() else {
+            return Ok(Self { root: None });
+        };
+        if let Some(held) = held().iter_mut().find(|h| h.root == root) {
+            held.guards += 1;
+            return Ok(Self { root: Some(root) });
+        }
+
+        let path = root.join(LOCK_FILE_NAME);
+        let io_err = |action: &str| {
+            let action = action.to_string();
+            let path = path.display().to_string();
+            move |source| Error::Io {
+                action,
+                path,
+                source,
+            }
+        };
+        let deadline = Instant::now() + wait;
+        let mut waiting = false;
+        let file = loop {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(io_err("open lock file"))?;
+            match FileExt::try_lock(&file) {
+                // The previous holder may have removed the root, and the
+                // file with it, while we waited on it.
+                Ok(()) if is_same_file(&file, &path) => break file,
+                Ok(()) => continue,
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(e)) => return Err(io_err("lock")(e)),
+            }
+            let holder = holder(&path);
+            if Instant::now() >= deadline {
+                return Err(Error::InstallRootLocked {
+                    root: root.display().to_string(),
+                    holder,
+                });
+            }
+            if !waiting {
+                tracing::info!(root = %root.display(), %holder, "Waiting for another wasmedgeup process");
+                waiting = true;
+            }
+            tokio::time::sleep(POLL_INTERVAL.min(deadline - Instant::now())).await;
+        };
+
+        let record_pid = |mut file: &File| -> std::io::Result<()> {
+            file.set_len(0)?;
+            file.rewind()?;
+            write!(file, "{}", std::process::id())?;
+            file.flush()
+        };
+        record_pid(&file).map_err(io_err("write lock file"))?;
+        tracing::debug!(lock = %path.display(), "Locked install root");
+        held().push(Held {
+            root: root.clone(),
+            _file: file,
+            guards: 1,
+        });
+        Ok(Self { root: Some(root) })
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let Some(root) = &self.root else {
+            return;
+        };
+        let mut held = held();
+        if let Some(i) = held.iter().position(|h| h.root == *root) {
+            held[i].guards -= 1;
+            if held[i].guards == 0 {
+                held.swap_remove(i);
+                tracing::debug!(root = %root.display(), "Unlocked install root");
+            }
+        }
+    }
+}
+
+fn held() -> std::sync::MutexGuard<'static, Vec<Held>> {
+    HELD.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// "PID 1234", as recorded in the lock file by its holder.
+fn holder(path: &Path) -> String {
+    let mut content = String::new();
+    let pid = File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut content))
+        .ok()
+        .and_then(|_| content.trim().parse::<u32>().ok());
+    match pid {
+        Some(pid) => format!("PID {pid}"),
+        None => "PID unknown".to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), std::fs::metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(windows)]
+fn is_same_file(_file: &File, path: &Path) -> bool {
+    // An open file cannot be removed on Windows; it only has to exist.
+    path.exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn lock_is_reentrant_and_released_on_drop() {
+        let root = tempfile::tempdir().unwrap();
+        let outer = InstallLock::acquire(root.path(), Duration::ZERO)
+            .await
+            .unwrap();
+        let inner = InstallLock::acquire(root.path(), Duration::ZERO)
+            .await
+            .unwrap();
+        let pid = std::fs::read_to_string(root.path().join(LOCK_FILE_NAME)).unwrap();
+        assert_eq!(pid, std::process::id().to_string());
+
+        let other = File::open(root.path().join(LOCK_FILE_NAME)).unwrap();
+        drop(inner);
+        assert!(matches!(
+            FileExt::try_lock(&other),
+            Err(TryLockError::WouldBlock)
+        ));
+        drop(outer);
+        FileExt::try_lock(&other).unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_root_is_not_locked() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("missing");
+        let lock = InstallLock::acquire(&root, Duration::ZERO).await.unwrap();
+        assert!(lock.root.is_none());
+        assert!(!root.exists());
+    }
+}
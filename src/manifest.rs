@@ -0,0 +1,288 @@
+//! Per-version record of what wasmedgeup installed and where it came from.
+//!
+//! Every runtime version gets a `versions/<version>/.wasmedgeup-manifest.json`
+//! listing the archive it was unpacked from (URL or local path, SHA256), the
+//! files copied out of it, and each plugin installed into the version along
+//! with its own files. `remove` and `plugin remove` delete exactly the
+//! recorded files rather than guessing from file names, and
+//! `plugin list --installed` reports the recorded plugin versions.
+//!
+//! Versions installed before manifests existed have none; commands fall back
+//! to scanning the version directory for those.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::commands::plugin::utils::normalize_name;
+use crate::prelude::*;
+
+/// Name of the manifest file in a version directory.
+pub const MANIFEST_FILE_NAME: &str = ".wasmedgeup-manifest.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    /// The runtime version, e.g. `0.14.1`.
+    pub version: String,
+    /// Where the runtime archive came from: a download URL or a local path.
+    /// `None` when the runtime predates the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Hex SHA256 of the runtime archive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Runtime files, relative to the version directory.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    #[serde(default)]
+    pub plugins: Vec<PluginRecord>,
+}
+
+/// A plugin installed into a runtime version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginRecord {
+    pub name: String,
+    pub version: String,
+    /// Platform key of the archive, e.g. `manylinux_2_28_x86_64`.
+    pub platform: String,
+    /// Where the plugin archive came from: a download URL or a local path.
+    pub source: String,
+    /// Hex SHA256 of the plugin archive.
+    pub sha256: String,
+    /// Plugin files, relative to the version directory.
+    pub files: Vec<PathBuf>,
+}
+
+impl InstallManifest {
+    /// An empty manifest for `version`.
+    pub fn new(version: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Read the manifest of the version installed in `version_dir`, or
+    /// `None` if it has none. An unreadable manifest is logged and treated
+    /// as missing, so commands fall back to scanning.
+    pub fn load(version_dir: &Path) -> Option<Self> {
+        let path = version_dir.join(MANIFEST_FILE_NAME);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!(error = %e, path = %path.display(), "Failed to read install manifest");
+                return None;
+            }
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                tracing::warn!(error = %e, path = %path.display(), "Ignoring malformed install manifest");
+                None
+            }
+        }
+    }
+
+    /// Write the manifest into `version_dir`, atomically.
+    pub fn save(&self, version_dir: &Path) -> Result<()> {
+        let path = version_dir.join(MANIFEST_FILE_NAME);
+        let io_err = |source| Error::Io {
+            action: "write install manifest".to_string(),
+            path: path.display().to_string(),
+            source,
+        };
+        let tmp = tempfile::NamedTempFile::new_in(version_dir).map_err(io_err)?;
+        serde_json::to_writer_pretty(tmp.as_file(), self).context(JsonSnafu {
+            resource: "install manifest",
+        })?;
+        tmp.persist(&path).map_err(|e| io_err(e.error))?;
+        tracing::debug!(path = %path.display(), "Wrote install manifest");
+        Ok(())
+    }
+
+    /// The record of plugin `name`, matched exactly or by normalized name.
+    pub fn plugin(&self, name: &str) -> Option<&PluginRecord> {
+        self.plugin_index(name).map(|i| &self.plugins[i])
+    }
+
+    /// Record `plugin`, replacing an earlier record of the same plugin.
+    ///
+    /// Variants of one plugin (e.g. `wasi_nn-ggml` and `wasi_nn-openvino`)
+    /// install the same file, so the last one installed owns it: its files
+    /// are taken away from every other record, and records left without
+    /// files are dropped.
+    pub fn add_plugin(&mut self, plugin: PluginRecord) {
+        for other in &mut self.plugins {
+            other.files.retain(|f| !plugin.files.contains(f));
+        }
+        self.plugins.retain(|p| !p.files.is_empty());
+        match self.plugin_index(&plugin.name) {
+            Some(i) => self.plugins[i] = plugin,
+            None => self.plugins.push(plugin),
+        }
+    }
+
+    /// Drop the record of plugin `name`, returning it.
+    pub fn remove_plugin(&mut self, name: &str) -> Option<PluginRecord> {
+        self.plugin_index(name).map(|i| self.plugins.remove(i))
+    }
+
+    fn plugin_index(&self, name: &str) -> Option<usize> {
+        self.plugins
+            .iter()
+            .position(|p| p.name == name)
+            .or_else(|| {
+                let name = normalize_name(name);
+                self.plugins
+                    .iter()
+                    .position(|p| normalize_name(&p.name) == name)
+            })
+    }
+
+    /// Every recorded file, runtime and plugins, relative to the version
+    /// directory.
+    pub fn all_files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files
+            .iter()
+            .chain(self.plugins.iter().flat_map(|p| &p.files))
+    }
+}
+
+/// Remove `files` (relative to `version_dir`), then any directory they
+/// leave empty. Files already gone are skipped; other failures are logged
+/// and counted, and the number of files that could not be removed is
+/// returned.
+pub fn remove_files<'a>(version_dir: &Path, files: impl IntoIterator<Item = &'a PathBuf>) -> usize {
+    let mut failed = 0;
+    let mut parents: Vec<PathBuf> = Vec::new();
+    for file in files {
+        let path = version_dir.join(file);
+        match std::fs::remove_file(&path) {
+            Ok(()) => tracing::debug!(path = %path.display(), "Removed installed file"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(error = %e, path = %path.display(), "Failed to remove installed file");
+                failed += 1;
+            }
+        }
+        parents.extend(
+            path.ancestors()
+                .skip(1)
+                .take_while(|p| p.starts_with(version_dir) && *p != version_dir)
+                .map(Path::to_path_buf),
+        );
+    }
+    // Deepest first, so a parent is only tried once its children are gone.
+    parents.sort_by(|a, b| {
+        (b.components().count())
+            .cmp(&a.components().count())
+            .then_with(|| a.cmp(b))
+    });
+    parents.dedup();
+    for dir in parents {
+        // Fails harmlessly while the directory still holds other files.
+        let _ = std::fs::remove_dir(&dir);
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, files: &[&str]) -> PluginRecord {
+        PluginRecord {
+            name: name.to_string(),
+            version: "0.14.1".to_string(),
+            platform: "manylinux_2_28_x86_64".to_string(),
+            source: format!("https://example.com/{name}.tar.gz"),
+            sha256: "abcd".to_string(),
+            files: files.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_and_tracks_plugins_by_normalized_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(InstallManifest::load(dir.path()), None);
+
+        let mut manifest = InstallManifest::new("0.14.1");
+        manifest.files = vec![PathBuf::from("bin/wasmedge")];
+        manifest.add_plugin(record(
+            "wasi_nn-ggml",
+            &["plugin/libwasmedgePluginWasiNN.so"],
+        ));
+        manifest.add_plugin(record("wasi_logging", &["plugin/libold.so"]));
+        manifest.add_plugin(record(
+            "wasi_logging",
+            &["plugin/libwasmedgePluginWasiLogging.so"],
+        ));
+        manifest.save(dir.path()).unwrap();
+
+        let loaded = InstallManifest::load(dir.path()).unwrap();
+        assert_eq!(loaded, manifest);
+        assert_eq!(loaded.plugins.len(), 2);
+        assert_eq!(loaded.plugin("wasi-nn-ggml").unwrap().name, "wasi_nn-ggml");
+        assert_eq!(loaded.all_files().count(), 3);
+
+        std::fs::write(dir.path().join(MANIFEST_FILE_NAME), "{").unwrap();
+        assert_eq!(InstallManifest::load(dir.path()), None);
+    }
+
+    #[test]
+    fn variants_sharing_a_file_hand_it_to_the_last_installed() {
+        let mut manifest = InstallManifest::new("0.14.1");
+        manifest.add_plugin(record(
+            "wasi_nn-ggml",
+            &["plugin/libwasmedgePluginWasiNN.so"],
+        ));
+        manifest.add_plugin(record(
+            "wasi_logging",
+            &[
+                "plugin/libwasmedgePluginWasiLogging.so",
+                "plugin/libwasmedgePluginWasiNN.so",
+            ],
+        ));
+        manifest.add_plugin(record(
+            "wasi_nn-openvino",
+            &["plugin/libwasmedgePluginWasiNN.so"],
+        ));
+
+        assert!(manifest.plugin("wasi_nn-ggml").is_none());
+        assert_eq!(
+            manifest.plugin("wasi_logging").unwrap().files,
+            [PathBuf::from("plugin/libwasmedgePluginWasiLogging.so")]
+        );
+        assert_eq!(
+            manifest.plugin("wasi_nn-openvino").unwrap().files,
+            [PathBuf::from("plugin/libwasmedgePluginWasiNN.so")]
+        );
+        assert_eq!(manifest.plugins.len(), 2);
+    }
+
+    #[test]
+    fn removes_recorded_files_and_emptied_dirs_only() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "lib/libwasmedge.so",
+            "include/wasmedge/wasmedge.h",
+            "lib/user.txt",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let files = [
+            PathBuf::from("lib/libwasmedge.so"),
+            PathBuf::from("include/wasmedge/wasmedge.h"),
+            PathBuf::from("bin/already-gone"),
+        ];
+
+        assert_eq!(remove_files(dir.path(), &files), 0);
+        assert!(!dir.path().join("include").exists());
+        assert!(dir.path().join("lib/user.txt").exists());
+        assert!(dir.path().exists());
+    }
+}
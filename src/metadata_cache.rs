@@ -0,0 +1,232 @@
+//! On-disk cache of release metadata: the tags listed by the git remote
+//! and the plugin assets of each release.
+//!
+//! Entries live under `<user cache dir>/wasmedgeup/metadata` as
+//! `releases.json` and `assets/<tag>.json`. Each records the URL it was
+//! fetched from, so switching to a mirror never serves the upstream's
+//! listing, and when it was fetched: an entry older than the TTL is
+//! fetched again. `--refresh` ignores entries fetched before the command
+//! started and `--offline` uses them whatever their age, failing rather
+//! than going to the network when there are none.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::prelude::*;
+
+const RELEASES_FILE: &str = "releases.json";
+const ASSETS_DIR: &str = "assets";
+
+/// How cached entries are used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetadataMode {
+    /// Use entries younger than the TTL.
+    #[default]
+    Normal,
+    /// Only use entries fetched by this command.
+    Refresh,
+    /// Use any entry and never fetch.
+    Offline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataCache {
+    root: PathBuf,
+    ttl: Duration,
+    mode: MetadataMode,
+    started: u128,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    source: String,
+    /// Milliseconds since the Unix epoch.
+    fetched_at: u128,
+    data: T,
+}
+
+impl MetadataCache {
+    pub fn new(root: impl Into<PathBuf>, ttl: Duration, mode: MetadataMode) -> Self {
+        Self {
+            root: root.into(),
+            ttl,
+            mode,
+            started: now_millis(),
+        }
+    }
+
+    /// The cache under the user's cache directory, if there is one.
+    pub fn default_location(ttl: Duration, mode: MetadataMode) -> Option<Self> {
+        dirs::cache_dir().map(|d| Self::new(d.join("wasmedgeup").join("metadata"), ttl, mode))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.mode == MetadataMode::Offline
+    }
+
+    /// Release tags listed by the git remote at `git_url`.
+    pub fn releases(&self, git_url: &str) -> Result<Option<Vec<String>>> {
+        self.lookup(&self.root.join(RELEASES_FILE), git_url, "release list")
+    }
+
+    pub fn store_releases(&self, git_url: &str, tags: &[String]) {
+        self.store(&self.root.join(RELEASES_FILE), git_url, tags);
+    }
+
+    /// Plugin assets of release `tag`, fetched from `url`.
+    pub fn assets<T: DeserializeOwned>(&self, tag: &str, url: &str) -> Result<Option<T>> {
+        match self.assets_path(tag) {
+            Some(path) => self.lookup(&path, url, "plugin asset list"),
+            None if self.is_offline() => Err(Error::OfflineMetadataMissing {
+                what: "plugin asset list".to_string(),
+                source_url: url.to_string(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn store_assets<T: Serialize>(&self, tag: &str, url: &str, assets: &T) {
+        if let Some(path) = self.assets_path(tag) {
+            self.store(&path, url, assets);
+        }
+    }
+
+    /// `None` for tags that are not a plain file name; those are never
+    /// cached.
+    fn assets_path(&self, tag: &str) -> Option<PathBuf> {
+        let plain = !tag.is_empty() && !tag.starts_with('.') && !tag.contains(['/', '\\', ':']);
+        plain.then(|| self.root.join(ASSETS_DIR).join(format!("{tag}.json")))
+    }
+
+    fn lookup<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        source: &str,
+        what: &str,
+    ) -> Result<Option<T>> {
+        let entry = std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Entry<T>>(&bytes).ok())
+            .filter(|entry| entry.source == source);
+        let usable = entry.filter(|entry| match self.mode {
+            MetadataMode::Normal => {
+                now_millis().saturating_sub(entry.fetched_at) < self.ttl.as_millis()
+            }
+            MetadataMode::Refresh => entry.fetched_at >= self.started,
+            MetadataMode::Offline => true,
+        });
+        match usable {
+            Some(entry) => {
+                tracing::debug!(path = %path.display(), "Using cached {what}");
+                Ok(Some(entry.data))
+            }
+            None if self.is_offline() => Err(Error::OfflineMetadataMissing {
+                what: what.to_string(),
+                source_url: source.to_string(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Write an entry, atomically. Failures are logged: the cache only saves
+    /// work and must never fail the command.
+    fn store<T: Serialize + ?Sized>(&self, path: &Path, source: &str, data: &T) {
+        let entry = Entry {
+            source: source.to_string(),
+            fetched_at: now_millis(),
+            data,
+        };
+        let write = || -> std::io::Result<()> {
+            let dir = path.parent().expect("metadata entries have a parent");
+            std::fs::create_dir_all(dir)?;
+            let tmp = tempfile::NamedTempFile::new_in(dir)?;
+            serde_json::to_writer(tmp.as_file(), &entry)?;
+            tmp.persist(path)?;
+            Ok(())
+        };
+        if let Err(e) = write() {
+            tracing::debug!(error = %e, path = %path.display(), "Failed to cache release metadata");
+        }
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://github.com/WasmEdge/WasmEdge.git";
+
+    fn tags() -> Vec<String> {
+        vec!["0.15.0".to_string(), "0.14.1".to_string()]
+    }
+
+    #[test]
+    fn fresh_entries_are_served_for_their_source_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(dir.path(), Duration::from_secs(60), MetadataMode::Normal);
+        assert_eq!(cache.releases(URL).unwrap(), None);
+        cache.store_releases(URL, &tags());
+        assert_eq!(cache.releases(URL).unwrap(), Some(tags()));
+        assert_eq!(
+            cache.releases("https://mirror.local/WasmEdge.git").unwrap(),
+            None
+        );
+
+        let expired = MetadataCache::new(dir.path(), Duration::ZERO, MetadataMode::Normal);
+        assert_eq!(expired.releases(URL).unwrap(), None);
+    }
+
+    #[test]
+    fn offline_uses_stale_entries_and_fails_without_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let offline = MetadataCache::new(dir.path(), Duration::ZERO, MetadataMode::Offline);
+        let err = offline.releases(URL).unwrap_err();
+        assert!(
+            matches!(err, Error::OfflineMetadataMissing { .. }),
+            "{err:?}"
+        );
+
+        offline.store_releases(URL, &tags());
+        assert_eq!(offline.releases(URL).unwrap(), Some(tags()));
+    }
+
+    #[test]
+    fn refresh_ignores_entries_from_earlier_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RELEASES_FILE);
+        let old = Entry {
+            source: URL.to_string(),
+            fetched_at: now_millis() - 10_000,
+            data: tags(),
+        };
+        std::fs::write(&path, serde_json::to_vec(&old).unwrap()).unwrap();
+
+        let refresh =
+            MetadataCache::new(dir.path(), Duration::from_secs(3600), MetadataMode::Refresh);
+        assert_eq!(refresh.releases(URL).unwrap(), None);
+        refresh.store_releases(URL, &tags());
+        assert_eq!(refresh.releases(URL).unwrap(), Some(tags()));
+    }
+
+    #[test]
+    fn unusual_tags_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(dir.path(), Duration::from_secs(60), MetadataMode::Normal);
+        cache.store_assets("../escape", URL, &vec!["a"]);
+        assert!(!dir.path().join("escape.json").exists());
+        assert_eq!(cache.assets::<Vec<String>>("../escape", URL).unwrap(), None);
+    }
+}
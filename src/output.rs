@@ -0,0 +1,35 @@
+//! Output formats for command results.
+//!
+//! Commands print human-oriented text by default. With `--output json` they
+//! instead print exactly one JSON document to stdout, with stable field
+//! names, so scripts do not have to scrape tables. Logs and progress bars go
+//! to stderr in that mode.
+
+use serde::Serialize;
+use snafu::ResultExt;
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single JSON document on stdout
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == Self::Json
+    }
+}
+
+/// Print `value` as a single-line JSON document to stdout.
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    let json = serde_json::to_string(value).context(JsonSnafu {
+        resource: "command output",
+    })?;
+    println!("{json}");
+    Ok(())
+}
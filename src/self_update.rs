@@ -0,0 +1,266 @@
+//! Updating and removing the wasmedgeup binary itself.
+//!
+//! Releases are published on GitHub as `wasmedgeup-<target triple>.tgz`,
+//! each holding `wasmedgeup-<target triple>/wasmedgeup`. The expected
+//! checksum comes from a `SHA256SUM` asset when the release has one, else
+//! from the `sha256:` digest GitHub reports for the asset.
+
+use std::path::{Path, PathBuf};
+
+use semver::Version;
+use serde::Deserialize;
+use snafu::ResultExt;
+use url::Url;
+
+use crate::api::{find_checksum, WasmEdgeApiClient};
+use crate::constants::CHECKSUM_FILE_NAME;
+use crate::prelude::*;
+
+#[derive(Debug, Deserialize)]
+struct ReleaseRepr {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<AssetRepr>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AssetRepr {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// A published wasmedgeup release.
+#[derive(Debug)]
+pub struct SelfRelease {
+    pub version: Version,
+    assets: Vec<AssetRepr>,
+}
+
+/// The release archive for one target, with where to download it from.
+#[derive(Debug, Clone)]
+pub struct SelfAsset {
+    pub name: String,
+    pub url: Url,
+    /// Digest GitHub computed for the asset, if reported.
+    digest: Option<String>,
+}
+
+/// Archive name of the wasmedgeup release for `triple`.
+pub fn asset_name(triple: &str) -> String {
+    format!("wasmedgeup-{triple}.tgz")
+}
+
+impl SelfRelease {
+    /// Fetch the latest release from the client's `self_update_api`.
+    pub async fn latest(client: &WasmEdgeApiClient) -> Result<Self> {
+        let url = client.endpoints.self_update_url();
+        tracing::debug!(%url, "Fetching latest wasmedgeup release");
+        let body = client
+            .github_api_get(url, "wasmedgeup release metadata")
+            .await?
+            .error_for_status()
+            .context(RequestSnafu {
+                resource: "wasmedgeup release metadata",
+            })?
+            .text()
+            .await
+            .context(RequestSnafu {
+                resource: "wasmedgeup release metadata",
+            })?;
+        Self::parse(&body)
+    }
+
+    fn parse(body: &str) -> Result<Self> {
+        let repr: ReleaseRepr = serde_json::from_str(body).context(JsonSnafu {
+            resource: "wasmedgeup release metadata",
+        })?;
+        let tag = repr.tag_name.trim_start_matches('v');
+        Ok(Self {
+            version: Version::parse(tag).context(SemVerSnafu {})?,
+            assets: repr.assets,
+        })
+    }
+
+    /// The release archive for `triple`.
+    pub fn asset(&self, triple: &str) -> Result<SelfAsset> {
+        let name = asset_name(triple);
+        let asset =
+            self.assets
+                .iter()
+                .find(|a| a.name == name)
+                .ok_or(Error::ReleaseAssetNotFound {
+                    version: self.version.to_string(),
+                    asset: name,
+                })?;
+        Ok(SelfAsset {
+            name: asset.name.clone(),
+            url: Url::parse(&asset.browser_download_url).context(UrlSnafu {})?,
+            digest: asset.digest.clone(),
+        })
+    }
+
+    /// Expected SHA256 of `asset`: its line in the release's `SHA256SUM`,
+    /// else the digest GitHub reports for it.
+    pub async fn checksum(&self, client: &WasmEdgeApiClient, asset: &SelfAsset) -> Result<String> {
+        if let Some(sums) = self.assets.iter().find(|a| a.name == CHECKSUM_FILE_NAME) {
+            let content = client
+                .send(
+                    client
+                        .http_client()?
+                        .get(sums.browser_download_url.as_str()),
+                )
+                .await
+                .and_then(|r| r.error_for_status())
+                .context(RequestSnafu {
+                    resource: "checksums",
+                })?
+                .text()
+                .await
+                .context(RequestSnafu {
+                    resource: "checksums",
+                })?;
+            if let Some(sha) = find_checksum(&content, &asset.name) {
+                return Ok(sha.to_string());
+            }
+        }
+        asset
+            .digest
+            .as_deref()
+            .and_then(|d| d.strip_prefix("sha256:"))
+            .map(str::to_string)
+            .ok_or(Error::ChecksumNotFound {
+                version: self.version.to_string(),
+                asset: asset.name.clone(),
+            })
+    }
+}
+
+/// The binary to replace or remove: the running executable, with symlinks
+/// resolved so a link on PATH keeps pointing at the updated file.
+pub fn current_exe() -> Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    Ok(std::fs::canonicalize(&exe).unwrap_or(exe))
+}
+
+/// Atomically replace `exe` with the binary at `new`.
+///
+/// The new binary is copied next to `exe` and renamed over it, so a failure
+/// leaves the old binary in place and nothing ever sees a partial file.
+/// Running processes keep using the old binary until they exit.
+#[cfg(unix)]
+pub fn replace_exe(new: &Path, exe: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = exe.parent().unwrap_or(Path::new("."));
+    let io_err = |action: &str, path: &Path| {
+        let action = action.to_string();
+        let path = path.display().to_string();
+        move |source| Error::Io {
+            action,
+            path,
+            source,
+        }
+    };
+    let tmp = tempfile::Builder::new()
+        .prefix(".wasmedgeup-update")
+        .tempfile_in(dir)
+        .map_err(io_err("stage new wasmedgeup binary", dir))?;
+    std::fs::copy(new, tmp.path()).map_err(io_err("stage new wasmedgeup binary", tmp.path()))?;
+    std::fs::set_permissions(tmp.path(), std::fs::Permissions::from_mode(0o755))
+        .map_err(io_err("set permissions", tmp.path()))?;
+    tmp.persist(exe).map_err(|e| Error::Io {
+        action: "replace wasmedgeup binary".to_string(),
+        path: exe.display().to_string(),
+        source: e.error,
+    })?;
+    Ok(())
+}
+
+/// Remove the binary at `exe`.
+///
+/// Windows cannot delete a running executable, so there the caller is
+/// told to delete it once wasmedgeup has exited; returns whether the file
+/// was removed.
+pub fn remove_exe(exe: &Path) -> Result<bool> {
+    if cfg!(windows) {
+        return Ok(false);
+    }
+    std::fs::remove_file(exe).map_err(|source| Error::Io {
+        action: "remove wasmedgeup binary".to_string(),
+        path: exe.display().to_string(),
+        source,
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIPLE: &str = "x86_64-unknown-linux-gnu";
+
+    fn release(assets: &str) -> SelfRelease {
+        SelfRelease::parse(&format!(
+            r#"{{"tag_name": "v0.3.0", "assets": [{assets}]}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_release_and_picks_asset() {
+        let r = release(
+            r#"{"name": "wasmedgeup-x86_64-unknown-linux-gnu.tgz",
+                "browser_download_url": "https://example.com/a.tgz",
+                "digest": "sha256:abc123"},
+               {"name": "wasmedgeup-aarch64-apple-darwin.tgz",
+                "browser_download_url": "https://example.com/b.tgz"}"#,
+        );
+        assert_eq!(r.version, Version::new(0, 3, 0));
+        let asset = r.asset(TRIPLE).unwrap();
+        assert_eq!(asset.url.as_str(), "https://example.com/a.tgz");
+        assert_eq!(asset.digest.as_deref(), Some("sha256:abc123"));
+
+        let err = r.asset("riscv64gc-unknown-linux-gnu").unwrap_err();
+        assert!(matches!(err, Error::ReleaseAssetNotFound { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn checksum_falls_back_to_digest() {
+        let client = WasmEdgeApiClient::default();
+        let r = release(
+            r#"{"name": "wasmedgeup-x86_64-unknown-linux-gnu.tgz",
+                "browser_download_url": "https://example.com/a.tgz",
+                "digest": "sha256:abc123"}"#,
+        );
+        let asset = r.asset(TRIPLE).unwrap();
+        assert_eq!(r.checksum(&client, &asset).await.unwrap(), "abc123");
+
+        let r = release(
+            r#"{"name": "wasmedgeup-x86_64-unknown-linux-gnu.tgz",
+                "browser_download_url": "https://example.com/a.tgz"}"#,
+        );
+        let asset = r.asset(TRIPLE).unwrap();
+        let err = r.checksum(&client, &asset).await.unwrap_err();
+        assert!(matches!(err, Error::ChecksumNotFound { .. }), "{err:?}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replaces_exe_in_place() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("wasmedgeup");
+        let new = dir.path().join("new");
+        std::fs::write(&exe, "old").unwrap();
+        std::fs::write(&new, "new").unwrap();
+
+        replace_exe(&new, &exe).unwrap();
+        assert_eq!(std::fs::read_to_string(&exe).unwrap(), "new");
+        let mode = std::fs::metadata(&exe).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
@@ -1,9 +1,11 @@
 #[cfg(unix)]
 mod unix;
 #[cfg(unix)]
-pub use unix::{get_available_shells, setup_path, uninstall_path};
+pub use unix::{
+    get_available_shells, setup_files, setup_path, setup_status, uninstall_path, SetupStatus,
+};
 
 #[cfg(windows)]
 mod windows;
 #[cfg(windows)]
-pub use windows::{setup_path, uninstall_path};
+pub use windows::{setup_files, setup_path, uninstall_path};
@@ -61,6 +61,17 @@ pub fn setup_path(install_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Files [`setup_path`] may create or change for `install_dir`.
+pub fn setup_files(install_dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for shell in get_available_shells() {
+        files.push(install_dir.join(shell.env_script().name));
+        files.extend(shell.effective_rc_files());
+    }
+    files.dedup();
+    files
+}
+
 pub fn uninstall_path(install_dir: &Path) -> Result<()> {
     for shell in get_available_shells() {
         let source_line = shell.source_line(install_dir);
@@ -110,6 +121,36 @@ pub fn uninstall_path(install_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Shell setup problems for an install root, as reported by `wasmedgeup doctor`.
+#[derive(Debug, Default)]
+pub struct SetupStatus {
+    /// Env scripts of the available shells that do not exist.
+    pub missing_env_scripts: Vec<PathBuf>,
+    /// rc files of the available shells that do not source the env script,
+    /// paired with the line that should be added.
+    pub unsourced_rc_files: Vec<(PathBuf, String)>,
+}
+
+/// Check what [`setup_path`] would have written for `install_dir`.
+pub fn setup_status(install_dir: &Path) -> SetupStatus {
+    let mut status = SetupStatus::default();
+    for shell in get_available_shells() {
+        let script = install_dir.join(shell.env_script().name);
+        if !script.is_file() && !status.missing_env_scripts.contains(&script) {
+            status.missing_env_scripts.push(script);
+        }
+
+        let source_line = shell.source_line(install_dir);
+        for rc in shell.effective_rc_files() {
+            let sourced = read_to_string(&rc).is_ok_and(|content| content.contains(&source_line));
+            if !sourced && !status.unsourced_rc_files.iter().any(|(p, _)| *p == rc) {
+                status.unsourced_rc_files.push((rc, source_line.clone()));
+            }
+        }
+    }
+    status
+}
+
 pub fn get_supported_shells() -> Vec<Shell> {
     vec![
         Box::new(Posix),
@@ -40,6 +40,12 @@ pub fn setup_path(install_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Files [`setup_path`] may create or change for `install_dir`: none, as
+/// it only updates `PATH` in the registry, in a single write.
+pub fn setup_files(_install_dir: &Path) -> Vec<std::path::PathBuf> {
+    Vec::new()
+}
+
 pub fn uninstall_path(install_dir: &Path) -> Result<()> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
     let env = hkcu
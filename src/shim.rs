@@ -0,0 +1,410 @@
+//! rustup-style shims for the WasmEdge executables.
+//!
+//! With shims enabled, `<root>/bin` is a real directory holding copies of
+//! the `wasmedgeup` executable named after the runtime binaries
+//! (`wasmedge`, `wasmedgec`). When started under one of those names, the
+//! binary resolves which runtime version to use and execs
+//! `versions/<version>/bin/<name>` with the library and plugin search
+//! paths pointing at that version. Two shells can therefore run different
+//! versions at the same time, and switching the default never swaps a
+//! directory out from under a running process.
+//!
+//! Version resolution, first match wins:
+//! 1. a leading `+<version>` argument (`wasmedge +0.14.1 app.wasm`),
+//! 2. the `WASMEDGE_VERSION` environment variable,
+//! 3. the nearest `wasmedge-toolchain.toml`,
+//! 4. the default version the stable `lib`/`include`/`plugin` links point at.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::api::{latest_installed_version, select_installed_version};
+use crate::prelude::*;
+use crate::toolchain_file::ToolchainFile;
+
+/// Runtime executables that get a shim in `<root>/bin`.
+pub const SHIM_BINARIES: &[&str] = &["wasmedge", "wasmedgec"];
+
+/// Marker file identifying `<root>/bin` as a shim directory managed by
+/// wasmedgeup, as opposed to a foreign directory we must never touch.
+pub const SHIM_MARKER_FILE_NAME: &str = ".wasmedgeup-shims";
+
+/// Environment variable selecting the runtime version for shims and
+/// `wasmedgeup run`.
+pub const VERSION_ENV_VAR: &str = "WASMEDGE_VERSION";
+
+/// Return the shimmed binary name if the process was started as one of
+/// [`SHIM_BINARIES`] (ignoring the directory and any `.exe` suffix).
+pub fn shim_name(argv0: &OsStr) -> Option<&'static str> {
+    let stem = Path::new(argv0).file_stem()?.to_str()?;
+    SHIM_BINARIES.iter().copied().find(|name| *name == stem)
+}
+
+/// `true` if `dir` is a shim directory created by [`enable`].
+pub fn is_shim_dir(dir: &Path) -> bool {
+    std::fs::symlink_metadata(dir)
+        .map(|m| m.file_type().is_dir())
+        .unwrap_or(false)
+        && dir.join(SHIM_MARKER_FILE_NAME).is_file()
+}
+
+/// Environment under which a specific installed runtime version runs: its
+/// `bin`, `lib` and `plugin` directories come first on the respective search
+/// paths, and the install root's stable (default-version) entries are
+/// dropped so they cannot shadow the selected version.
+#[derive(Debug, Clone)]
+pub struct RuntimeEnv {
+    root: PathBuf,
+    version: String,
+}
+
+impl RuntimeEnv {
+    /// Build the environment for `version`, failing if it is not installed
+    /// under `root`.
+    pub fn new(root: &Path, version: &str) -> Result<Self> {
+        let env = Self {
+            root: root.to_path_buf(),
+            version: version.to_string(),
+        };
+        if !env.version_dir().is_dir() {
+            return Err(Error::VersionNotFound {
+                version: version.to_string(),
+            });
+        }
+        Ok(env)
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn version_dir(&self) -> PathBuf {
+        self.root.join("versions").join(&self.version)
+    }
+
+    pub fn bin_dir(&self) -> PathBuf {
+        self.version_dir().join("bin")
+    }
+
+    /// Path of the runtime executable `name` inside this version.
+    pub fn binary(&self, name: &str) -> PathBuf {
+        self.bin_dir()
+            .join(format!("{name}{}", std::env::consts::EXE_SUFFIX))
+    }
+
+    /// Apply the environment to `cmd`.
+    ///
+    /// `WASMEDGE_VERSION` is exported too, so shims invoked by the child
+    /// (e.g. a script calling `wasmedge`) resolve to the same version.
+    pub fn apply(&self, cmd: &mut Command) {
+        let version_dir = self.version_dir();
+
+        cmd.env(
+            "PATH",
+            prepend_path_list(
+                std::env::var_os("PATH"),
+                &version_dir.join("bin"),
+                &self.root.join("bin"),
+            ),
+        );
+
+        let lib_var = if cfg!(target_os = "macos") {
+            Some("DYLD_LIBRARY_PATH")
+        } else if cfg!(windows) {
+            None
+        } else {
+            Some("LD_LIBRARY_PATH")
+        };
+        if let Some(var) = lib_var {
+            cmd.env(
+                var,
+                prepend_path_list(
+                    std::env::var_os(var),
+                    &version_dir.join(LIB_DIR),
+                    &self.root.join(LIB_DIR),
+                ),
+            );
+        }
+
+        cmd.env(
+            "WASMEDGE_PLUGIN_PATH",
+            prepend_path_list(
+                std::env::var_os("WASMEDGE_PLUGIN_PATH"),
+                &version_dir.join("plugin"),
+                &self.root.join("plugin"),
+            ),
+        );
+        cmd.env(VERSION_ENV_VAR, &self.version);
+    }
+}
+
+/// Prepend `first` to the search-path list `current`, removing any entry
+/// equal to `first` or `drop`. If the result cannot be represented (an entry
+/// contains the platform separator), only `first` is returned.
+pub fn prepend_path_list(current: Option<OsString>, first: &Path, drop: &Path) -> OsString {
+    let mut entries = vec![first.to_path_buf()];
+    if let Some(current) = current {
+        entries.extend(
+            std::env::split_paths(&current)
+                .filter(|p| !p.as_os_str().is_empty() && p != first && p != drop),
+        );
+    }
+    std::env::join_paths(&entries).unwrap_or_else(|_| first.as_os_str().to_os_string())
+}
+
+/// Install root a shim belongs to: shims live in `<root>/bin`.
+fn shim_root() -> Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    exe.parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .ok_or_else(|| Error::InvalidPath {
+            path: exe.display().to_string(),
+            reason: "shim is not located in an install root's bin directory".to_string(),
+        })
+}
+
+/// Resolve the runtime version a shim (or `wasmedgeup run`) should use when
+/// no explicit version was given. See the module docs for the order.
+pub async fn resolve_default_version(root: &Path) -> Result<String> {
+    if let Some(v) = std::env::var(VERSION_ENV_VAR)
+        .ok()
+        .filter(|v| !v.is_empty())
+    {
+        tracing::debug!(version = %v, "Using version from {VERSION_ENV_VAR}");
+        return Ok(v);
+    }
+    if let Some((v, path)) = ToolchainFile::discover_version()? {
+        tracing::debug!(version = %v, path = %path.display(), "Using version pinned by toolchain file");
+        return select_installed_version(&root.join("versions"), &v).map(|v| v.to_string());
+    }
+    if let Some(v) = crate::fs::current_version(root).await {
+        return Ok(v);
+    }
+    match latest_installed_version(&root.join("versions"))? {
+        Some(v) => Ok(v.to_string()),
+        None => Err(Error::VersionNotFound {
+            version: "<none installed>".to_string(),
+        }),
+    }
+}
+
+/// Entry point when the executable was started as the shim `name`. Returns
+/// the exit code to terminate with.
+pub async fn run(name: &str, args: Vec<OsString>) -> Result<i32> {
+    let root = shim_root()?;
+
+    let mut args = args.into_iter().peekable();
+    let explicit = args
+        .next_if(|a| a.to_str().is_some_and(|s| s.starts_with('+')))
+        .and_then(|a| a.to_str().map(|s| s[1..].to_string()));
+    let version = match explicit {
+        Some(v) => v,
+        None => resolve_default_version(&root).await?,
+    };
+
+    let env = RuntimeEnv::new(&root, &version)?;
+    let binary = env.binary(name);
+    if !binary.is_file() {
+        return Err(Error::ShimTargetNotFound {
+            binary: name.to_string(),
+            version,
+        });
+    }
+
+    let mut cmd = Command::new(&binary);
+    cmd.args(args);
+    env.apply(&mut cmd);
+    exec(cmd)
+}
+
+/// Replace the current process with `cmd` (Unix), or run it to completion
+/// and return its exit code (Windows, which has no `exec`).
+pub fn exec(mut cmd: Command) -> Result<i32> {
+    let program = cmd.get_program().to_string_lossy().to_string();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // `exec` only returns on failure.
+        let source = cmd.exec();
+        Err(Error::Io {
+            action: "execute".to_string(),
+            path: program,
+            source,
+        })
+    }
+
+    #[cfg(windows)]
+    {
+        let status = cmd.status().map_err(|source| Error::Io {
+            action: "execute".to_string(),
+            path: program,
+            source,
+        })?;
+        Ok(status.code().unwrap_or(1))
+    }
+}
+
+/// Turn `<root>/bin` into a shim directory, or refresh the shims if it
+/// already is one.
+///
+/// An existing `bin` symlink is replaced; a foreign real directory is
+/// refused, mirroring the guard in [`crate::fs::create_version_symlinks`].
+pub async fn enable(root: &Path) -> Result<()> {
+    enable_with(root, &std::env::current_exe()?).await
+}
+
+/// [`enable`], installing `exe` as the shims instead of the running binary.
+/// `self update` uses this to refresh the shims with the new release.
+pub async fn enable_with(root: &Path, exe: &Path) -> Result<()> {
+    let bin = root.join("bin");
+    match tokio::fs::symlink_metadata(&bin).await {
+        Ok(meta) if meta.file_type().is_symlink() || meta.file_type().is_file() => {
+            remove_link(&bin).await?;
+        }
+        Ok(meta) if meta.file_type().is_dir() && !is_shim_dir(&bin) => {
+            return Err(Error::InvalidPath {
+                path: bin.display().to_string(),
+                reason: "refusing to turn an existing directory into a shim directory; \
+                         remove it manually or choose a dedicated install path"
+                    .to_string(),
+            });
+        }
+        _ => {}
+    }
+
+    tokio::fs::create_dir_all(&bin)
+        .await
+        .map_err(|source| Error::Io {
+            action: "create shim directory".to_string(),
+            path: bin.display().to_string(),
+            source,
+        })?;
+
+    for name in SHIM_BINARIES {
+        let shim = bin.join(format!("{name}{}", std::env::consts::EXE_SUFFIX));
+        install_shim(exe, &shim).await?;
+        tracing::debug!(shim = %shim.display(), "Installed shim");
+    }
+
+    tokio::fs::write(
+        bin.join(SHIM_MARKER_FILE_NAME),
+        "This directory is managed by wasmedgeup (`wasmedgeup shims`).\n",
+    )
+    .await
+    .map_err(|source| Error::Io {
+        action: "write shim marker".to_string(),
+        path: bin.display().to_string(),
+        source,
+    })?;
+    Ok(())
+}
+
+/// Replace the shim directory with the regular `bin` symlink to the default
+/// version (if there is one).
+pub async fn disable(root: &Path) -> Result<()> {
+    let bin = root.join("bin");
+    if !is_shim_dir(&bin) {
+        tracing::debug!(bin = %bin.display(), "Shims are not enabled; nothing to do");
+        return Ok(());
+    }
+    tokio::fs::remove_dir_all(&bin)
+        .await
+        .map_err(|source| Error::Io {
+            action: "remove shim directory".to_string(),
+            path: bin.display().to_string(),
+            source,
+        })?;
+
+    if let Some(version) = crate::fs::current_version(root).await {
+        crate::fs::create_version_symlinks(root, &version).await?;
+    }
+    Ok(())
+}
+
+/// Copy (or hard link, when possible) `exe` to `shim`, replacing any stale
+/// shim left by a previous wasmedgeup version.
+async fn install_shim(exe: &Path, shim: &Path) -> Result<()> {
+    remove_link(shim).await?;
+    if tokio::fs::hard_link(exe, shim).await.is_ok() {
+        return Ok(());
+    }
+    tokio::fs::copy(exe, shim)
+        .await
+        .map_err(|source| Error::Io {
+            action: "install shim".to_string(),
+            path: shim.display().to_string(),
+            source,
+        })?;
+    Ok(())
+}
+
+/// Remove a file or symlink at `path`, treating "not found" as success.
+async fn remove_link(path: &Path) -> Result<()> {
+    #[cfg(windows)]
+    {
+        // Directory symlinks need remove_dir on Windows.
+        if tokio::fs::remove_dir(path).await.is_ok() {
+            return Ok(());
+        }
+    }
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(source) => Err(Error::Io {
+            action: "remove existing entry".to_string(),
+            path: path.display().to_string(),
+            source,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shim_name_matches_runtime_binaries_only() {
+        assert_eq!(shim_name(OsStr::new("wasmedge")), Some("wasmedge"));
+        assert_eq!(
+            shim_name(OsStr::new("/home/u/.wasmedge/bin/wasmedgec")),
+            Some("wasmedgec")
+        );
+        assert_eq!(shim_name(OsStr::new("wasmedge.exe")), Some("wasmedge"));
+        assert_eq!(shim_name(OsStr::new("wasmedgeup")), None);
+        assert_eq!(shim_name(OsStr::new("")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prepend_path_list_puts_version_first_and_drops_stable_entry() {
+        let out = prepend_path_list(
+            Some(OsString::from("/root/.wasmedge/lib:/usr/lib:/opt/v/lib")),
+            Path::new("/opt/v/lib"),
+            Path::new("/root/.wasmedge/lib"),
+        );
+        assert_eq!(out, OsString::from("/opt/v/lib:/usr/lib"));
+    }
+
+    #[test]
+    fn prepend_path_list_handles_unset_variable() {
+        let first = Path::new("first");
+        let out = prepend_path_list(None, first, Path::new("drop"));
+        assert_eq!(out, first.as_os_str());
+    }
+
+    #[test]
+    fn runtime_env_requires_installed_version() {
+        let root = tempfile::tempdir().unwrap();
+        let err = RuntimeEnv::new(root.path(), "0.14.1").unwrap_err();
+        assert!(matches!(err, Error::VersionNotFound { .. }), "got {err:?}");
+
+        std::fs::create_dir_all(root.path().join("versions").join("0.14.1")).unwrap();
+        let env = RuntimeEnv::new(root.path(), "0.14.1").unwrap();
+        assert!(env
+            .binary("wasmedge")
+            .starts_with(root.path().join("versions").join("0.14.1").join("bin")));
+    }
+}
@@ -0,0 +1,223 @@
+//! Minisign signatures on `SHA256SUM` files.
+//!
+//! Archives and their `SHA256SUM` come from the same release, so checking
+//! one against the other catches corruption but not tampering. A release
+//! may also publish `SHA256SUM.minisig`, a detached minisign signature made
+//! with a key pinned in the config file (`signature_public_key`); the
+//! checksum lines are only trusted once it verifies.
+//!
+//! The [`SignaturePolicy`] decides what a missing key or signature means. A
+//! signature that is present but does not verify always fails.
+
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::CHECKSUM_FILE_NAME;
+use crate::prelude::*;
+
+/// File name of the detached signature on [`CHECKSUM_FILE_NAME`].
+pub const SIGNATURE_FILE_NAME: &str = "SHA256SUM.minisig";
+
+/// What to do when a `SHA256SUM` cannot be checked against a signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SignaturePolicy {
+    /// Fail without a public key or a signature
+    Require,
+    /// Verify when a public key is configured; warn about a missing signature
+    #[default]
+    Warn,
+    /// Never look for signatures
+    Skip,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureVerifier {
+    policy: SignaturePolicy,
+    public_key: Option<PublicKey>,
+}
+
+impl SignatureVerifier {
+    /// A verifier for the minisign public key `public_key`, given either as
+    /// the bare base64 key or as the contents of a `.pub` file.
+    pub fn new(policy: SignaturePolicy, public_key: Option<&str>) -> Result<Self> {
+        let public_key = public_key
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(|k| {
+                if k.contains('\n') {
+                    PublicKey::decode(k)
+                } else {
+                    PublicKey::from_base64(k)
+                }
+                .map_err(|e| Error::InvalidSignaturePublicKey {
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()?;
+        Ok(Self { policy, public_key })
+    }
+
+    /// Whether [`Self::verify`] looks at the signature at all; when it does
+    /// not, there is no point fetching one.
+    pub fn wants_signature(&self) -> bool {
+        match self.policy {
+            SignaturePolicy::Require => true,
+            SignaturePolicy::Warn => self.public_key.is_some(),
+            SignaturePolicy::Skip => false,
+        }
+    }
+
+    /// Check `signature` on the `SHA256SUM` contents `content`, read from
+    /// `source` (a URL or path, used in messages).
+    pub fn verify(&self, source: &str, content: &[u8], signature: Option<&str>) -> Result<()> {
+        if self.policy == SignaturePolicy::Skip {
+            return Ok(());
+        }
+        let Some(public_key) = &self.public_key else {
+            if self.policy == SignaturePolicy::Require {
+                return Err(Error::SignaturePublicKeyMissing);
+            }
+            tracing::debug!(%source, "No signature public key configured; not verifying");
+            return Ok(());
+        };
+        let Some(signature) = signature else {
+            if self.policy == SignaturePolicy::Require {
+                return Err(Error::SignatureMissing {
+                    file: source.to_string(),
+                });
+            }
+            tracing::warn!(%source, "No {SIGNATURE_FILE_NAME} published; trusting {CHECKSUM_FILE_NAME} unsigned");
+            return Ok(());
+        };
+        Signature::decode(signature)
+            .and_then(|sig| public_key.verify(content, &sig, false))
+            .map_err(|e| Error::SignatureInvalid {
+                file: source.to_string(),
+                reason: e.to_string(),
+            })?;
+        tracing::debug!(%source, "Signature verified");
+        Ok(())
+    }
+}
+
+/// Read the `SHA256SUM` at `checksum_file`, verifying it against the
+/// [`SIGNATURE_FILE_NAME`] next to it.
+pub fn read_verified(
+    checksum_file: &std::path::Path,
+    verifier: &SignatureVerifier,
+) -> Result<String> {
+    let content = std::fs::read_to_string(checksum_file).map_err(|source| Error::Io {
+        action: "read checksum file".to_string(),
+        path: checksum_file.display().to_string(),
+        source,
+    })?;
+    let signature = if verifier.wants_signature() {
+        let path = checksum_file.with_file_name(SIGNATURE_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(sig) => Some(sig),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(source) => {
+                return Err(Error::Io {
+                    action: "read signature file".to_string(),
+                    path: path.display().to_string(),
+                    source,
+                })
+            }
+        }
+    } else {
+        None
+    };
+    verifier.verify(
+        &checksum_file.display().to_string(),
+        content.as_bytes(),
+        signature.as_deref(),
+    )?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMS: &str = "abcd  WasmEdge-0.14.1-manylinux2014_x86_64.tar.gz\n";
+
+    /// A fresh key pair: the public key in base64 and a signer.
+    fn keypair() -> (String, impl Fn(&str) -> String) {
+        let kp = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+        let public = kp.pk.to_base64();
+        let sign = move |content: &str| {
+            minisign::sign(Some(&kp.pk), &kp.sk, content.as_bytes(), None, None)
+                .unwrap()
+                .into_string()
+        };
+        (public, sign)
+    }
+
+    #[test]
+    fn accepts_good_and_rejects_tampered_signatures() {
+        let (public, sign) = keypair();
+        let signature = sign(SUMS);
+        for policy in [SignaturePolicy::Require, SignaturePolicy::Warn] {
+            let verifier = SignatureVerifier::new(policy, Some(&public)).unwrap();
+            verifier
+                .verify("SHA256SUM", SUMS.as_bytes(), Some(&signature))
+                .unwrap();
+            let err = verifier
+                .verify("SHA256SUM", b"ffff  evil.tar.gz\n", Some(&signature))
+                .unwrap_err();
+            assert!(matches!(err, Error::SignatureInvalid { .. }), "{err:?}");
+        }
+
+        let (other, _) = keypair();
+        let verifier = SignatureVerifier::new(SignaturePolicy::Warn, Some(&other)).unwrap();
+        let err = verifier
+            .verify("SHA256SUM", SUMS.as_bytes(), Some(&signature))
+            .unwrap_err();
+        assert!(matches!(err, Error::SignatureInvalid { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn policy_decides_about_missing_keys_and_signatures() {
+        let (public, _) = keypair();
+        let require = SignatureVerifier::new(SignaturePolicy::Require, Some(&public)).unwrap();
+        let err = require
+            .verify("SHA256SUM", SUMS.as_bytes(), None)
+            .unwrap_err();
+        assert!(matches!(err, Error::SignatureMissing { .. }), "{err:?}");
+        let err = SignatureVerifier::new(SignaturePolicy::Require, None)
+            .unwrap()
+            .verify("SHA256SUM", SUMS.as_bytes(), None)
+            .unwrap_err();
+        assert!(matches!(err, Error::SignaturePublicKeyMissing), "{err:?}");
+
+        let warn = SignatureVerifier::new(SignaturePolicy::Warn, Some(&public)).unwrap();
+        warn.verify("SHA256SUM", SUMS.as_bytes(), None).unwrap();
+        assert!(!SignatureVerifier::default().wants_signature());
+
+        let skip = SignatureVerifier::new(SignaturePolicy::Skip, Some(&public)).unwrap();
+        assert!(!skip.wants_signature());
+        skip.verify("SHA256SUM", SUMS.as_bytes(), Some("garbage"))
+            .unwrap();
+
+        let err = SignatureVerifier::new(SignaturePolicy::Warn, Some("not a key")).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidSignaturePublicKey { .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn reads_signature_next_to_local_checksum_file() {
+        let (public, sign) = keypair();
+        let dir = tempfile::tempdir().unwrap();
+        let sums = dir.path().join(CHECKSUM_FILE_NAME);
+        std::fs::write(&sums, SUMS).unwrap();
+        let verifier = SignatureVerifier::new(SignaturePolicy::Require, Some(&public)).unwrap();
+        let err = read_verified(&sums, &verifier).unwrap_err();
+        assert!(matches!(err, Error::SignatureMissing { .. }), "{err:?}");
+
+        std::fs::write(dir.path().join(SIGNATURE_FILE_NAME), sign(SUMS)).unwrap();
+        assert_eq!(read_verified(&sums, &verifier).unwrap(), SUMS);
+    }
+}
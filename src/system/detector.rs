@@ -33,7 +33,12 @@ pub fn detect() -> SystemSpec {
     }
 }
 
-fn compute_target_triple(os: crate::target::TargetOS, arch: TargetArch, libc: LibcKind) -> String {
+/// Rust target triple for the host, as used in wasmedgeup release asset names.
+pub fn compute_target_triple(
+    os: crate::target::TargetOS,
+    arch: TargetArch,
+    libc: LibcKind,
+) -> String {
     let arch_str = match arch {
         TargetArch::X86_64 => "x86_64",
         TargetArch::Aarch64 => "aarch64",
@@ -1,7 +1,7 @@
 use clap::ValueEnum;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, ValueEnum, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
 pub enum TargetOS {
     Linux,
     Ubuntu,
@@ -67,7 +67,7 @@ fn get_ubuntu_version() -> Option<(u32, u32)> {
     None
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Default)]
 pub enum TargetArch {
     /// aliases: [x86_64, amd64]
     #[value(name = "x86_64", alias("amd64"))]
@@ -0,0 +1,180 @@
+//! Per-directory runtime pinning via `wasmedge-toolchain.toml`.
+//!
+//! Works like `rust-toolchain.toml`: the first file found while walking up
+//! from the current directory pins the WasmEdge runtime version (and,
+//! optionally, the plugins a project needs). `use`, `plugin install` and
+//! `plugin list` consult it before falling back to the highest installed
+//! version or `wasmedge --version` on PATH, so every checkout of a project
+//! resolves to the same runtime.
+//!
+//! ```toml
+//! [toolchain]
+//! version = "0.14.1"
+//! plugins = ["wasi_logging", "wasi_nn-ggml@0.14.1"]
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use crate::constants::TOOLCHAIN_FILE_NAME;
+use crate::prelude::*;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolchainFileRepr {
+    toolchain: ToolchainSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolchainSection {
+    version: Option<String>,
+    #[serde(default)]
+    plugins: Vec<String>,
+}
+
+/// A parsed `wasmedge-toolchain.toml` together with the path it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainFile {
+    /// Location of the file, reported to users so they know why a version
+    /// was picked.
+    pub path: PathBuf,
+    /// Pinned runtime version, e.g. `0.14.1` or `latest`.
+    pub version: Option<String>,
+    /// Plugins the project requires, in `name` or `name@version` form.
+    pub plugins: Vec<String>,
+}
+
+impl ToolchainFile {
+    /// Walk up from `start` and return the path of the first toolchain file
+    /// found, if any.
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(TOOLCHAIN_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Read and parse the toolchain file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|source| Error::Io {
+            action: "read toolchain file".to_string(),
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::parse(path, &content)
+    }
+
+    fn parse(path: &Path, content: &str) -> Result<Self> {
+        let repr: ToolchainFileRepr = toml::from_str(content).context(ToolchainFileSnafu {
+            path: path.display().to_string(),
+        })?;
+        let version = repr
+            .toolchain
+            .version
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        Ok(Self {
+            path: path.to_path_buf(),
+            version,
+            plugins: repr.toolchain.plugins,
+        })
+    }
+
+    /// Locate and load the toolchain file governing `start`, if any.
+    pub fn discover_from(start: &Path) -> Result<Option<Self>> {
+        match Self::find(start) {
+            Some(path) => {
+                let file = Self::load(&path)?;
+                tracing::debug!(path = %file.path.display(), version = ?file.version, "Found toolchain file");
+                Ok(Some(file))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Locate and load the toolchain file governing the current directory.
+    ///
+    /// An unreadable current directory (e.g. deleted from under the shell)
+    /// is treated as "no toolchain file" rather than an error: pinning is an
+    /// optional layer on top of the regular version selection.
+    pub fn discover() -> Result<Option<Self>> {
+        match std::env::current_dir() {
+            Ok(cwd) => Self::discover_from(&cwd),
+            Err(e) => {
+                tracing::debug!(error = %e, "Unable to read current directory; skipping toolchain file lookup");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Convenience wrapper returning only the pinned version and the file
+    /// that pinned it.
+    pub fn discover_version() -> Result<Option<(String, PathBuf)>> {
+        Ok(Self::discover()?.and_then(|f| f.version.map(|v| (v, f.path))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_and_plugins() {
+        let file = ToolchainFile::parse(
+            Path::new("wasmedge-toolchain.toml"),
+            "[toolchain]\nversion = \"0.14.1\"\nplugins = [\"wasi_logging\", \"wasi_nn-ggml@0.14.1\"]\n",
+        )
+        .unwrap();
+        assert_eq!(file.version.as_deref(), Some("0.14.1"));
+        assert_eq!(file.plugins, vec!["wasi_logging", "wasi_nn-ggml@0.14.1"]);
+    }
+
+    #[test]
+    fn plugins_are_optional_and_blank_version_is_none() {
+        let file = ToolchainFile::parse(
+            Path::new("wasmedge-toolchain.toml"),
+            "[toolchain]\nversion = \"  \"\n",
+        )
+        .unwrap();
+        assert_eq!(file.version, None);
+        assert!(file.plugins.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let err = ToolchainFile::parse(
+            Path::new("wasmedge-toolchain.toml"),
+            "[toolchain]\nchannel = \"0.14.1\"\n",
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::ToolchainFile { .. }), "got {err:?}");
+    }
+
+    #[test]
+    fn find_walks_up_to_the_nearest_file() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            root.path().join(TOOLCHAIN_FILE_NAME),
+            "[toolchain]\nversion = \"0.13.5\"\n",
+        )
+        .unwrap();
+
+        let found = ToolchainFile::discover_from(&nested).unwrap().unwrap();
+        assert_eq!(found.path, root.path().join(TOOLCHAIN_FILE_NAME));
+        assert_eq!(found.version.as_deref(), Some("0.13.5"));
+
+        // A closer file shadows the outer one.
+        std::fs::write(
+            nested.join(TOOLCHAIN_FILE_NAME),
+            "[toolchain]\nversion = \"0.15.0\"\n",
+        )
+        .unwrap();
+        let found = ToolchainFile::discover_from(&nested).unwrap().unwrap();
+        assert_eq!(found.version.as_deref(), Some("0.15.0"));
+    }
+}
@@ -0,0 +1,332 @@
+//! All-or-nothing runtime installs.
+//!
+//! A runtime is staged in `versions/.<version>.partial` and only renamed to
+//! `versions/<version>` once it is complete; a version it replaces is kept
+//! as `versions/.<version>.backup` until the install commits. The stable
+//! links and the files `setup_path` writes are recorded before they change.
+//! When any step fails, [`InstallTransaction::rollback`] puts everything
+//! back, so a failed install never leaves a half-populated version behind
+//! for `list` or `use` to find.
+//!
+//! A process killed mid-install can still leave staging directories; they
+//! are never taken for versions and [`stale_dirs`] reports them.
+
+use std::path::{Path, PathBuf};
+
+use crate::prelude::*;
+
+const PARTIAL_SUFFIX: &str = ".partial";
+const BACKUP_SUFFIX: &str = ".backup";
+
+/// Stable links in the install root that point into `versions/<version>`.
+const STABLE_LINKS: [&str; 4] = ["bin", "include", "lib", "plugin"];
+
+/// What a stable link was before the install touched it.
+enum LinkState {
+    Missing,
+    Symlink(PathBuf),
+    File(Vec<u8>),
+    /// A real directory, e.g. the shim `bin`; never replaced.
+    Dir,
+}
+
+/// A version directory moved into place, and the one it replaced.
+struct Placed {
+    dir: PathBuf,
+    backup: Option<PathBuf>,
+}
+
+pub struct InstallTransaction {
+    root: PathBuf,
+    staging: Vec<PathBuf>,
+    placed: Vec<Placed>,
+    links: Option<Vec<(PathBuf, LinkState)>>,
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl InstallTransaction {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            staging: Vec::new(),
+            placed: Vec::new(),
+            links: None,
+            files: Vec::new(),
+        }
+    }
+
+    fn versions_dir(&self) -> PathBuf {
+        self.root.join("versions")
+    }
+
+    /// Where `version` is installed.
+    pub fn version_dir(&self, version: &str) -> PathBuf {
+        self.versions_dir().join(version)
+    }
+
+    /// A fresh, empty staging directory for `version`. One left behind by
+    /// an interrupted install is removed first.
+    pub fn staging_dir(&mut self, version: &str) -> Result<PathBuf> {
+        let dir = self
+            .versions_dir()
+            .join(format!(".{version}{PARTIAL_SUFFIX}"));
+        if dir.exists() {
+            tracing::debug!(dir = %dir.display(), "Removing stale staging directory");
+            std::fs::remove_dir_all(&dir).map_err(|source| Error::Io {
+                action: "remove stale staging directory".to_string(),
+                path: dir.display().to_string(),
+                source,
+            })?;
+        }
+        std::fs::create_dir_all(&dir).map_err(|source| Error::Io {
+            action: "create staging directory".to_string(),
+            path: dir.display().to_string(),
+            source,
+        })?;
+        self.staging.push(dir.clone());
+        Ok(dir)
+    }
+
+    /// Rename the complete `staging` directory to `versions/<version>`,
+    /// moving an existing installation of it aside.
+    pub fn place(&mut self, staging: &Path, version: &str) -> Result<PathBuf> {
+        let dir = self.version_dir(version);
+        let rename = |from: &Path, to: &Path| {
+            std::fs::rename(from, to).map_err(|source| Error::Io {
+                action: "move version directory into place".to_string(),
+                path: to.display().to_string(),
+                source,
+            })
+        };
+        let backup = if dir.exists() {
+            let backup = self
+                .versions_dir()
+                .join(format!(".{version}{BACKUP_SUFFIX}"));
+            if backup.exists() {
+                remove_dir(&backup);
+            }
+            rename(&dir, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+        if let Err(e) = rename(staging, &dir) {
+            if let Some(backup) = &backup {
+                if let Err(e) = std::fs::rename(backup, &dir) {
+                    tracing::warn!(error = %e, dir = %dir.display(), "Failed to restore replaced version");
+                }
+            }
+            return Err(e);
+        }
+        tracing::debug!(dir = %dir.display(), "Moved version into place");
+        self.placed.push(Placed {
+            dir: dir.clone(),
+            backup,
+        });
+        Ok(dir)
+    }
+
+    /// Record the stable links before they are re-pointed.
+    pub fn record_links(&mut self) {
+        let links = STABLE_LINKS
+            .iter()
+            .map(|name| {
+                let path = self.root.join(name);
+                let state = match std::fs::symlink_metadata(&path) {
+                    Err(_) => LinkState::Missing,
+                    Ok(meta) if meta.file_type().is_symlink() => std::fs::read_link(&path)
+                        .map(LinkState::Symlink)
+                        .unwrap_or(LinkState::Dir),
+                    Ok(meta) if meta.is_dir() => LinkState::Dir,
+                    Ok(_) => std::fs::read(&path)
+                        .map(LinkState::File)
+                        .unwrap_or(LinkState::Dir),
+                };
+                (path, state)
+            })
+            .collect();
+        self.links = Some(links);
+    }
+
+    /// Record the contents of `paths` before they are written.
+    pub fn record_files(&mut self, paths: impl IntoIterator<Item = PathBuf>) {
+        for path in paths {
+            if self.files.iter().any(|(p, _)| *p == path) {
+                continue;
+            }
+            let content = std::fs::read(&path).ok();
+            self.files.push((path, content));
+        }
+    }
+
+    /// Keep the changes, dropping the versions they replaced.
+    pub fn commit(self) {
+        for placed in &self.placed {
+            if let Some(backup) = &placed.backup {
+                remove_dir(backup);
+            }
+        }
+    }
+
+    /// Undo every recorded change, newest first. Failures are logged: the
+    /// error that caused the rollback is the one to report.
+    pub fn rollback(self) {
+        tracing::debug!(root = %self.root.display(), "Rolling back install");
+        for (path, content) in self.files.iter().rev() {
+            let restored = match content {
+                Some(content) => std::fs::write(path, content),
+                None if path.exists() => std::fs::remove_file(path),
+                None => Ok(()),
+            };
+            if let Err(e) = restored {
+                tracing::warn!(error = %e, path = %path.display(), "Failed to restore file");
+            }
+        }
+        for (path, state) in self.links.iter().flatten() {
+            if let Err(e) = restore_link(path, state) {
+                tracing::warn!(error = %e, link = %path.display(), "Failed to restore link");
+            }
+        }
+        for dir in self.staging.iter().filter(|d| d.exists()) {
+            remove_dir(dir);
+        }
+        for placed in self.placed.iter().rev() {
+            remove_dir(&placed.dir);
+            if let Some(backup) = &placed.backup {
+                if let Err(e) = std::fs::rename(backup, &placed.dir) {
+                    tracing::warn!(error = %e, dir = %placed.dir.display(), "Failed to restore replaced version");
+                }
+            }
+        }
+    }
+}
+
+fn remove_dir(dir: &Path) {
+    if let Err(e) = std::fs::remove_dir_all(dir) {
+        tracing::warn!(error = %e, dir = %dir.display(), "Failed to remove directory");
+    }
+}
+
+fn restore_link(path: &Path, state: &LinkState) -> std::io::Result<()> {
+    if matches!(state, LinkState::Dir) {
+        return Ok(());
+    }
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if meta.file_type().is_symlink() || meta.is_file() {
+            // Directory symlinks on Windows are removed as directories.
+            std::fs::remove_file(path).or_else(|_| std::fs::remove_dir(path))?;
+        }
+    }
+    match state {
+        LinkState::Symlink(target) => {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(target, path)?;
+            #[cfg(windows)]
+            std::os::windows::fs::symlink_dir(target, path)?;
+        }
+        LinkState::File(content) => std::fs::write(path, content)?,
+        LinkState::Missing | LinkState::Dir => {}
+    }
+    Ok(())
+}
+
+/// Staging directories under `versions_dir` left by interrupted installs.
+pub fn stale_dirs(versions_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(versions_dir) else {
+        return Vec::new();
+    };
+    let mut stale: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_str().is_some_and(is_staging_name))
+        .map(|e| e.path())
+        .collect();
+    stale.sort();
+    stale
+}
+
+/// Whether `name` is a staging directory rather than an installed version.
+pub fn is_staging_name(name: &str) -> bool {
+    name.starts_with('.') && (name.ends_with(PARTIAL_SUFFIX) || name.ends_with(BACKUP_SUFFIX))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn commit_replaces_version_and_drops_backup() {
+        let root = tempfile::tempdir().unwrap();
+        write(&root.path().join("versions/0.14.1/bin/wasmedge"), "old");
+
+        let mut txn = InstallTransaction::new(root.path());
+        let staging = txn.staging_dir("0.14.1").unwrap();
+        write(&staging.join("bin/wasmedge"), "new");
+        let dir = txn.place(&staging, "0.14.1").unwrap();
+        assert_eq!(stale_dirs(&root.path().join("versions")).len(), 1);
+        txn.commit();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("bin/wasmedge")).unwrap(),
+            "new"
+        );
+        assert!(stale_dirs(&root.path().join("versions")).is_empty());
+    }
+
+    #[test]
+    fn rollback_restores_versions_links_and_files() {
+        let root = tempfile::tempdir().unwrap();
+        write(&root.path().join("versions/0.14.1/bin/wasmedge"), "old");
+        std::os::unix::fs::symlink("versions/0.14.1/bin", root.path().join("bin")).unwrap();
+        let rc = root.path().join(".bashrc");
+        write(&rc, "# rc\n");
+        let env = root.path().join("env");
+
+        let mut txn = InstallTransaction::new(root.path());
+        for version in ["0.14.1", "0.15.0"] {
+            let staging = txn.staging_dir(version).unwrap();
+            write(&staging.join("bin/wasmedge"), "new");
+            txn.place(&staging, version).unwrap();
+        }
+        txn.record_links();
+        std::fs::remove_file(root.path().join("bin")).unwrap();
+        std::os::unix::fs::symlink("versions/0.15.0/bin", root.path().join("bin")).unwrap();
+        std::os::unix::fs::symlink("versions/0.15.0/lib", root.path().join("lib")).unwrap();
+        txn.record_files([rc.clone(), env.clone()]);
+        write(&rc, "# rc\n. env\n");
+        write(&env, "export PATH");
+        txn.rollback();
+
+        let versions = root.path().join("versions");
+        assert_eq!(
+            std::fs::read_to_string(versions.join("0.14.1/bin/wasmedge")).unwrap(),
+            "old"
+        );
+        assert!(!versions.join("0.15.0").exists());
+        assert!(stale_dirs(&versions).is_empty());
+        assert_eq!(
+            std::fs::read_link(root.path().join("bin")).unwrap(),
+            Path::new("versions/0.14.1/bin")
+        );
+        assert!(std::fs::symlink_metadata(root.path().join("lib")).is_err());
+        assert_eq!(std::fs::read_to_string(&rc).unwrap(), "# rc\n");
+        assert!(!env.exists());
+    }
+
+    #[test]
+    fn stale_staging_dirs_are_reported_and_replaced() {
+        let root = tempfile::tempdir().unwrap();
+        let versions = root.path().join("versions");
+        write(&versions.join(".0.15.0.partial/bin/wasmedge"), "half");
+        std::fs::create_dir_all(versions.join("0.14.1")).unwrap();
+        assert_eq!(stale_dirs(&versions), [versions.join(".0.15.0.partial")]);
+
+        let mut txn = InstallTransaction::new(root.path());
+        let staging = txn.staging_dir("0.15.0").unwrap();
+        assert_eq!(std::fs::read_dir(staging).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+
+use sha2::{Digest, Sha256};
+use url::Url;
+use wasmedgeup::{
+    api::WasmEdgeApiClient,
+    cache::{partial_etag_path, DownloadCache},
+};
+
+use test_utils::Response;
+
+mod test_utils;
+
+const ARCHIVE: &str = "WasmEdge-plugin-wasi_logging-0.14.1-manylinux2014_x86_64.tar.gz";
+
+/// Nothing listens on the discard port, so any request fails fast.
+fn unreachable_url() -> Url {
+    Url::parse(&format!("http://127.0.0.1:9/0.14.1/{ARCHIVE}")).unwrap()
+}
+
+#[tokio::test]
+async fn test_download_served_from_cache_without_network() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::write(&src, b"cached plugin archive").unwrap();
+    let sha = hex::encode(Sha256::digest(b"cached plugin archive"));
+
+    let cache = DownloadCache::new(dir.path().join("cache"));
+    cache.store(&src, &sha, ARCHIVE).unwrap();
+
+    let client = WasmEdgeApiClient::default().with_cache(Some(cache));
+    let to = dir.path().join("plugin.tar.gz");
+    client
+        .download_to_path(unreachable_url(), &to, true, "plugin download", Some(&sha))
+        .await
+        .expect("cache hit must not touch the network");
+    assert_eq!(std::fs::read(&to).unwrap(), b"cached plugin archive");
+}
+
+#[tokio::test]
+async fn test_cache_miss_falls_back_to_network() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DownloadCache::new(dir.path().join("cache"));
+    let client = WasmEdgeApiClient::default()
+        .with_connect_timeout(2)
+        .with_cache(Some(cache));
+
+    // Unknown checksum: not cached, so the download is attempted and fails.
+    let sha = hex::encode(Sha256::digest(b"something else"));
+    let result = client
+        .download_to_path(
+            unreachable_url(),
+            &dir.path().join("plugin.tar.gz"),
+            true,
+            "plugin download",
+            Some(&sha),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+/// Serve `body` with a strong `ETag`, honouring `Range: bytes=<start>-`
+/// requests whose `If-Range` matches. Returns the URL and the `Range`
+/// header of each request (empty when absent).
+fn serve_ranges(body: &'static [u8], etag: &'static str) -> (Url, Arc<Mutex<Vec<String>>>) {
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&ranges);
+    let (base, _) = test_utils::serve(move |request| {
+        let range = request.header("range").unwrap_or_default();
+        log.lock().unwrap().push(range.to_string());
+
+        let start = range
+            .strip_prefix("bytes=")
+            .and_then(|r| r.strip_suffix('-'))
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|_| request.header("if-range") == Some(etag));
+        let response = match start {
+            Some(start) => Response::new("206 Partial Content").with_header(
+                "Content-Range",
+                format!("bytes {start}-{}/{}", body.len() - 1, body.len()),
+            ),
+            None => Response::new("200 OK"),
+        };
+        response
+            .with_header("Accept-Ranges", "bytes")
+            .with_header("ETag", etag)
+            .with_body(&body[start.unwrap_or(0)..])
+    });
+    (
+        Url::parse(&format!("{base}/0.14.1/{ARCHIVE}")).unwrap(),
+        ranges,
+    )
+}
+
+#[tokio::test]
+async fn test_interrupted_download_is_resumed_with_range_request() {
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    const ETAG: &str = "\"v1\"";
+    let (url, ranges) = serve_ranges(BODY, ETAG);
+    let sha = hex::encode(Sha256::digest(BODY));
+
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DownloadCache::new(dir.path().join("cache"));
+    // An earlier attempt stopped after 10 bytes.
+    let partial = cache.partial_path(&sha, ARCHIVE).unwrap();
+    std::fs::write(&partial, &BODY[..10]).unwrap();
+    std::fs::write(partial_etag_path(&partial), ETAG).unwrap();
+
+    let client = WasmEdgeApiClient::default().with_cache(Some(cache.clone()));
+    let to = dir.path().join("plugin.tar.gz");
+    client
+        .download_to_path(url, &to, true, "plugin download", Some(&sha))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&to).unwrap(), BODY);
+    assert_eq!(*ranges.lock().unwrap(), ["bytes=10-"]);
+    assert!(!partial.exists(), "partial download should be removed");
+    assert!(cache.get(&sha, ARCHIVE).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_partial_download_with_stale_etag_restarts() {
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let (url, ranges) = serve_ranges(BODY, "\"v2\"");
+    let sha = hex::encode(Sha256::digest(BODY));
+
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DownloadCache::new(dir.path().join("cache"));
+    let partial = cache.partial_path(&sha, ARCHIVE).unwrap();
+    std::fs::write(&partial, b"stale bytes").unwrap();
+    std::fs::write(partial_etag_path(&partial), "\"v1\"").unwrap();
+
+    let client = WasmEdgeApiClient::default().with_cache(Some(cache));
+    let to = dir.path().join("plugin.tar.gz");
+    client
+        .download_to_path(url, &to, true, "plugin download", Some(&sha))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&to).unwrap(), BODY);
+    assert_eq!(*ranges.lock().unwrap(), ["bytes=11-"]);
+}
+
+#[tokio::test]
+async fn test_unwritable_cache_does_not_fail_download() {
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let (url, ranges) = serve_ranges(BODY, "\"v1\"");
+    let sha = hex::encode(Sha256::digest(BODY));
+
+    let dir = tempfile::tempdir().unwrap();
+    // A regular file where the cache root should be.
+    let root = dir.path().join("cache");
+    std::fs::write(&root, b"").unwrap();
+
+    let client = WasmEdgeApiClient::default().with_cache(Some(DownloadCache::new(&root)));
+    let to = dir.path().join("plugin.tar.gz");
+    client
+        .download_to_path(url, &to, true, "plugin download", Some(&sha))
+        .await
+        .expect("an unwritable cache must not fail the download");
+
+    assert_eq!(std::fs::read(&to).unwrap(), BODY);
+    assert_eq!(*ranges.lock().unwrap(), [""]);
+}
+
+#[tokio::test]
+async fn test_partial_download_locked_by_another_process_is_left_alone() {
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    const ETAG: &str = "\"v1\"";
+    let (url, ranges) = serve_ranges(BODY, ETAG);
+    let sha = hex::encode(Sha256::digest(BODY));
+
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DownloadCache::new(dir.path().join("cache"));
+    // Another download of the same archive is writing the partial file.
+    let partial = cache.partial_path(&sha, ARCHIVE).unwrap();
+    std::fs::write(&partial, &BODY[..10]).unwrap();
+    std::fs::write(partial_etag_path(&partial), ETAG).unwrap();
+    let _held = cache.lock_partial(&partial).unwrap().unwrap();
+
+    let client = WasmEdgeApiClient::default().with_cache(Some(cache.clone()));
+    let to = dir.path().join("plugin.tar.gz");
+    client
+        .download_to_path(url, &to, true, "plugin download", Some(&sha))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&to).unwrap(), BODY);
+    assert_eq!(*ranges.lock().unwrap(), [""]);
+    assert_eq!(std::fs::read(&partial).unwrap(), &BODY[..10]);
+}
@@ -84,12 +84,17 @@ async fn test_get_release_checksum() {
     let client = WasmEdgeApiClient::default();
     let version = client.latest_release().await.unwrap();
     let mut args = InstallArgs {
-        version: "latest".to_string(),
+        versions: vec!["latest".to_string()],
+        default: None,
         path: None,
         tmpdir: None,
         os: None,
         arch: None,
         no_verify: false,
+        verify: false,
+        no_switch: false,
+        from_file: None,
+        from_dir: None,
     };
     let os = args.os.get_or_insert_default();
     let arch = args.arch.get_or_insert_default();
@@ -0,0 +1,174 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+fn wasmedgeup(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(args)
+        .env("HOME", home)
+        .env(
+            "WASMEDGEUP_CONFIG",
+            home.join("wasmedgeup").join("config.toml"),
+        )
+        .output()
+        .unwrap()
+}
+
+fn stdout_json(out: Output) -> Value {
+    assert!(out.status.success(), "command failed: {out:?}");
+    serde_json::from_slice(&out.stdout).unwrap()
+}
+
+#[test]
+fn test_config_set_get_list_unset() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+
+    assert!(
+        wasmedgeup(home, &["config", "set", "connect_timeout", "30"])
+            .status
+            .success()
+    );
+    assert!(wasmedgeup(
+        home,
+        &["config", "set", "default_plugins", r#"["wasi_logging"]"#]
+    )
+    .status
+    .success());
+
+    let out = wasmedgeup(home, &["config", "get", "connect_timeout"]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "30");
+
+    let listed = stdout_json(wasmedgeup(home, &["--output", "json", "config", "list"]));
+    assert_eq!(listed["settings"]["connect_timeout"], 30);
+    assert_eq!(listed["settings"]["default_plugins"][0], "wasi_logging");
+
+    assert!(wasmedgeup(home, &["config", "unset", "connect_timeout"])
+        .status
+        .success());
+    let got = stdout_json(wasmedgeup(
+        home,
+        &["--output", "json", "config", "get", "connect_timeout"],
+    ));
+    assert_eq!(got["value"], Value::Null);
+
+    let out = wasmedgeup(home, &["config", "set", "connect_timeout", "soon"]);
+    assert!(!out.status.success());
+    let out = wasmedgeup(home, &["config", "get", "no_such_key"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Unknown config key"));
+}
+
+#[test]
+fn test_configured_path_is_the_default_install_root() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+    let root = home.join("configured-root");
+    std::fs::create_dir_all(root.join("versions").join("0.14.1")).unwrap();
+
+    assert!(
+        wasmedgeup(home, &["config", "set", "path", root.to_str().unwrap()])
+            .status
+            .success()
+    );
+
+    let listed = stdout_json(wasmedgeup(home, &["--output", "json", "list"]));
+    assert_eq!(listed["install_root"], root.display().to_string());
+    assert_eq!(listed["versions"][0]["version"], "0.14.1");
+
+    // `--path` still wins over the config file.
+    let other = home.join("other-root");
+    let listed = stdout_json(wasmedgeup(
+        home,
+        &["--output", "json", "list", "-p", other.to_str().unwrap()],
+    ));
+    assert_eq!(listed["install_root"], other.display().to_string());
+}
+
+#[test]
+fn test_invalid_config_file_is_ignored_and_can_be_fixed() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+    let config = home.join("wasmedgeup").join("config.toml");
+    std::fs::create_dir_all(config.parent().unwrap()).unwrap();
+    std::fs::write(&config, "verfy = false\nretries = 3\n").unwrap();
+
+    let out = wasmedgeup(home, &["--output", "json", "list"]);
+    assert!(out.status.success(), "command failed: {out:?}");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Ignoring config file"));
+
+    let listed = stdout_json(wasmedgeup(home, &["--output", "json", "config", "list"]));
+    assert_eq!(listed["settings"]["verfy"], false);
+    assert!(listed["error"].as_str().unwrap().contains("verfy"));
+
+    assert!(wasmedgeup(home, &["config", "unset", "verfy"])
+        .status
+        .success());
+    let listed = stdout_json(wasmedgeup(home, &["--output", "json", "config", "list"]));
+    assert_eq!(listed["error"], Value::Null);
+    assert_eq!(listed["settings"]["retries"], 3);
+}
+
+#[test]
+fn test_invalid_mirror_url_is_rejected_and_can_be_removed() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+
+    let out = wasmedgeup(home, &["config", "set", "mirror.dist_server", "not a url"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("mirror.dist_server"));
+
+    // Written by hand: `config` still runs so the setting can be removed.
+    let config = home.join("wasmedgeup").join("config.toml");
+    std::fs::create_dir_all(config.parent().unwrap()).unwrap();
+    std::fs::write(&config, "[mirror]\ndist_server = \"not a url\"\n").unwrap();
+    let out = wasmedgeup(home, &["config", "unset", "mirror.dist_server"]);
+    assert!(out.status.success(), "command failed: {out:?}");
+    assert_eq!(std::fs::read_to_string(&config).unwrap().trim(), "");
+}
+
+#[test]
+fn test_invalid_signature_public_key_is_rejected_and_can_be_removed() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+
+    let out = wasmedgeup(
+        home,
+        &["config", "set", "signature_public_key", "RWQnotakey"],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("public key"));
+
+    let config = home.join("wasmedgeup").join("config.toml");
+    std::fs::create_dir_all(config.parent().unwrap()).unwrap();
+    std::fs::write(&config, "signature_public_key = \"RWQnotakey\"\n").unwrap();
+    let out = wasmedgeup(home, &["config", "unset", "signature_public_key"]);
+    assert!(out.status.success(), "command failed: {out:?}");
+    assert_eq!(std::fs::read_to_string(&config).unwrap().trim(), "");
+}
+
+#[test]
+fn test_github_token_is_masked() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+    let token = "ghp_0123456789abcdef";
+
+    let out = wasmedgeup(home, &["config", "set", "github_token", token]);
+    assert!(out.status.success(), "command failed: {out:?}");
+    let set = String::from_utf8_lossy(&out.stdout);
+    assert!(set.contains("ghp_***") && !set.contains(token), "{set}");
+
+    let out = wasmedgeup(home, &["config", "list"]);
+    let listed = String::from_utf8_lossy(&out.stdout);
+    assert!(listed.contains("github_token = \"ghp_***\""), "{listed}");
+    let listed = stdout_json(wasmedgeup(home, &["--output", "json", "config", "list"]));
+    assert_eq!(listed["settings"]["github_token"], "ghp_***");
+
+    let out = wasmedgeup(home, &["config", "get", "github_token"]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "ghp_***");
+
+    // The file keeps the real token.
+    let file = std::fs::read_to_string(home.join("wasmedgeup").join("config.toml")).unwrap();
+    assert!(file.contains(token));
+}
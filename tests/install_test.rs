@@ -5,7 +5,7 @@ use serial_test::serial;
 use tempfile::{tempdir, TempDir};
 use wasmedgeup::system;
 use wasmedgeup::{
-    api::{releases, Asset, ReleasesFilter, WasmEdgeApiClient},
+    api::{releases, Asset, Endpoints, ReleasesFilter, WasmEdgeApiClient},
     cli::{CommandContext, CommandExecutor},
     commands::install::InstallArgs,
 };
@@ -23,7 +23,7 @@ async fn first_available_prerelease(versions: Vec<SemVersion>) -> Option<SemVers
 
     for v in versions.into_iter().filter(|v| !v.pre.is_empty()) {
         let asset = Asset::new(&v, &specs.os.os_type, &specs.os.arch);
-        let url = match asset.url() {
+        let url = match asset.url(&Endpoints::default()) {
             Ok(u) => u,
             Err(_) => continue,
         };
@@ -43,18 +43,24 @@ async fn execute_install_test(
     no_verify: bool,
 ) {
     let args = InstallArgs {
-        version,
+        versions: vec![version],
+        default: None,
         path: Some(install_dir.clone()),
         tmpdir: Some(tmpdir.path().to_path_buf()),
         os: None,
         arch: None,
         no_verify,
+        verify: false,
+        no_switch: false,
+        from_file: None,
+        from_dir: None,
     };
 
     let client = WasmEdgeApiClient::default();
     let ctx = CommandContext {
         client,
         no_progress: false,
+        ..Default::default()
     };
 
     args.execute(ctx).await.expect("install failed");
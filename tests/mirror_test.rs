@@ -0,0 +1,321 @@
+#![cfg(unix)]
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use semver::Version;
+use sha2::{Digest, Sha256};
+use wasmedgeup::{
+    api::Asset,
+    target::{TargetArch, TargetOS},
+};
+
+use test_utils::Response;
+
+mod test_utils;
+
+const VERSION: &str = "0.14.1";
+
+/// Lay out a release mirror holding the runtime archive for this host and
+/// its `SHA256SUM`, as `<mirror>/<tag>/...`.
+fn populate_mirror(mirror: &Path) {
+    let asset = Asset::new(
+        &Version::parse(VERSION).unwrap(),
+        &TargetOS::default(),
+        &TargetArch::default(),
+    );
+    let tag_dir = mirror.join(VERSION);
+    std::fs::create_dir_all(&tag_dir).unwrap();
+    let archive = tag_dir.join(&asset.archive_name);
+
+    let gz = flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive).unwrap(),
+        flate2::Compression::fast(),
+    );
+    let mut tar = tar::Builder::new(gz);
+    let prefix = format!("WasmEdge-{VERSION}-Linux");
+    for (path, content) in [
+        (format!("{prefix}/bin/wasmedge"), &b"#!/bin/sh\n"[..]),
+        (format!("{prefix}/lib/libwasmedge.so"), b"lib"),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, path, content).unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+
+    let sha = hex::encode(Sha256::digest(std::fs::read(&archive).unwrap()));
+    std::fs::write(
+        tag_dir.join("SHA256SUM"),
+        format!("{sha}  {}\n", asset.archive_name),
+    )
+    .unwrap();
+}
+
+fn install(home: &Path, envs: &[(&str, &str)]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(["--no-cache", "install", VERSION, "-p"])
+        .arg(home.join(".wasmedge"))
+        .env("HOME", home)
+        .env("WASMEDGEUP_CONFIG", home.join("config.toml"))
+        .env_remove("WASMEDGEUP_DIST_SERVER")
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_install_from_mirror_in_config_file() {
+    let home = tempfile::tempdir().unwrap();
+    let mirror = tempfile::tempdir().unwrap();
+    populate_mirror(mirror.path());
+    let (base, requests) = test_utils::serve_dir(mirror.path(), "/releases/download");
+    std::fs::write(
+        home.path().join("config.toml"),
+        format!("[mirror]\ndist_server = \"{base}/\"\n"),
+    )
+    .unwrap();
+
+    let out = install(home.path(), &[]);
+    assert!(out.status.success(), "install failed: {out:?}");
+
+    let version_dir = home.path().join(".wasmedge").join("versions").join(VERSION);
+    assert!(version_dir.join("bin").join("wasmedge").is_file());
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests.contains(&format!("/releases/download/{VERSION}/SHA256SUM")),
+        "{requests:?}"
+    );
+    assert_eq!(requests.len(), 2, "{requests:?}");
+}
+
+#[test]
+fn test_dist_server_env_overrides_config_file() {
+    let home = tempfile::tempdir().unwrap();
+    let mirror = tempfile::tempdir().unwrap();
+    populate_mirror(mirror.path());
+    let (base, requests) = test_utils::serve_dir(mirror.path(), "/releases/download");
+    // Nothing listens on the discard port, so using the config value fails.
+    std::fs::write(
+        home.path().join("config.toml"),
+        "[mirror]\ndist_server = \"http://127.0.0.1:9/releases/download\"\n",
+    )
+    .unwrap();
+
+    let out = install(home.path(), &[("WASMEDGEUP_DIST_SERVER", &base)]);
+    assert!(out.status.success(), "install failed: {out:?}");
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_install_requires_valid_checksum_signature() {
+    let home = tempfile::tempdir().unwrap();
+    let mirror = tempfile::tempdir().unwrap();
+    populate_mirror(mirror.path());
+    let sums = mirror.path().join(VERSION).join("SHA256SUM");
+    let keypair = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+    let signature = minisign::sign(
+        Some(&keypair.pk),
+        &keypair.sk,
+        std::fs::File::open(&sums).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let (base, _) = test_utils::serve_dir(mirror.path(), "/releases/download");
+    std::fs::write(
+        home.path().join("config.toml"),
+        format!(
+            "signature_policy = \"require\"\nsignature_public_key = \"{}\"\n\n[mirror]\ndist_server = \"{base}/\"\n",
+            keypair.pk.to_base64()
+        ),
+    )
+    .unwrap();
+
+    let out = install(home.path(), &[]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("No signature found"), "{stderr}");
+
+    // A SHA256SUM edited after signing is rejected.
+    let sig_path = mirror.path().join(VERSION).join("SHA256SUM.minisig");
+    std::fs::write(&sig_path, signature.into_string()).unwrap();
+    let original = std::fs::read_to_string(&sums).unwrap();
+    std::fs::write(&sums, format!("{original}0000  extra.tar.gz\n")).unwrap();
+    let out = install(home.path(), &[]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Signature verification failed"), "{stderr}");
+    let version_dir = home.path().join(".wasmedge").join("versions").join(VERSION);
+    assert!(!version_dir.exists());
+
+    std::fs::write(&sums, original).unwrap();
+    let out = install(home.path(), &[]);
+    assert!(out.status.success(), "install failed: {out:?}");
+    assert!(version_dir.join("bin").join("wasmedge").is_file());
+}
+
+#[test]
+fn test_invalid_config_file_is_reported_and_ignored() {
+    let home = tempfile::tempdir().unwrap();
+    let mirror = tempfile::tempdir().unwrap();
+    populate_mirror(mirror.path());
+    let (base, _) = test_utils::serve_dir(mirror.path(), "/releases/download");
+    std::fs::write(home.path().join("config.toml"), "[mirror]\nunknown = 1\n").unwrap();
+
+    let out = install(home.path(), &[("WASMEDGEUP_DIST_SERVER", &base)]);
+    assert!(out.status.success(), "install failed: {out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Invalid config file"), "{stdout}");
+}
+
+#[test]
+fn test_plugin_list_reports_rate_limit_instead_of_empty_table() {
+    let (base, _) = test_utils::serve(|_| {
+        let reset = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 1800;
+        Response::new("403 Forbidden")
+            .with_header("x-ratelimit-remaining", 0)
+            .with_header("x-ratelimit-reset", reset)
+    });
+    let api = format!("{base}/releases/tags");
+
+    let home = tempfile::tempdir().unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(["plugin", "list", "--runtime", VERSION])
+        .env("HOME", home.path())
+        .env("WASMEDGEUP_CONFIG", home.path().join("config.toml"))
+        .env("WASMEDGEUP_RELEASE_API", &api)
+        .output()
+        .unwrap();
+    assert!(!out.status.success(), "{out:?}");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("rate limit exceeded"), "{stderr}");
+    assert!(stderr.contains("UTC (in 30 minutes)"), "{stderr}");
+    assert!(String::from_utf8_lossy(&out.stdout).is_empty());
+}
+
+/// Encode `payload` as a git pkt-line.
+fn pkt_line(payload: &str) -> String {
+    format!("{:04x}{payload}", payload.len() + 4)
+}
+
+/// Answer every request with a git ref advertisement listing `0.14.1` and
+/// `0.15.0`, returning the server's base URL and the request targets seen.
+/// Requests through it as a forward proxy carry absolute-form targets.
+fn serve_git_refs() -> (String, Arc<Mutex<Vec<String>>>) {
+    test_utils::serve(|_| {
+        let sha = "1".repeat(40);
+        let body = [
+            pkt_line("# service=git-upload-pack\n"),
+            "0000".to_string(),
+            pkt_line(&format!("{sha} refs/tags/0.14.1\0multi_ack\n")),
+            pkt_line(&format!("{sha} refs/tags/0.15.0\n")),
+            "0000".to_string(),
+        ]
+        .concat();
+        Response::ok(body).with_header(
+            "Content-Type",
+            "application/x-git-upload-pack-advertisement",
+        )
+    })
+}
+
+fn list_remote(home: &Path, git_url: &str, flags: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(flags)
+        .args(["list", "--remote"])
+        .env("HOME", home)
+        .env("XDG_CACHE_HOME", home.join(".cache"))
+        .env("WASMEDGEUP_CONFIG", home.join("config.toml"))
+        .env("WASMEDGEUP_GIT_URL", git_url)
+        .env_remove("NO_PROXY")
+        .env_remove("no_proxy")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_list_remote_goes_through_configured_proxy() {
+    let (proxy, targets) = serve_git_refs();
+    let home = tempfile::tempdir().unwrap();
+    let out = list_remote(
+        home.path(),
+        "http://git.invalid/WasmEdge.git",
+        &["--proxy", &proxy],
+    );
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("0.15.0") && stdout.contains("0.14.1"),
+        "{stdout}"
+    );
+    assert_eq!(
+        targets.lock().unwrap().as_slice(),
+        ["http://git.invalid/WasmEdge.git/info/refs?service=git-upload-pack"]
+    );
+}
+
+#[test]
+fn test_list_remote_uses_cached_release_list() {
+    let (base, targets) = serve_git_refs();
+    let git_url = format!("{base}/WasmEdge.git");
+    let home = tempfile::tempdir().unwrap();
+
+    for flags in [&[][..], &[], &["--offline"]] {
+        let out = list_remote(home.path(), &git_url, flags);
+        assert!(out.status.success(), "{out:?}");
+        assert!(String::from_utf8_lossy(&out.stdout).contains("0.15.0 <- latest"));
+    }
+    assert_eq!(targets.lock().unwrap().len(), 1);
+
+    let out = list_remote(home.path(), &git_url, &["--refresh"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(targets.lock().unwrap().len(), 2);
+
+    // A different remote has no cached listing to fall back on.
+    let out = list_remote(
+        home.path(),
+        "http://127.0.0.1:9/WasmEdge.git",
+        &["--offline"],
+    );
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("No cached release list"), "{stderr}");
+}
+
+#[test]
+fn test_invalid_cacert_is_reported() {
+    let home = tempfile::tempdir().unwrap();
+    let cacert = home.path().join("root.pem");
+    std::fs::write(&cacert, "not a certificate").unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(["--retries", "0", "--cacert"])
+        .arg(&cacert)
+        .args(["list", "--remote"])
+        .env("HOME", home.path())
+        .env("WASMEDGEUP_CONFIG", home.path().join("config.toml"))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("root.pem"), "{stderr}");
+}
+
+#[tokio::test]
+async fn test_get_all_can_be_called_from_async_code() {
+    let (base, _) = serve_git_refs();
+    let releases = wasmedgeup::api::releases::get_all(
+        &format!("{base}/WasmEdge.git"),
+        wasmedgeup::api::ReleasesFilter::All,
+    )
+    .unwrap();
+    assert_eq!(releases, [Version::new(0, 15, 0), Version::new(0, 14, 1)]);
+}
@@ -0,0 +1,490 @@
+#![cfg(unix)]
+
+use std::path::Path;
+
+use semver::Version;
+use serial_test::serial;
+use sha2::{Digest, Sha256};
+use wasmedgeup::{
+    api::{plugin_archive_name, Asset},
+    cli::{CommandContext, CommandExecutor},
+    commands::install::InstallArgs,
+    commands::plugin::{
+        install::PluginInstallArgs, remove::PluginRemoveArgs, version::PluginVersion,
+    },
+    manifest::InstallManifest,
+    system::{self, plugins::plugin_platform_key},
+    target::{TargetArch, TargetOS},
+};
+
+mod test_utils;
+
+const VERSION: &str = "0.14.1";
+
+/// Write a gzipped tarball holding `files` (path, content) to `dest`.
+fn write_tar_gz(dest: &Path, files: &[(&str, &[u8])]) {
+    let gz = flate2::write::GzEncoder::new(
+        std::fs::File::create(dest).unwrap(),
+        flate2::Compression::fast(),
+    );
+    let mut tar = tar::Builder::new(gz);
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, path, *content).unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+}
+
+fn append_checksum(dir: &Path, archive: &Path) {
+    let sha = hex::encode(Sha256::digest(std::fs::read(archive).unwrap()));
+    let name = archive.file_name().unwrap().to_string_lossy();
+    let sums = dir.join("SHA256SUM");
+    let mut content = std::fs::read_to_string(&sums).unwrap_or_default();
+    content.push_str(&format!("{sha}  {name}\n"));
+    std::fs::write(sums, content).unwrap();
+}
+
+/// A bundle holding the runtime archive for this host under `<bundle>/<tag>/`.
+fn runtime_bundle(bundle: &Path) -> std::path::PathBuf {
+    runtime_bundle_version(bundle, VERSION)
+}
+
+fn runtime_bundle_version(bundle: &Path, tag: &str) -> std::path::PathBuf {
+    let version = Version::parse(tag).unwrap();
+    let asset = Asset::new(&version, &TargetOS::default(), &TargetArch::default());
+    let tag_dir = bundle.join(tag);
+    std::fs::create_dir_all(&tag_dir).unwrap();
+    let archive = tag_dir.join(&asset.archive_name);
+    let prefix = format!("WasmEdge-{tag}-Linux");
+    write_tar_gz(
+        &archive,
+        &[
+            (&format!("{prefix}/bin/wasmedge"), b"#!/bin/sh\n"),
+            (&format!("{prefix}/lib/libwasmedge.so"), b"lib"),
+            (&format!("{prefix}/include/wasmedge/wasmedge.h"), b"header"),
+        ],
+    );
+    append_checksum(&tag_dir, &archive);
+    archive
+}
+
+fn install_args(root: &Path, tmp: &Path, version: &str) -> InstallArgs {
+    InstallArgs {
+        versions: vec![version.to_string()],
+        default: None,
+        path: Some(root.to_path_buf()),
+        tmpdir: Some(tmp.to_path_buf()),
+        os: None,
+        arch: None,
+        no_verify: false,
+        verify: false,
+        no_switch: false,
+        from_file: None,
+        from_dir: None,
+    }
+}
+
+/// Local installs must not touch the network; these tests also run in
+/// sandboxes without it.
+fn offline_ctx() -> CommandContext {
+    CommandContext {
+        no_progress: true,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_install_from_dir_latest_without_network() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bundle = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    runtime_bundle(bundle.path());
+
+    let root = test_home.join(".wasmedge");
+    let mut args = install_args(&root, tmp.path(), "latest");
+    args.from_dir = Some(bundle.path().to_path_buf());
+    args.execute(offline_ctx()).await.unwrap();
+
+    let version_dir = root.join("versions").join(VERSION);
+    assert!(version_dir.join("bin").join("wasmedge").is_file());
+    assert!(version_dir.join("lib").join("libwasmedge.so").is_file());
+    assert_eq!(
+        wasmedgeup::fs::current_version(&root).await.as_deref(),
+        Some(VERSION)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_install_several_versions_switches_to_default_only() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bundle = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    for tag in ["0.13.5", "0.14.1", "0.15.0"] {
+        runtime_bundle_version(bundle.path(), tag);
+    }
+
+    let root = test_home.join(".wasmedge");
+    let mut args = install_args(&root, tmp.path(), "0.13.5");
+    args.versions
+        .extend(["~0.14".to_string(), "0.15.0".to_string()]);
+    args.default = Some("~0.14".to_string());
+    args.from_dir = Some(bundle.path().to_path_buf());
+    args.execute(offline_ctx()).await.unwrap();
+
+    for tag in ["0.13.5", "0.14.1", "0.15.0"] {
+        let version_dir = root.join("versions").join(tag);
+        assert!(version_dir.join("bin").join("wasmedge").is_file(), "{tag}");
+    }
+    assert_eq!(
+        wasmedgeup::fs::current_version(&root).await.as_deref(),
+        Some("0.14.1")
+    );
+
+    let mut args = install_args(&root, tmp.path(), "0.13.5");
+    args.default = Some("0.15.0".to_string());
+    args.from_dir = Some(bundle.path().to_path_buf());
+    let err = args.execute(offline_ctx()).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            wasmedgeup::error::Error::DefaultVersionNotRequested { .. }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_install_from_file_rejects_tampered_archive() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bundle = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let archive = runtime_bundle(bundle.path());
+    std::fs::write(&archive, b"tampered").unwrap();
+
+    let root = test_home.join(".wasmedge");
+    let mut args = install_args(&root, tmp.path(), VERSION);
+    args.from_file = Some(archive);
+    let err = args.execute(offline_ctx()).await.unwrap_err();
+    assert!(
+        matches!(err, wasmedgeup::error::Error::ChecksumMismatch { .. }),
+        "{err:?}"
+    );
+    assert!(!root.join("versions").join(VERSION).exists());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_install_rolls_back_versions_and_links() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bundle = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    for tag in [VERSION, "0.15.0"] {
+        runtime_bundle_version(bundle.path(), tag);
+    }
+    let root = test_home.join(".wasmedge");
+    let mut args = install_args(&root, tmp.path(), VERSION);
+    args.from_dir = Some(bundle.path().to_path_buf());
+    args.execute(offline_ctx()).await.unwrap();
+    let plugin = root
+        .join("versions")
+        .join(VERSION)
+        .join("plugin")
+        .join("p.so");
+    std::fs::create_dir_all(plugin.parent().unwrap()).unwrap();
+    std::fs::write(&plugin, b"plugin").unwrap();
+
+    // A real `include` directory makes switching the links fail after both
+    // versions have been unpacked.
+    std::fs::remove_file(root.join("include")).unwrap();
+    std::fs::create_dir(root.join("include")).unwrap();
+    let mut args = install_args(&root, tmp.path(), "0.15.0");
+    args.versions.push(VERSION.to_string());
+    args.from_dir = Some(bundle.path().to_path_buf());
+    let err = args.execute(offline_ctx()).await.unwrap_err();
+    assert!(
+        matches!(err, wasmedgeup::error::Error::InvalidPath { .. }),
+        "{err:?}"
+    );
+
+    let versions = root.join("versions");
+    assert!(!versions.join("0.15.0").exists());
+    assert!(plugin.is_file());
+    assert!(wasmedgeup::transaction::stale_dirs(&versions).is_empty());
+    assert_eq!(
+        wasmedgeup::api::installed_versions(&versions).unwrap(),
+        [Version::parse(VERSION).unwrap()]
+    );
+    assert_eq!(
+        wasmedgeup::fs::current_version(&root).await.as_deref(),
+        Some(VERSION)
+    );
+
+    // Reinstalling keeps the plugins added to the version.
+    std::fs::remove_dir(root.join("include")).unwrap();
+    let mut args = install_args(&root, tmp.path(), VERSION);
+    args.from_dir = Some(bundle.path().to_path_buf());
+    args.execute(offline_ctx()).await.unwrap();
+    assert!(plugin.is_file());
+    assert!(wasmedgeup::transaction::stale_dirs(&versions).is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_plugin_install_from_file_without_network() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let root = test_home.join(".wasmedge");
+    std::fs::create_dir_all(root.join("versions").join(VERSION).join("plugin")).unwrap();
+
+    let spec = system::detect();
+    let os_key = plugin_platform_key(&spec.os, &Version::parse(VERSION).unwrap()).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir
+        .path()
+        .join(plugin_archive_name("wasi_logging", VERSION, &os_key, false));
+    let so = format!(
+        "libwasmedgePluginWasiLogging.{}",
+        if cfg!(target_os = "macos") {
+            "dylib"
+        } else {
+            "so"
+        }
+    );
+    write_tar_gz(&archive, &[(&so, b"plugin")]);
+    append_checksum(dir.path(), &archive);
+
+    let args = PluginInstallArgs {
+        plugins: vec![PluginVersion::Name("wasi_logging".to_string())],
+        tmpdir: Some(dir.path().to_path_buf()),
+        runtime: Some(VERSION.to_string()),
+        path: Some(root.clone()),
+        no_verify: false,
+        verify: false,
+        from_file: Some(archive),
+        from_dir: None,
+        jobs: None,
+    };
+    args.execute(offline_ctx()).await.unwrap();
+
+    assert!(root
+        .join("versions")
+        .join(VERSION)
+        .join("plugin")
+        .join(so)
+        .is_file());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_plugin_install_reports_failures_after_installing_the_rest() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let root = test_home.join(".wasmedge");
+    std::fs::create_dir_all(root.join("versions").join(VERSION).join("plugin")).unwrap();
+
+    let spec = system::detect();
+    let os_key = plugin_platform_key(&spec.os, &Version::parse(VERSION).unwrap()).unwrap();
+    let ext = if cfg!(target_os = "macos") {
+        "dylib"
+    } else {
+        "so"
+    };
+    let bundle = tempfile::tempdir().unwrap();
+    let mut objects = Vec::new();
+    for (plugin, core) in [
+        ("wasi_logging", "WasiLogging"),
+        ("wasi_crypto", "WasiCrypto"),
+    ] {
+        let archive = bundle
+            .path()
+            .join(plugin_archive_name(plugin, VERSION, &os_key, false));
+        let so = format!("libwasmedgePlugin{core}.{ext}");
+        write_tar_gz(&archive, &[(&so, b"plugin")]);
+        append_checksum(bundle.path(), &archive);
+        objects.push(so);
+    }
+
+    let tmp = tempfile::tempdir().unwrap();
+    let args = PluginInstallArgs {
+        plugins: ["wasi_logging", "wasi_nn", "wasi_crypto"]
+            .iter()
+            .map(|p| PluginVersion::Name(p.to_string()))
+            .collect(),
+        tmpdir: Some(tmp.path().to_path_buf()),
+        runtime: Some(VERSION.to_string()),
+        path: Some(root.clone()),
+        no_verify: false,
+        verify: false,
+        from_file: None,
+        from_dir: Some(bundle.path().to_path_buf()),
+        jobs: Some(2),
+    };
+    let err = args.execute(offline_ctx()).await.unwrap_err();
+    assert!(
+        matches!(err, wasmedgeup::error::Error::LocalArchiveNotFound { .. }),
+        "{err:?}"
+    );
+
+    let plugin_dir = root.join("versions").join(VERSION).join("plugin");
+    for so in objects {
+        assert!(plugin_dir.join(&so).is_file(), "{so} was not installed");
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_manifest_records_provenance_and_drives_plugin_remove() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bundle = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let archive = runtime_bundle(bundle.path());
+    let sha256 = hex::encode(Sha256::digest(std::fs::read(&archive).unwrap()));
+
+    let root = test_home.join(".wasmedge");
+    let install = || {
+        let mut args = install_args(&root, tmp.path(), VERSION);
+        args.from_dir = Some(bundle.path().to_path_buf());
+        args
+    };
+    install().execute(offline_ctx()).await.unwrap();
+
+    let version_dir = root.join("versions").join(VERSION);
+    let manifest = InstallManifest::load(&version_dir).unwrap();
+    assert_eq!(manifest.version, VERSION);
+    assert_eq!(manifest.source, Some(archive.display().to_string()));
+    assert_eq!(manifest.sha256, Some(sha256));
+    let mut files = manifest.files.clone();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            "bin/wasmedge",
+            "include/wasmedge/wasmedge.h",
+            "lib/libwasmedge.so"
+        ]
+        .map(std::path::PathBuf::from)
+    );
+
+    let spec = system::detect();
+    let os_key = plugin_platform_key(&spec.os, &Version::parse(VERSION).unwrap()).unwrap();
+    let plugin_archive =
+        tmp.path()
+            .join(plugin_archive_name("wasi_logging", VERSION, &os_key, false));
+    // Named so that guessing from the file name would not find it.
+    let so = &format!(
+        "libwasmedgePluginWasiLoggingExtra.{}",
+        if cfg!(target_os = "macos") {
+            "dylib"
+        } else {
+            "so"
+        }
+    );
+    write_tar_gz(&plugin_archive, &[(so, b"plugin")]);
+    append_checksum(tmp.path(), &plugin_archive);
+    let args = PluginInstallArgs {
+        plugins: vec![PluginVersion::Name("wasi_logging".to_string())],
+        tmpdir: Some(tmp.path().to_path_buf()),
+        runtime: Some(VERSION.to_string()),
+        path: Some(root.clone()),
+        no_verify: false,
+        verify: false,
+        from_file: Some(plugin_archive.clone()),
+        from_dir: None,
+        jobs: None,
+    };
+    args.execute(offline_ctx()).await.unwrap();
+
+    // Reinstalling the runtime keeps the plugin and its record.
+    install().execute(offline_ctx()).await.unwrap();
+    let manifest = InstallManifest::load(&version_dir).unwrap();
+    let plugin = manifest.plugin("wasi-logging").unwrap();
+    assert_eq!(plugin.version, VERSION);
+    assert_eq!(plugin.platform, os_key);
+    assert_eq!(plugin.source, plugin_archive.display().to_string());
+    assert_eq!(
+        plugin.sha256,
+        hex::encode(Sha256::digest(std::fs::read(&plugin_archive).unwrap()))
+    );
+    assert_eq!(plugin.files, [Path::new("plugin").join(so)]);
+    assert!(version_dir.join("plugin").join(so).is_file());
+
+    let args = PluginRemoveArgs {
+        plugins: vec![PluginVersion::Name("wasi_logging".to_string())],
+        runtime: Some(VERSION.to_string()),
+        path: Some(root.clone()),
+    };
+    args.execute(offline_ctx()).await.unwrap();
+    assert!(!version_dir.join("plugin").join(so).exists());
+    let manifest = InstallManifest::load(&version_dir).unwrap();
+    assert!(manifest.plugins.is_empty());
+    assert_eq!(manifest.files.len(), 3);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_plugin_variants_sharing_a_file_are_tracked_by_the_last_installed() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let root = test_home.join(".wasmedge");
+    let version_dir = root.join("versions").join(VERSION);
+    std::fs::create_dir_all(version_dir.join("plugin")).unwrap();
+
+    let spec = system::detect();
+    let os_key = plugin_platform_key(&spec.os, &Version::parse(VERSION).unwrap()).unwrap();
+    let so = format!(
+        "libwasmedgePluginWasiNN.{}",
+        if cfg!(target_os = "macos") {
+            "dylib"
+        } else {
+            "so"
+        }
+    );
+    let bundle = tempfile::tempdir().unwrap();
+    for variant in ["wasi_nn-ggml", "wasi_nn-openvino"] {
+        let archive = bundle
+            .path()
+            .join(plugin_archive_name(variant, VERSION, &os_key, false));
+        write_tar_gz(&archive, &[(&so, variant.as_bytes())]);
+        append_checksum(bundle.path(), &archive);
+    }
+
+    let tmp = tempfile::tempdir().unwrap();
+    for variant in ["wasi_nn-ggml", "wasi_nn-openvino"] {
+        let args = PluginInstallArgs {
+            plugins: vec![PluginVersion::Name(variant.to_string())],
+            tmpdir: Some(tmp.path().to_path_buf()),
+            runtime: Some(VERSION.to_string()),
+            path: Some(root.clone()),
+            no_verify: false,
+            verify: false,
+            from_file: None,
+            from_dir: Some(bundle.path().to_path_buf()),
+            jobs: None,
+        };
+        args.execute(offline_ctx()).await.unwrap();
+    }
+    let manifest = InstallManifest::load(&version_dir).unwrap();
+    assert!(manifest.plugin("wasi_nn-ggml").is_none());
+    assert_eq!(
+        manifest.plugin("wasi_nn-openvino").unwrap().files,
+        [Path::new("plugin").join(&so)]
+    );
+
+    let args = PluginRemoveArgs {
+        plugins: vec![PluginVersion::Name("wasi_nn-ggml".to_string())],
+        runtime: Some(VERSION.to_string()),
+        path: Some(root.clone()),
+    };
+    args.execute(offline_ctx()).await.unwrap();
+    assert_eq!(
+        std::fs::read_to_string(version_dir.join("plugin").join(&so)).unwrap(),
+        "wasi_nn-openvino"
+    );
+    assert!(InstallManifest::load(&version_dir)
+        .unwrap()
+        .plugin("wasi_nn-openvino")
+        .is_some());
+}
@@ -0,0 +1,116 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+use serial_test::serial;
+
+mod test_utils;
+
+fn run_json(root: &Path, args: &[&str]) -> Value {
+    let out = Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(["--output", "json"])
+        .args(args)
+        .arg("-p")
+        .arg(root)
+        .env("HOME", root)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "command failed: {out:?}");
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "expected one document: {stdout}");
+    serde_json::from_str(&stdout).unwrap()
+}
+
+#[test]
+#[serial]
+fn test_list_use_remove_json() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    test_utils::mock_version(&test_home, "0.14.1");
+    test_utils::mock_version(&test_home, "0.15.0");
+
+    let used = run_json(&test_home, &["use", "0.14.1"]);
+    assert_eq!(used["version"], "0.14.1");
+    assert_eq!(used["install_root"], test_home.display().to_string());
+
+    let listed = run_json(&test_home, &["list"]);
+    assert_eq!(listed["current"], "0.14.1");
+    assert_eq!(
+        listed["versions"],
+        serde_json::json!([
+            { "version": "0.15.0", "current": false },
+            { "version": "0.14.1", "current": true },
+        ])
+    );
+
+    let removed = run_json(&test_home, &["remove", "0.14.1"]);
+    assert_eq!(removed["removed"], serde_json::json!(["0.14.1"]));
+    assert_eq!(removed["switched_to"], "0.15.0");
+
+    let listed = run_json(&test_home, &["list"]);
+    assert_eq!(listed["current"], "0.15.0");
+}
+
+#[test]
+#[serial]
+fn test_list_json_without_installation() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+
+    let listed = run_json(&test_home.join("missing"), &["list"]);
+    assert_eq!(listed["current"], Value::Null);
+    assert_eq!(listed["versions"], serde_json::json!([]));
+}
+
+#[test]
+#[serial]
+fn test_plugin_list_installed_json() {
+    use wasmedgeup::commands::plugin::utils::plugin_filename;
+    use wasmedgeup::manifest::{InstallManifest, PluginRecord};
+
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    test_utils::mock_version(&test_home, "0.14.1");
+    test_utils::mock_version(&test_home, "0.15.0");
+    let logging = plugin_filename("WasiLogging");
+    let crypto = plugin_filename("WasiCrypto");
+    let versions = test_home.join("versions");
+    std::fs::write(versions.join("0.15.0/plugin").join(&logging), "logging").unwrap();
+    std::fs::write(versions.join("0.14.1/plugin").join(&crypto), "crypto!").unwrap();
+    std::fs::write(versions.join("0.14.1/plugin/README"), "not a plugin").unwrap();
+
+    let mut manifest = InstallManifest::new("0.15.0");
+    manifest.add_plugin(PluginRecord {
+        name: "wasi_logging".to_string(),
+        version: "0.15.0".to_string(),
+        platform: "manylinux_2_28_x86_64".to_string(),
+        source: "WasmEdge-plugin-wasi_logging-0.15.0-manylinux_2_28_x86_64.tar.gz".to_string(),
+        sha256: "abcd".to_string(),
+        files: vec![Path::new("plugin").join(&logging)],
+    });
+    manifest.save(&versions.join("0.15.0")).unwrap();
+    run_json(&test_home, &["use", "0.15.0"]);
+
+    let listed = run_json(&test_home, &["plugin", "list", "--installed"]);
+    assert_eq!(listed["current"], "0.15.0");
+    let plugins = listed["plugins"].as_array().unwrap();
+    assert_eq!(plugins.len(), 2, "{plugins:?}");
+    assert_eq!(plugins[0]["name"], "WasiLogging");
+    assert_eq!(plugins[0]["runtime"], "0.15.0");
+    assert_eq!(plugins[0]["active"], true);
+    assert_eq!(plugins[0]["size"], 7);
+    assert_eq!(plugins[0]["version"], "0.15.0");
+    assert_eq!(plugins[0]["sha256"], "abcd");
+    assert_eq!(plugins[1]["name"], "WasiCrypto");
+    assert_eq!(plugins[1]["runtime"], "0.14.1");
+    assert_eq!(plugins[1]["active"], false);
+    assert_eq!(plugins[1]["version"], Value::Null);
+
+    let listed = run_json(
+        &test_home,
+        &["plugin", "list", "--installed", "--name", "wasi_logging"],
+    );
+    assert_eq!(listed["plugins"].as_array().unwrap().len(), 1);
+    let listed = run_json(
+        &test_home,
+        &["plugin", "list", "--installed", "--runtime", "0.14.1"],
+    );
+    assert_eq!(listed["plugins"][0]["name"], "WasiCrypto");
+}
@@ -15,18 +15,24 @@ use test_utils::setup_test_environment;
 
 async fn execute_runtime_install(version: String, install_dir: &Path, tmpdir: &TempDir) {
     let args = InstallArgs {
-        version,
+        versions: vec![version],
+        default: None,
         path: Some(install_dir.to_path_buf()),
         tmpdir: Some(tmpdir.path().to_path_buf()),
         os: None,
         arch: None,
         no_verify: false,
+        verify: false,
+        no_switch: false,
+        from_file: None,
+        from_dir: None,
     };
 
     let client = WasmEdgeApiClient::default();
     let ctx = CommandContext {
         client,
         no_progress: false,
+        ..Default::default()
     };
 
     args.execute(ctx).await.expect("runtime install failed");
@@ -49,12 +55,17 @@ async fn execute_plugin_install(
         runtime,
         path: Some(install_dir.clone()),
         no_verify,
+        verify: false,
+        from_file: None,
+        from_dir: None,
+        jobs: None,
     };
 
     let client = WasmEdgeApiClient::default();
     let ctx = CommandContext {
         client,
         no_progress: false,
+        ..Default::default()
     };
 
     args.execute(ctx).await.expect("plugin install failed");
@@ -108,6 +108,7 @@ async fn test_plugin_remove_single() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     args.execute(ctx).await.unwrap();
 
@@ -133,6 +134,7 @@ async fn test_plugin_remove_multiple_and_cleanup_empty_dir() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     args.execute(ctx).await.unwrap();
 
@@ -164,6 +166,7 @@ async fn test_plugin_remove_nonexistent_is_noop() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     args.execute(ctx).await.unwrap();
 
@@ -186,6 +189,7 @@ async fn test_plugin_remove_when_no_plugin_dir() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     args.execute(ctx).await.unwrap();
 
@@ -27,12 +27,54 @@ async fn test_remove_single_version() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     remove_args.execute(ctx).await.unwrap();
 
     assert!(!version_dir.exists(), "Version directory should be removed");
 }
 
+#[tokio::test]
+#[serial]
+async fn test_remove_version_recorded_in_manifest() {
+    use wasmedgeup::manifest::{InstallManifest, PluginRecord};
+
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let versions_dir = test_home.join("versions");
+    setup_mock_version(&versions_dir.join("0.15.0"), "0.15.0").await;
+    let version_dir = versions_dir.join("0.14.1");
+    setup_mock_version(&version_dir, "0.14.1").await;
+
+    let plugin = Path::new("plugin").join("libwasmedgePluginWasiLogging.so");
+    std::fs::create_dir_all(version_dir.join("plugin")).unwrap();
+    std::fs::write(version_dir.join(&plugin), "").unwrap();
+    std::fs::write(version_dir.join("lib").join("user.txt"), "").unwrap();
+    let mut manifest = InstallManifest::new("0.14.1");
+    manifest.files = vec![Path::new("bin").join("wasmedge")];
+    manifest.add_plugin(PluginRecord {
+        name: "wasi_logging".to_string(),
+        version: "0.14.1".to_string(),
+        platform: "manylinux_2_28_x86_64".to_string(),
+        source: "https://example.com/wasi_logging.tar.gz".to_string(),
+        sha256: "abcd".to_string(),
+        files: vec![plugin],
+    });
+    manifest.save(&version_dir).unwrap();
+
+    let remove_args = RemoveArgs {
+        version: "0.14.1".to_string(),
+        all: false,
+        path: Some(test_home.clone()),
+    };
+    remove_args
+        .execute(CommandContext::default())
+        .await
+        .unwrap();
+
+    assert!(!version_dir.exists(), "Version directory should be removed");
+    assert!(versions_dir.join("0.15.0").exists());
+}
+
 #[tokio::test]
 #[serial]
 async fn test_remove_multiple_versions() {
@@ -55,6 +97,7 @@ async fn test_remove_multiple_versions() {
         let ctx = CommandContext {
             client: WasmEdgeApiClient::default(),
             no_progress: true,
+            ..Default::default()
         };
         remove_args.execute(ctx).await.unwrap();
 
@@ -108,6 +151,7 @@ async fn test_remove_all_versions() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     remove_args.execute(ctx).await.unwrap();
 
@@ -131,6 +175,7 @@ async fn test_remove_nonexistent_version() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     let result = remove_args.execute(ctx).await;
     assert!(
@@ -0,0 +1,95 @@
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+use serial_test::serial;
+use wasmedgeup::{
+    cli::{CommandContext, CommandExecutor},
+    commands::run::RunArgs,
+};
+
+mod test_utils;
+
+/// [`test_utils::mock_version`] with a `wasmedge` script that echoes the
+/// version, its plugin path and its arguments.
+fn mock_version(root: &Path, version: &str) {
+    let version_dir = test_utils::mock_version(root, version);
+    let script = version_dir.join("bin").join("wasmedge");
+    std::fs::write(
+        &script,
+        format!("#!/bin/sh\necho \"{version} $WASMEDGE_PLUGIN_PATH $*\"\n"),
+    )
+    .unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+#[serial]
+fn test_run_uses_requested_version_without_switching_default() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    mock_version(&test_home, "0.14.1");
+    mock_version(&test_home, "0.15.0");
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(wasmedgeup::fs::create_version_symlinks(
+            &test_home, "0.15.0",
+        ))
+        .unwrap();
+
+    let run = |version: &str| {
+        let out = Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+            .args(["run", version, "-p"])
+            .arg(&test_home)
+            .args(["--", "wasmedge", "app.wasm"])
+            .env_remove("WASMEDGE_PLUGIN_PATH")
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "run failed: {out:?}");
+        String::from_utf8(out.stdout).unwrap()
+    };
+
+    let plugin_dir = test_home.join("versions").join("0.14.1").join("plugin");
+    assert_eq!(
+        run("0.14.1").trim(),
+        format!("0.14.1 {} app.wasm", plugin_dir.display())
+    );
+    assert!(run("latest").starts_with("0.15.0 "));
+
+    let bin_link = std::fs::read_link(test_home.join("bin")).unwrap();
+    assert!(bin_link.ends_with(Path::new("versions").join("0.15.0").join("bin")));
+}
+
+#[test]
+#[serial]
+fn test_run_propagates_exit_code() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    mock_version(&test_home, "0.14.1");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_wasmedgeup"))
+        .args(["run", "0.14.1", "-p"])
+        .arg(&test_home)
+        .args(["--", "sh", "-c", "exit 3"])
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(3));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_run_missing_version_without_install_fails() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+
+    let args = RunArgs {
+        version: "0.99.99".to_string(),
+        install: false,
+        path: Some(test_home),
+        command: vec!["wasmedge".into()],
+    };
+    let err = args.execute(CommandContext::default()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        wasmedgeup::error::Error::VersionNotFound { version } if version == "0.99.99"
+    ));
+}
@@ -0,0 +1,142 @@
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use wasmedgeup::system::{detector::compute_target_triple, os::detect_os};
+
+mod test_utils;
+
+const NEW_BINARY: &[u8] = b"#!/bin/sh\necho updated\n";
+
+/// Publish release 99.0.0 for this host under `root`, as GitHub's
+/// `releases/latest` document plus the release tarball. The tarball's
+/// digest is reported as `digest`, or its real SHA256 when `None`.
+fn publish_release(root: &Path, base: &str, digest: Option<&str>) {
+    let (host, _, _) = detect_os();
+    let triple = compute_target_triple(host.os_type, host.arch, host.libc.kind);
+    let asset = format!("wasmedgeup-{triple}.tgz");
+    let archive = root.join(&asset);
+
+    let gz = flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive).unwrap(),
+        flate2::Compression::fast(),
+    );
+    let mut tar = tar::Builder::new(gz);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(NEW_BINARY.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    tar.append_data(
+        &mut header,
+        format!("wasmedgeup-{triple}/wasmedgeup"),
+        NEW_BINARY,
+    )
+    .unwrap();
+    tar.into_inner().unwrap().finish().unwrap();
+
+    let sha = hex::encode(Sha256::digest(std::fs::read(&archive).unwrap()));
+    let latest = serde_json::json!({
+        "tag_name": "v99.0.0",
+        "assets": [{
+            "name": asset,
+            "browser_download_url": format!("{base}/{asset}"),
+            "digest": format!("sha256:{}", digest.unwrap_or(&sha)),
+        }],
+    });
+    std::fs::write(root.join("latest"), latest.to_string()).unwrap();
+}
+
+/// Copy the wasmedgeup binary into `dir` so it can be replaced or removed.
+fn copy_binary(dir: &Path) -> PathBuf {
+    let exe = dir.join("wasmedgeup");
+    std::fs::copy(env!("CARGO_BIN_EXE_wasmedgeup"), &exe).unwrap();
+    exe
+}
+
+fn run(exe: &Path, home: &Path, api: &str, args: &[&str]) -> Output {
+    Command::new(exe)
+        .args(["--output", "json", "--no-cache"])
+        .args(args)
+        .env("HOME", home)
+        .env("WASMEDGEUP_CONFIG", home.join("config.toml"))
+        .env("WASMEDGEUP_SELF_UPDATE_API", api)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_self_update_check_only_reports() {
+    let home = tempfile::tempdir().unwrap();
+    let server = tempfile::tempdir().unwrap();
+    let (base, _) = test_utils::serve_dir(server.path(), "");
+    publish_release(server.path(), &base, None);
+    let exe = copy_binary(home.path());
+
+    let out = run(&exe, home.path(), &base, &["self", "update", "--check"]);
+    assert!(out.status.success(), "{out:?}");
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["latest"], "99.0.0");
+    assert_eq!(report["update_available"], true);
+    assert_eq!(report["updated"], false);
+    assert_ne!(std::fs::read(&exe).unwrap(), NEW_BINARY);
+}
+
+#[test]
+fn test_self_update_replaces_binary() {
+    let home = tempfile::tempdir().unwrap();
+    let server = tempfile::tempdir().unwrap();
+    let (base, _) = test_utils::serve_dir(server.path(), "");
+    publish_release(server.path(), &base, None);
+    let exe = copy_binary(home.path());
+
+    let out = run(&exe, home.path(), &base, &["self", "update"]);
+    assert!(out.status.success(), "{out:?}");
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["updated"], true);
+
+    assert_eq!(std::fs::read(&exe).unwrap(), NEW_BINARY);
+    let out = Command::new(&exe).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "updated");
+}
+
+#[test]
+fn test_self_update_rejects_checksum_mismatch() {
+    let home = tempfile::tempdir().unwrap();
+    let server = tempfile::tempdir().unwrap();
+    let (base, _) = test_utils::serve_dir(server.path(), "");
+    publish_release(server.path(), &base, Some(&"0".repeat(64)));
+    let exe = copy_binary(home.path());
+    let before = std::fs::read(&exe).unwrap();
+
+    let out = run(&exe, home.path(), &base, &["self", "update"]);
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("Checksum mismatch"),
+        "{out:?}"
+    );
+    assert_eq!(std::fs::read(&exe).unwrap(), before);
+}
+
+#[test]
+fn test_self_uninstall_removes_binary() {
+    let home = tempfile::tempdir().unwrap();
+    let root = home.path().join(".wasmedge");
+    std::fs::create_dir_all(root.join("versions").join("0.14.1")).unwrap();
+    let exe = copy_binary(home.path());
+
+    let out = run(
+        &exe,
+        home.path(),
+        "http://127.0.0.1:9",
+        &["self", "uninstall", "-p", root.to_str().unwrap()],
+    );
+    assert!(out.status.success(), "{out:?}");
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["binary_removed"], true);
+    assert!(!exe.exists());
+    // Installed runtimes are left alone.
+    assert!(root.join("versions").join("0.14.1").is_dir());
+}
@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use serial_test::serial;
+use wasmedgeup::{
+    cli::{CommandContext, CommandExecutor},
+    commands::use_cmd::UseArgs,
+    shim::{self, SHIM_MARKER_FILE_NAME},
+};
+
+mod test_utils;
+
+#[tokio::test]
+#[serial]
+async fn test_enable_disable_round_trip() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    for version in ["0.14.1", "0.15.0"] {
+        test_utils::mock_version(&test_home, version);
+    }
+    wasmedgeup::fs::create_version_symlinks(&test_home, "0.14.1")
+        .await
+        .unwrap();
+
+    shim::enable(&test_home).await.unwrap();
+    let bin = test_home.join("bin");
+    assert!(shim::is_shim_dir(&bin), "bin should be a shim directory");
+    assert!(bin.join(SHIM_MARKER_FILE_NAME).is_file());
+    for name in shim::SHIM_BINARIES {
+        let shim_path = bin.join(format!("{name}{}", std::env::consts::EXE_SUFFIX));
+        assert!(shim_path.is_file(), "missing shim {}", shim_path.display());
+    }
+
+    // Switching the default leaves the shims in place but still re-points
+    // the remaining links.
+    let args = UseArgs {
+        version: "0.15.0".to_string(),
+        path: Some(test_home.clone()),
+    };
+    args.execute(CommandContext::default()).await.unwrap();
+    assert!(shim::is_shim_dir(&bin));
+    assert_eq!(
+        wasmedgeup::fs::current_version(&test_home).await.as_deref(),
+        Some("0.15.0")
+    );
+
+    shim::disable(&test_home).await.unwrap();
+    let meta = tokio::fs::symlink_metadata(&bin).await.unwrap();
+    assert!(
+        meta.file_type().is_symlink(),
+        "bin should be a symlink again"
+    );
+    assert!(tokio::fs::read_link(&bin)
+        .await
+        .unwrap()
+        .ends_with(Path::new("versions").join("0.15.0").join("bin")));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_enable_refuses_foreign_directory() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    let bin = test_home.join("bin");
+    tokio::fs::create_dir_all(&bin).await.unwrap();
+    tokio::fs::write(bin.join("do-not-delete"), "marker")
+        .await
+        .unwrap();
+
+    let err = shim::enable(&test_home).await.unwrap_err();
+    assert!(
+        matches!(err, wasmedgeup::error::Error::InvalidPath { .. }),
+        "got {err:?}"
+    );
+    assert!(bin.join("do-not-delete").is_file());
+    assert!(!bin.join(SHIM_MARKER_FILE_NAME).exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[serial]
+async fn test_shim_dispatches_to_selected_version() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    for version in ["0.14.1", "0.15.0"] {
+        test_utils::mock_version(&test_home, version);
+    }
+    for version in ["0.14.1", "0.15.0"] {
+        let script = test_home
+            .join("versions")
+            .join(version)
+            .join("bin")
+            .join("wasmedge");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\necho \"{version} $WASMEDGE_VERSION $WASMEDGE_PLUGIN_PATH $*\"\n"),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    wasmedgeup::fs::create_version_symlinks(&test_home, "0.14.1")
+        .await
+        .unwrap();
+
+    // Stand in for `shims enable`, which would copy the test harness rather
+    // than the wasmedgeup binary.
+    std::fs::remove_file(test_home.join("bin")).unwrap();
+    std::fs::create_dir(test_home.join("bin")).unwrap();
+    std::fs::write(test_home.join("bin").join(SHIM_MARKER_FILE_NAME), "").unwrap();
+    let shim_path = test_home.join("bin").join("wasmedge");
+    std::fs::copy(env!("CARGO_BIN_EXE_wasmedgeup"), &shim_path).unwrap();
+
+    let run = |version_env: Option<&str>, args: &[&str]| {
+        let mut cmd = std::process::Command::new(&shim_path);
+        cmd.args(args).current_dir(&test_home);
+        cmd.env(
+            "WASMEDGE_PLUGIN_PATH",
+            test_home.join("plugin").display().to_string(),
+        );
+        match version_env {
+            Some(v) => cmd.env("WASMEDGE_VERSION", v),
+            None => cmd.env_remove("WASMEDGE_VERSION"),
+        };
+        let out = cmd.output().unwrap();
+        assert!(out.status.success(), "shim failed: {out:?}");
+        String::from_utf8(out.stdout).unwrap()
+    };
+
+    let plugin_dir = |v: &str| {
+        test_home
+            .join("versions")
+            .join(v)
+            .join("plugin")
+            .display()
+            .to_string()
+    };
+
+    // Default version from the stable links.
+    assert_eq!(
+        run(None, &["app.wasm"]).trim(),
+        format!("0.14.1 0.14.1 {} app.wasm", plugin_dir("0.14.1"))
+    );
+    // Environment override; the stable plugin dir must not leak through.
+    assert_eq!(
+        run(Some("0.15.0"), &["app.wasm"]).trim(),
+        format!("0.15.0 0.15.0 {} app.wasm", plugin_dir("0.15.0"))
+    );
+    // `+<version>` wins over the environment and is not forwarded.
+    assert_eq!(
+        run(Some("0.15.0"), &["+0.14.1", "app.wasm"]).trim(),
+        format!("0.14.1 0.14.1 {} app.wasm", plugin_dir("0.14.1"))
+    );
+
+    let out = std::process::Command::new(&shim_path)
+        .env("WASMEDGE_VERSION", "0.99.0")
+        .current_dir(&test_home)
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_toolchain_requirement_resolves_to_newest_installed_match() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    for version in ["0.13.5", "0.14.0", "0.14.1", "0.15.0"] {
+        test_utils::mock_version(&test_home, version);
+    }
+
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("wasmedge-toolchain.toml"),
+        "[toolchain]\nversion = \"~0.14\"\n",
+    )
+    .unwrap();
+
+    let original_cwd = std::env::current_dir().unwrap();
+    std::env::set_current_dir(project.path()).unwrap();
+    let resolved = shim::resolve_default_version(&test_home).await;
+    std::env::set_current_dir(original_cwd).unwrap();
+
+    assert_eq!(resolved.unwrap(), "0.14.1");
+}
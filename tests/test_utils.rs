@@ -1,4 +1,10 @@
-use std::path::PathBuf;
+// Each test crate includes this module and uses only part of it.
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 pub fn setup_test_environment() -> (tempfile::TempDir, PathBuf) {
     let test_home = tempfile::tempdir().unwrap();
@@ -23,3 +29,139 @@ pub fn setup_test_environment() -> (tempfile::TempDir, PathBuf) {
 
     (test_home, test_home_path)
 }
+
+/// Lay out an empty installed `version` under `root`: its `bin`, `include`,
+/// `lib` and `plugin` directories.
+pub fn mock_version(root: &Path, version: &str) -> PathBuf {
+    let version_dir = root.join("versions").join(version);
+    for dir in ["bin", "include", "lib", "plugin"] {
+        std::fs::create_dir_all(version_dir.join(dir)).unwrap();
+    }
+    version_dir
+}
+
+/// An HTTP request received by [`serve`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// The request target, e.g. `/0.14.1/SHA256SUM`, or an absolute URL
+    /// for requests sent through a proxy.
+    pub target: String,
+    /// Header names (lowercased) and values.
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// The value of header `name`, given in lowercase.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// The response [`serve`] sends back. `Content-Length` and
+/// `Connection: close` are added.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// Status code and reason, e.g. `200 OK`.
+    pub status: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: &str) -> Self {
+        Self {
+            status: status.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new("200 OK").with_body(body)
+    }
+
+    pub fn not_found() -> Self {
+        Self::new("404 Not Found")
+    }
+
+    pub fn with_header(mut self, name: &str, value: impl ToString) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// Serve HTTP on a loopback port, answering each request (one per
+/// connection) with `handler`. Returns the base URL, e.g.
+/// `http://127.0.0.1:4321`, and the targets of the requests served.
+pub fn serve(
+    handler: impl Fn(&Request) -> Response + Send + 'static,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&targets);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let mut headers = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+                }
+                line.clear();
+            }
+            let request = Request {
+                target: request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or("/")
+                    .to_string(),
+                headers,
+            };
+            log.lock().unwrap().push(request.target.clone());
+
+            let response = handler(&request);
+            let mut head = format!("HTTP/1.1 {}\r\n", response.status);
+            for (name, value) in &response.headers {
+                head.push_str(&format!("{name}: {value}\r\n"));
+            }
+            head.push_str(&format!(
+                "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                response.body.len()
+            ));
+            let mut stream = &stream;
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(&response.body);
+        }
+    });
+    (base, targets)
+}
+
+/// [`serve`] the files under `root` at `<base URL><prefix>/`, answering
+/// `404 Not Found` for anything else. Returns `<base URL><prefix>` and the
+/// request targets.
+pub fn serve_dir(root: &Path, prefix: &str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let root = root.to_path_buf();
+    let dir_prefix = format!("{prefix}/");
+    let (base, targets) = serve(move |request| {
+        request
+            .target
+            .strip_prefix(&dir_prefix)
+            .and_then(|rel| std::fs::read(root.join(rel)).ok())
+            .map_or_else(Response::not_found, Response::ok)
+    });
+    (format!("{base}{prefix}"), targets)
+}
@@ -81,6 +81,7 @@ async fn test_use_latest_version() {
     let ctx = CommandContext {
         client: WasmEdgeApiClient::default(),
         no_progress: true,
+        ..Default::default()
     };
     args.execute(ctx).await.unwrap();
 
@@ -174,6 +175,44 @@ async fn test_use_latest_picks_highest_local_version_not_remote() {
     verify_symlinks(&test_home, "0.14.1").await;
 }
 
+#[tokio::test]
+#[serial]
+async fn test_use_version_requirement_picks_newest_local_match() {
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+
+    for version in ["0.14.0", "0.14.1", "0.15.0"] {
+        let version_dir = test_home.join("versions").join(version);
+        for sub in ["bin", "lib", "include"] {
+            tokio::fs::create_dir_all(version_dir.join(sub))
+                .await
+                .unwrap();
+        }
+        tokio::fs::write(
+            version_dir.join("bin").join("wasmedge"),
+            format!("mock wasmedge {version}"),
+        )
+        .await
+        .unwrap();
+    }
+
+    let args = UseArgs {
+        version: "~0.14".to_string(),
+        path: Some(test_home.clone()),
+    };
+    args.execute(CommandContext::default()).await.unwrap();
+    verify_symlinks(&test_home, "0.14.1").await;
+
+    let args = UseArgs {
+        version: "0.13.x".to_string(),
+        path: Some(test_home.clone()),
+    };
+    let result = args.execute(CommandContext::default()).await;
+    assert!(matches!(
+        result,
+        Err(wasmedgeup::error::Error::VersionNotFound { version }) if version == "0.13.x"
+    ));
+}
+
 #[tokio::test]
 #[serial]
 async fn test_use_refuses_to_replace_existing_real_directory() {
@@ -213,6 +252,44 @@ async fn test_use_refuses_to_replace_existing_real_directory() {
     );
 }
 
+#[tokio::test]
+#[serial]
+async fn test_use_without_version_honours_toolchain_file() {
+    // With no version argument, `use` must pick the version pinned by the
+    // nearest wasmedge-toolchain.toml rather than the highest installed one.
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+
+    for version in ["0.13.5", "0.14.1"] {
+        let version_dir = test_home.join("versions").join(version);
+        for sub in ["bin", "lib", "include"] {
+            tokio::fs::create_dir_all(version_dir.join(sub))
+                .await
+                .unwrap();
+        }
+    }
+
+    let project = tempfile::tempdir().unwrap();
+    let nested = project.path().join("src");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(
+        project.path().join("wasmedge-toolchain.toml"),
+        "[toolchain]\nversion = \"0.13.5\"\n",
+    )
+    .unwrap();
+
+    let original_cwd = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&nested).unwrap();
+    let args = UseArgs {
+        version: String::new(),
+        path: Some(test_home.clone()),
+    };
+    let result = args.execute(CommandContext::default()).await;
+    std::env::set_current_dir(original_cwd).unwrap();
+
+    result.expect("`use` should resolve the pinned version");
+    verify_symlinks(&test_home, "0.13.5").await;
+}
+
 async fn verify_symlinks(base_dir: &Path, expected_version: &str) {
     for dir in ["bin", "lib", "include"] {
         let symlink = base_dir.join(dir);
@@ -244,3 +321,44 @@ async fn verify_symlinks(base_dir: &Path, expected_version: &str) {
         }
     }
 }
+
+#[tokio::test]
+#[serial]
+async fn test_use_waits_for_install_root_lock() {
+    use fs4::FileExt;
+
+    let (_tempdir, test_home) = test_utils::setup_test_environment();
+    for dir in ["bin", "lib", "include"] {
+        tokio::fs::create_dir_all(test_home.join("versions/0.14.1").join(dir))
+            .await
+            .unwrap();
+    }
+    let lock_path = test_home.join(wasmedgeup::lock::LOCK_FILE_NAME);
+    std::fs::write(&lock_path, "4242").unwrap();
+    let held = std::fs::File::open(&lock_path).unwrap();
+    FileExt::lock(&held).unwrap();
+
+    let args = || UseArgs {
+        version: "0.14.1".to_string(),
+        path: Some(test_home.clone()),
+    };
+    let err = args().execute(CommandContext::default()).await.unwrap_err();
+    assert!(
+        matches!(err, wasmedgeup::error::Error::InstallRootLocked { .. }),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("PID 4242"), "{err}");
+    assert!(!test_home.join("bin").exists());
+
+    let release = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        drop(held);
+    });
+    let ctx = CommandContext {
+        lock_wait: std::time::Duration::from_secs(10),
+        ..CommandContext::default()
+    };
+    args().execute(ctx).await.unwrap();
+    release.join().unwrap();
+    verify_symlinks(&test_home, "0.14.1").await;
+}